@@ -5,7 +5,7 @@
  * Handles UI interactions, Tauri IPC communication, and event handling.
  */
 
-import { invoke } from "@tauri-apps/api/core"
+import { convertFileSrc, invoke } from "@tauri-apps/api/core"
 import { listen } from "@tauri-apps/api/event"
 import { getCurrentWindow } from "@tauri-apps/api/window"
 
@@ -98,13 +98,26 @@ async function getReticle(): Promise<string> {
 	return await invoke("get_reticle")
 }
 
+async function getCrosshairUrl(filename: string): Promise<string> {
+	return await invoke("get_crosshair_url", { filename })
+}
+
+async function getShadowSettings(label: string): Promise<Preferences> {
+	return await invoke("get_shadow_settings", { label })
+}
+
 // ============================================================================
 // UI Updates
 // ============================================================================
 
-function updateCrosshairImage(filename: string): void {
-	// Use the crosshairs directory in public
-	crosshairImg.src = `/crosshairs/${filename}`
+async function updateCrosshairImage(filename: string): Promise<void> {
+	// Custom crosshairs live outside the bundle, so load through the asset protocol
+	try {
+		crosshairImg.src = await getCrosshairUrl(filename)
+	} catch (e) {
+		console.warn(`Failed to resolve crosshair: ${filename}`, e)
+		crosshairImg.src = `/crosshairs/${filename}`
+	}
 	crosshairImg.onerror = () => {
 		console.warn(`Failed to load crosshair: ${filename}`)
 		crosshairImg.src = "/crosshairs/crosshair-default.png"
@@ -341,28 +354,37 @@ async function setupEventListeners(): Promise<void> {
 	})
 
 	// Play sound
-	await listen<string>("play-sound", (event) => {
+	await listen<SoundEvent>("play-sound", (event) => {
 		playSound(event.payload)
 	})
 
 	// Sync settings (for shadow windows)
 	await listen<Preferences>("sync-settings", (event) => {
-		const prefs = event.payload
-		updateCrosshairImage(prefs.crosshair)
-		updateSize(prefs.size)
-		updateOpacity(prefs.opacity)
-		updateColor(prefs.color)
-		updateLockState(prefs.locked)
-        updateReticle(prefs.reticle)
+		applySettings(event.payload)
 	})
 }
 
+async function applySettings(prefs: Preferences): Promise<void> {
+	await updateCrosshairImage(prefs.crosshair)
+	updateSize(prefs.size)
+	updateOpacity(prefs.opacity)
+	updateColor(prefs.color)
+	updateLockState(prefs.locked)
+	updateReticle(prefs.reticle)
+}
+
 // ============================================================================
 // Sound Effects
 // ============================================================================
 
 const sounds: Record<string, HTMLAudioElement> = {}
 
+interface SoundEvent {
+	event: string
+	volume: number
+	custom_path: string | null
+}
+
 function preloadSounds(): void {
 	const soundFiles = ["lock", "unlock", "center"]
 	for (const name of soundFiles) {
@@ -372,9 +394,10 @@ function preloadSounds(): void {
 	}
 }
 
-function playSound(name: string): void {
-	const audio = sounds[name]
+function playSound({ event, volume, custom_path }: SoundEvent): void {
+	const audio = custom_path ? new Audio(convertFileSrc(custom_path)) : sounds[event]
 	if (audio) {
+		audio.volume = volume
 		audio.currentTime = 0
 		audio.play().catch(() => {
 			// Sound playback may be blocked by browser policies
@@ -405,6 +428,18 @@ function setupLocalKeyboardShortcuts(): void {
 // ============================================================================
 
 async function loadInitialState(): Promise<void> {
+	// Shadow windows fetch their own settings, including any overrides,
+	// since events sent while the page loads are missed
+	const label = getCurrentWindow().label
+	if (label.startsWith("shadow-")) {
+		try {
+			await applySettings(await getShadowSettings(label))
+		} catch (e) {
+			console.error("Failed to load shadow window settings:", e)
+		}
+		return
+	}
+
 	try {
         // Missing reticle in original destructuring?
 		const [crosshair, size, opacity, color, locked, reticle] = await Promise.all([
@@ -416,7 +451,7 @@ async function loadInitialState(): Promise<void> {
             getReticle(),
 		])
 
-		updateCrosshairImage(crosshair)
+		await updateCrosshairImage(crosshair)
 		updateSize(size)
 		updateOpacity(opacity)
 		updateColor(color)
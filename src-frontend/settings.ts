@@ -6,7 +6,6 @@
 import { invoke } from "@tauri-apps/api/core"
 import { listen } from "@tauri-apps/api/event"
 import { open as openDialog } from "@tauri-apps/plugin-dialog"
-import { enable, disable, isEnabled } from "@tauri-apps/plugin-autostart"
 
 // ============================================================================
 // DOM Elements
@@ -69,11 +68,11 @@ async function getColor(): Promise<string> {
 }
 
 async function centerWindow(): Promise<void> {
-	await invoke("center_window")
+	await invoke("center_window", { includeShadows: false })
 }
 
 async function moveToNextDisplay(): Promise<void> {
-	await invoke("move_to_next_display")
+	await invoke("move_to_next_display", { includeShadows: false })
 }
 
 async function getCrosshairList(): Promise<string[]> {
@@ -85,7 +84,7 @@ async function savePreferences(): Promise<void> {
 }
 
 async function resetPreferences(): Promise<void> {
-	await invoke("reset_preferences")
+	await invoke("reset_preferences", { resetKeybinds: true })
 }
 
 async function createShadowWindow(): Promise<string> {
@@ -109,6 +108,14 @@ async function setHideOnAds(hide: boolean): Promise<void> {
 	await invoke("set_hide_on_ads", { hide })
 }
 
+async function getStartOnBoot(): Promise<boolean> {
+	return await invoke("get_start_on_boot")
+}
+
+async function setStartOnBoot(enabled: boolean): Promise<void> {
+	await invoke("set_start_on_boot", { enabled })
+}
+
 async function getReticle(): Promise<string> {
 	return await invoke("get_reticle")
 }
@@ -153,6 +160,19 @@ function updateToggleUI(element: HTMLElement, checked: boolean): void {
 	}
 }
 
+interface AppError {
+	code: string
+	message: string
+	field?: string
+}
+
+function errorMessage(e: unknown): string {
+	if (typeof e === "object" && e !== null && "message" in e) {
+		return (e as AppError).message
+	}
+	return String(e)
+}
+
 function showToast(message: string, type: "info" | "success" | "error" = "info"): void {
 	const toast = document.createElement("div")
 	toast.className = `toast toast-${type}`
@@ -310,13 +330,8 @@ function setupEventListeners(): void {
 				const newState = !switchEl.classList.contains("checked")
 
 				try {
-					if (newState) {
-						await enable()
-						showToast("Start on Boot enabled", "success")
-					} else {
-						await disable()
-						showToast("Start on Boot disabled", "success")
-					}
+					await setStartOnBoot(newState)
+					showToast(`Start on Boot ${newState ? "enabled" : "disabled"}`, "success")
 					updateToggleUI(ctrlAutostart, newState)
 				} catch (e) {
 					console.error("Failed to toggle autostart:", e)
@@ -332,7 +347,7 @@ function setupEventListeners(): void {
 			await centerWindow()
 			showToast("Window centered", "success")
 		} catch (e) {
-			showToast(String(e), "error")
+			showToast(errorMessage(e), "error")
 		}
 	})
 
@@ -341,7 +356,7 @@ function setupEventListeners(): void {
 			await moveToNextDisplay()
 			showToast("Moved to next display", "success")
 		} catch (e) {
-			showToast(String(e), "error")
+			showToast(errorMessage(e), "error")
 		}
 	})
 
@@ -351,7 +366,7 @@ function setupEventListeners(): void {
 			await loadInitialState()
 			showToast("Settings reset to defaults", "success")
 		} catch (e) {
-			showToast(String(e), "error")
+			showToast(errorMessage(e), "error")
 		}
 	})
 
@@ -360,7 +375,7 @@ function setupEventListeners(): void {
 			await createShadowWindow()
 			showToast("Duplicate window created", "success")
 		} catch (e) {
-			showToast(String(e), "error")
+			showToast(errorMessage(e), "error")
 		}
 	})
 
@@ -428,7 +443,7 @@ async function loadInitialState(): Promise<void> {
 			getReticle(),
 			getFollowMouse(),
 			getHideOnAds(),
-			isEnabled(),
+			getStartOnBoot(),
 		])
 
 		updateSizeUI(size)
@@ -1,3 +1,95 @@
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
 fn main() {
+    // Build metadata for the `get_build_info` command
+    println!(
+        "cargo:rustc-env=CROSSOVER_TARGET_TRIPLE={}",
+        std::env::var("TARGET").unwrap_or_default()
+    );
+    println!("cargo:rustc-env=CROSSOVER_BUILD_DATE={}", build_date());
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
+    // Locked tray icon, embedded by `tray.rs` from OUT_DIR
+    println!("cargo:rerun-if-changed=icons/icon.png");
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    if let Err(e) = generate_locked_icon(
+        Path::new("icons/icon.png"),
+        &Path::new(&out_dir).join("icon-locked.png"),
+    ) {
+        panic!("Failed to generate locked tray icon: {}", e);
+    }
+
     tauri_build::build()
 }
+
+/// Write `icon-locked.png`: the base icon with a padlock in the bottom right
+fn generate_locked_icon(base: &Path, locked: &Path) -> Result<(), String> {
+    let mut icon = image::open(base)
+        .map_err(|e| format!("Failed to open {}: {}", base.display(), e))?
+        .to_rgba8();
+
+    // Padlock fills the bottom right 45% of the icon
+    let size = icon.width().min(icon.height()) as f64;
+    let side = size * 0.45;
+    let left = icon.width() as f64 - side;
+    let top = icon.height() as f64 - side;
+    let body_top = top + side * 0.45;
+    let shackle_x = left + side / 2.0;
+    let shackle_outer = side * 0.32;
+    let shackle_inner = side * 0.2;
+    let keyhole_y = body_top + (side - side * 0.45) / 2.0;
+    let keyhole_radius = side * 0.08;
+
+    for (x, y, pixel) in icon.enumerate_pixels_mut() {
+        let (px, py) = (x as f64 + 0.5, y as f64 + 0.5);
+        if px < left || py < top {
+            continue;
+        }
+
+        let in_body = py >= body_top;
+        let shackle_dist = ((px - shackle_x).powi(2) + (py - body_top).powi(2)).sqrt();
+        let in_shackle = py < body_top && (shackle_inner..=shackle_outer).contains(&shackle_dist);
+        let keyhole_dist = ((px - shackle_x).powi(2) + (py - keyhole_y).powi(2)).sqrt();
+
+        if in_body && keyhole_dist <= keyhole_radius {
+            *pixel = image::Rgba([40, 40, 40, 255]);
+        } else if in_body || in_shackle {
+            *pixel = image::Rgba([230, 70, 70, 255]);
+        }
+    }
+
+    icon.save(locked)
+        .map_err(|e| format!("Failed to write {}: {}", locked.display(), e))
+}
+
+/// Build date as `YYYY-MM-DD`, honoring `SOURCE_DATE_EPOCH` for reproducible builds
+fn build_date() -> String {
+    let secs = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0)
+        });
+
+    // Convert days since the epoch to a civil date
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
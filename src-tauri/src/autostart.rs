@@ -0,0 +1,38 @@
+//! Launch at login
+//!
+//! This module keeps the OS autostart registration made through
+//! `tauri-plugin-autostart` in step with the `start_on_boot` preference.
+
+use log::info;
+use tauri::AppHandle;
+use tauri_plugin_autostart::ManagerExt;
+
+/// Check whether the app is registered to launch at login
+pub fn is_enabled(app: &AppHandle) -> Result<bool, String> {
+    app.autolaunch()
+        .is_enabled()
+        .map_err(|e| format!("Failed to query autostart: {}", e))
+}
+
+/// Register or unregister the app to launch at login
+///
+/// Does nothing if the registration already matches.
+pub fn sync(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    if is_enabled(app)? == enabled {
+        return Ok(());
+    }
+
+    let autolaunch = app.autolaunch();
+    if enabled {
+        autolaunch.enable()
+    } else {
+        autolaunch.disable()
+    }
+    .map_err(|e| format!("Failed to update autostart: {}", e))?;
+
+    info!(
+        "Start on boot {}",
+        if enabled { "enabled" } else { "disabled" }
+    );
+    Ok(())
+}
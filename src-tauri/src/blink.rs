@@ -0,0 +1,57 @@
+//! Blink mode module
+//!
+//! This module drives the optional blink effect, where the crosshair
+//! periodically flashes on and off. The backend only controls timing by
+//! emitting `blink-on` / `blink-off` events; the frontend toggles the
+//! crosshair's visibility in response.
+
+use crate::config::MIN_BLINK_INTERVAL_MS;
+use crate::state::AppState;
+use log::info;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// Start or stop the blink task based on preferences
+///
+/// A running task is always restarted so that interval changes apply.
+pub fn update_blink_state(app: &AppHandle, state: Arc<AppState>) {
+    stop_blink(&state);
+
+    if state.get_blink_enabled() {
+        start_blink(app, state);
+    }
+}
+
+/// Start emitting blink events at the configured interval
+fn start_blink(app: &AppHandle, state: Arc<AppState>) {
+    // A zero interval makes tokio panic, so never trust the stored value
+    let interval_ms = state.get_blink_interval().max(MIN_BLINK_INTERVAL_MS);
+    let app_handle = app.clone();
+
+    info!("Starting blink mode ({} ms)", interval_ms);
+
+    let task = tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_millis(interval_ms));
+        let mut on = true;
+
+        loop {
+            interval.tick().await;
+            let event = if on { "blink-on" } else { "blink-off" };
+            if app_handle.emit(event, ()).is_err() {
+                break;
+            }
+            on = !on;
+        }
+    });
+
+    *state.blink_task.lock() = Some(task);
+}
+
+/// Cancel the blink task if it is running
+pub fn stop_blink(state: &AppState) {
+    if let Some(task) = state.blink_task.lock().take() {
+        task.abort();
+        info!("Blink mode stopped");
+    }
+}
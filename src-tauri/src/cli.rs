@@ -0,0 +1,159 @@
+//! Command line overrides
+//!
+//! Appearance flags passed at launch override the saved preferences for the
+//! session, e.g. `crossover --crosshair dot.png --opacity 0.5`. Parsing is
+//! kept free of Tauri so it can be tested without a runtime.
+
+use crate::config::{MAX_CROSSHAIR_SIZE, MIN_CROSSHAIR_SIZE};
+use crate::crosshair;
+use crate::state::AppState;
+use log::{info, warn};
+use serde::Serialize;
+use tauri::AppHandle;
+
+/// Overrides requested on the command line
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct StartupArgs {
+    pub crosshair: Option<String>,
+    pub opacity: Option<f64>,
+    pub size: Option<u32>,
+    pub profile: Option<String>,
+}
+
+impl StartupArgs {
+    /// Parse the process arguments, skipping the executable name
+    pub fn from_env() -> Self {
+        parse_args(std::env::args().skip(1))
+    }
+
+    /// Whether any override was given
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Apply the overrides on top of the loaded preferences
+    ///
+    /// The profile is loaded first so that the other flags take precedence.
+    /// Overrides only last for the session: saves write the values from
+    /// before them unless they are changed again, and the unsaved flag is
+    /// left as it was.
+    pub fn apply(&self, app: &AppHandle, state: &AppState) {
+        if self.is_empty() {
+            return;
+        }
+        let was_dirty = state.take_dirty();
+        let before = state.get_preferences();
+        if let Some(profile) = &self.profile {
+            match state.load_profile(profile) {
+                Ok(()) => info!("Loaded profile '{}' from the command line", profile),
+                Err(e) => warn!("Failed to load profile '{}': {}", profile, e),
+            }
+        }
+        if let Some(crosshair) = &self.crosshair {
+            if crosshair::crosshair_exists(app, crosshair) {
+                state.set_crosshair(crosshair.clone());
+            } else {
+                warn!("Ignoring unknown crosshair '{}'", crosshair);
+            }
+        }
+        if let Some(opacity) = self.opacity {
+            state.set_opacity(opacity);
+        }
+        if let Some(size) = self.size {
+            state.set_size(size.clamp(MIN_CROSSHAIR_SIZE, MAX_CROSSHAIR_SIZE));
+        }
+        if let Err(e) = state.set_session_overrides(&before) {
+            warn!(
+                "Failed to keep command line overrides out of storage: {}",
+                e
+            );
+        }
+        if was_dirty {
+            state.mark_dirty();
+        } else {
+            state.take_dirty();
+        }
+    }
+}
+
+/// Parse override flags, accepting both `--flag value` and `--flag=value`
+///
+/// Unknown arguments are ignored since other flags are handled elsewhere, and
+/// invalid values are logged and skipped.
+pub fn parse_args<I>(args: I) -> StartupArgs
+where
+    I: IntoIterator,
+    I::Item: Into<String>,
+{
+    let mut parsed = StartupArgs::default();
+    let mut args = args.into_iter().map(Into::into);
+
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+            None => (arg, None),
+        };
+        if !matches!(
+            flag.as_str(),
+            "--crosshair" | "--opacity" | "--size" | "--profile"
+        ) {
+            continue;
+        }
+
+        let Some(value) = inline.or_else(|| args.next()) else {
+            warn!("Missing value for {}", flag);
+            break;
+        };
+
+        match flag.as_str() {
+            "--crosshair" => parsed.crosshair = Some(value),
+            "--profile" => parsed.profile = Some(value),
+            "--opacity" => match value.parse::<f64>() {
+                Ok(opacity) if (0.0..=1.0).contains(&opacity) => parsed.opacity = Some(opacity),
+                _ => warn!("Ignoring invalid opacity '{}', expected 0.0-1.0", value),
+            },
+            "--size" => match value.parse::<u32>() {
+                Ok(size) if size > 0 => parsed.size = Some(size),
+                _ => warn!("Ignoring invalid size '{}'", value),
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    parsed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_args() {
+        let args = parse_args([
+            "--minimized",
+            "--crosshair",
+            "dot.png",
+            "--opacity=0.5",
+            "--size",
+            "48",
+            "--profile",
+            "Valorant",
+        ]);
+        assert_eq!(
+            args,
+            StartupArgs {
+                crosshair: Some("dot.png".to_string()),
+                opacity: Some(0.5),
+                size: Some(48),
+                profile: Some("Valorant".to_string()),
+            }
+        );
+        assert!(parse_args(["--portable"]).is_empty());
+    }
+
+    #[test]
+    fn test_parse_args_invalid_values() {
+        let args = parse_args(["--opacity", "2", "--size", "big", "--crosshair"]);
+        assert!(args.is_empty());
+    }
+}
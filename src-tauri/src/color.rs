@@ -0,0 +1,378 @@
+//! Color utilities
+//!
+//! This module handles validation and normalization of crosshair colors
+//! before they are stored in preferences or sent to the frontend.
+
+/// Formats the frontend color picker can show
+pub const COLOR_FORMATS: &[&str] = &["hex", "rgb", "hsl"];
+
+/// Validate a color string and normalize it to `#RRGGBB`
+///
+/// Accepts `#RGB`, `#RRGGBB` and `#RRGGBBAA` hex forms as well as CSS
+/// `rgb(r, g, b)` and `hsl(h, s%, l%)` strings. Shorthand values are
+/// expanded, the alpha channel is dropped and the result is uppercased.
+pub fn validate_color(color: &str) -> Result<String, String> {
+    let trimmed = color.trim();
+    let lower = trimmed.to_ascii_lowercase();
+    if let Some(args) = css_function_args(&lower, "rgb") {
+        let (r, g, b) = parse_css_rgb(args)
+            .ok_or_else(|| format!("Invalid color '{}': expected rgb(r, g, b)", color))?;
+        return Ok(rgb_to_hex(r, g, b));
+    }
+    if let Some(args) = css_function_args(&lower, "hsl") {
+        let (h, s, l) = parse_css_hsl(args)
+            .ok_or_else(|| format!("Invalid color '{}': expected hsl(h, s%, l%)", color))?;
+        let (r, g, b) = hsl_to_rgb(h, s, l);
+        return Ok(rgb_to_hex(r, g, b));
+    }
+
+    let hex = trimmed
+        .strip_prefix('#')
+        .ok_or_else(|| format!("Invalid color '{}': expected a leading '#'", color))?;
+
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("Invalid color '{}': not a hex value", color));
+    }
+
+    let rgb = match hex.len() {
+        3 => hex.chars().flat_map(|c| [c, c]).collect::<String>(),
+        6 => hex.to_string(),
+        8 => hex[..6].to_string(),
+        _ => {
+            return Err(format!(
+                "Invalid color '{}': expected #RGB, #RRGGBB or #RRGGBBAA",
+                color
+            ))
+        }
+    };
+
+    Ok(format!("#{}", rgb.to_uppercase()))
+}
+
+/// Parse a hex color into its red, green and blue components
+pub fn parse_rgb(color: &str) -> Result<(u8, u8, u8), String> {
+    let hex = validate_color(color)?;
+    let channel = |range: std::ops::Range<usize>| {
+        u8::from_str_radix(&hex[range], 16).map_err(|e| format!("Invalid color '{}': {}", color, e))
+    };
+
+    Ok((channel(1..3)?, channel(3..5)?, channel(5..7)?))
+}
+
+/// Check that a color format is one of [`COLOR_FORMATS`]
+pub fn validate_color_format(format: &str) -> Result<String, String> {
+    let format = format.trim().to_lowercase();
+    if COLOR_FORMATS.contains(&format.as_str()) {
+        Ok(format)
+    } else {
+        Err(format!(
+            "Unknown color format '{}': expected one of {}",
+            format,
+            COLOR_FORMATS.join(", ")
+        ))
+    }
+}
+
+/// Convert any supported color string to the given format
+///
+/// Hex output is `#RRGGBB`, RGB output is `rgb(r, g, b)` and HSL output is
+/// `hsl(h, s%, l%)` with whole-number components.
+pub fn convert_color(color: &str, format: &str) -> Result<String, String> {
+    let (r, g, b) = parse_rgb(color)?;
+    match validate_color_format(format)?.as_str() {
+        "rgb" => Ok(format!("rgb({}, {}, {})", r, g, b)),
+        "hsl" => {
+            let (h, s, l) = rgb_to_hsl(r, g, b);
+            Ok(format!(
+                "hsl({}, {}%, {}%)",
+                h.round() as u32 % 360,
+                s.round() as u32,
+                l.round() as u32
+            ))
+        }
+        _ => Ok(rgb_to_hex(r, g, b)),
+    }
+}
+
+/// Arguments of a CSS function call like `rgb(...)`, without the parentheses
+fn css_function_args<'a>(color: &'a str, name: &str) -> Option<&'a str> {
+    color
+        .strip_prefix(name)?
+        .trim_start()
+        .strip_prefix('(')?
+        .strip_suffix(')')
+}
+
+/// Parse the `r, g, b` arguments of a CSS `rgb()` color
+fn parse_css_rgb(args: &str) -> Option<(u8, u8, u8)> {
+    let channels = args
+        .split(',')
+        .map(|part| part.trim().parse::<u8>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    match channels[..] {
+        [r, g, b] => Some((r, g, b)),
+        _ => None,
+    }
+}
+
+/// Parse the `h, s%, l%` arguments of a CSS `hsl()` color
+fn parse_css_hsl(args: &str) -> Option<(f64, f64, f64)> {
+    let parts: Vec<&str> = args.split(',').map(str::trim).collect();
+    let [h, s, l] = parts[..] else {
+        return None;
+    };
+
+    let h = h
+        .strip_suffix("deg")
+        .unwrap_or(h)
+        .trim()
+        .parse::<f64>()
+        .ok()?;
+    let percent = |value: &str| {
+        value
+            .strip_suffix('%')?
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|v| (0.0..=100.0).contains(v))
+    };
+
+    h.is_finite()
+        .then_some((h.rem_euclid(360.0), percent(s)?, percent(l)?))
+}
+
+/// Convert HSL (hue in degrees, saturation and lightness in percent) to RGB
+pub fn hsl_to_rgb(h: f64, s: f64, l: f64) -> (u8, u8, u8) {
+    let s = s / 100.0;
+    let l = l / 100.0;
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let h = h.rem_euclid(360.0) / 60.0;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = l - c / 2.0;
+    let channel = |v: f64| ((v + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+    (channel(r), channel(g), channel(b))
+}
+
+/// Convert RGB to HSL (hue in degrees, saturation and lightness in percent)
+pub fn rgb_to_hsl(r: u8, g: u8, b: u8) -> (f64, f64, f64) {
+    let (r, g, b) = (r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let d = max - min;
+    if d == 0.0 {
+        return (0.0, 0.0, l * 100.0);
+    }
+
+    let s = d / (1.0 - (2.0 * l - 1.0).abs());
+    let h = if max == r {
+        60.0 * ((g - b) / d).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / d + 2.0)
+    } else {
+        60.0 * ((r - g) / d + 4.0)
+    };
+    (h, s * 100.0, l * 100.0)
+}
+
+/// Format color components as `#RRGGBB`
+pub fn rgb_to_hex(r: u8, g: u8, b: u8) -> String {
+    format!("#{:02X}{:02X}{:02X}", r, g, b)
+}
+
+/// Read the color of the screen pixel at physical coordinates `(x, y)`
+pub fn sample_screen_color(x: i32, y: i32) -> Result<String, String> {
+    let (r, g, b) = sample_screen_pixel(x, y)?;
+    Ok(rgb_to_hex(r, g, b))
+}
+
+#[cfg(target_os = "windows")]
+fn sample_screen_pixel(x: i32, y: i32) -> Result<(u8, u8, u8), String> {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::Graphics::Gdi::{GetDC, GetPixel, ReleaseDC, CLR_INVALID};
+
+    unsafe {
+        let hdc = GetDC(HWND::default());
+        if hdc.is_invalid() {
+            return Err("Failed to get screen device context".to_string());
+        }
+
+        let pixel = GetPixel(hdc, x, y);
+        ReleaseDC(HWND::default(), hdc);
+
+        if pixel.0 == CLR_INVALID {
+            return Err(format!("Failed to read pixel at ({}, {})", x, y));
+        }
+
+        // COLORREF is 0x00BBGGRR
+        Ok((
+            (pixel.0 & 0xFF) as u8,
+            ((pixel.0 >> 8) & 0xFF) as u8,
+            ((pixel.0 >> 16) & 0xFF) as u8,
+        ))
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn sample_screen_pixel(x: i32, y: i32) -> Result<(u8, u8, u8), String> {
+    use core_graphics::display::{
+        kCGNullWindowID, kCGWindowImageDefault, kCGWindowListOptionOnScreenOnly, CGDisplay,
+    };
+    use core_graphics::geometry::{CGPoint, CGRect, CGSize};
+
+    let bounds = CGRect::new(&CGPoint::new(x as f64, y as f64), &CGSize::new(1.0, 1.0));
+    let image = CGDisplay::screenshot(
+        bounds,
+        kCGWindowListOptionOnScreenOnly,
+        kCGNullWindowID,
+        kCGWindowImageDefault,
+    )
+    .ok_or_else(|| format!("Failed to capture pixel at ({}, {})", x, y))?;
+
+    // Screen captures are 32-bit BGRA
+    let data = image.data();
+    match data.bytes() {
+        [b, g, r, ..] => Ok((*r, *g, *b)),
+        _ => Err(format!("Failed to read pixel at ({}, {})", x, y)),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn sample_screen_pixel(x: i32, y: i32) -> Result<(u8, u8, u8), String> {
+    use x11::xlib;
+
+    unsafe {
+        let display = xlib::XOpenDisplay(std::ptr::null());
+        if display.is_null() {
+            return Err("Failed to open X11 display".to_string());
+        }
+
+        // XGetImage outside the root window is a BadMatch error
+        let screen = xlib::XDefaultScreen(display);
+        let (width, height) = (
+            xlib::XDisplayWidth(display, screen),
+            xlib::XDisplayHeight(display, screen),
+        );
+        if !(0..width).contains(&x) || !(0..height).contains(&y) {
+            xlib::XCloseDisplay(display);
+            return Err(format!(
+                "Pixel ({}, {}) is outside the {}x{} screen",
+                x, y, width, height
+            ));
+        }
+
+        let root = xlib::XDefaultRootWindow(display);
+        let image = crate::x11_errors::trap_errors(display, || {
+            xlib::XGetImage(display, root, x, y, 1, 1, !0, xlib::ZPixmap)
+        });
+        let image = match image {
+            Ok(image) if !image.is_null() => image,
+            _ => {
+                xlib::XCloseDisplay(display);
+                return Err(format!("Failed to read pixel at ({}, {})", x, y));
+            }
+        };
+
+        // Assumes the common 24-bit TrueColor visual
+        let pixel = xlib::XGetPixel(image, 0, 0);
+        xlib::XDestroyImage(image);
+        xlib::XCloseDisplay(display);
+
+        Ok((
+            ((pixel >> 16) & 0xFF) as u8,
+            ((pixel >> 8) & 0xFF) as u8,
+            (pixel & 0xFF) as u8,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rgb_to_hex() {
+        assert_eq!(rgb_to_hex(255, 128, 0), "#FF8000");
+        assert_eq!(rgb_to_hex(0, 0, 0), "#000000");
+    }
+
+    #[test]
+    fn test_sample_screen_color_format() {
+        // Sampling needs a display, which isn't available on headless CI
+        if let Ok(color) = sample_screen_color(0, 0) {
+            assert_eq!(color.len(), 7);
+            assert!(color.starts_with('#'));
+            assert_eq!(validate_color(&color).unwrap(), color);
+        }
+    }
+
+    #[test]
+    fn test_parse_rgb() {
+        assert_eq!(parse_rgb("#FF8000").unwrap(), (255, 128, 0));
+        assert_eq!(parse_rgb("#0f0").unwrap(), (0, 255, 0));
+        assert!(parse_rgb("green").is_err());
+    }
+
+    #[test]
+    fn test_validate_color_short_form() {
+        assert_eq!(validate_color("#0f0").unwrap(), "#00FF00");
+        assert_eq!(validate_color("#ABC").unwrap(), "#AABBCC");
+    }
+
+    #[test]
+    fn test_validate_color_long_form() {
+        assert_eq!(validate_color("#00ff00").unwrap(), "#00FF00");
+        assert_eq!(validate_color(" #1a2B3c ").unwrap(), "#1A2B3C");
+    }
+
+    #[test]
+    fn test_validate_color_with_alpha() {
+        assert_eq!(validate_color("#ff000080").unwrap(), "#FF0000");
+    }
+
+    #[test]
+    fn test_validate_color_rgb() {
+        assert_eq!(validate_color("rgb(255, 0, 0)").unwrap(), "#FF0000");
+        assert_eq!(validate_color(" RGB(0,128,255) ").unwrap(), "#0080FF");
+        assert!(validate_color("rgb(256, 0, 0)").is_err());
+        assert!(validate_color("rgb(1, 2)").is_err());
+    }
+
+    #[test]
+    fn test_validate_color_hsl() {
+        assert_eq!(validate_color("hsl(120, 100%, 50%)").unwrap(), "#00FF00");
+        assert_eq!(validate_color("hsl(0, 0%, 100%)").unwrap(), "#FFFFFF");
+        assert_eq!(validate_color("hsl(-120, 100%, 50%)").unwrap(), "#0000FF");
+        assert!(validate_color("hsl(120, 100, 50)").is_err());
+        assert!(validate_color("hsl(120, 150%, 50%)").is_err());
+    }
+
+    #[test]
+    fn test_convert_color() {
+        assert_eq!(convert_color("#FF8000", "rgb").unwrap(), "rgb(255, 128, 0)");
+        assert_eq!(
+            convert_color("#00FF00", "hsl").unwrap(),
+            "hsl(120, 100%, 50%)"
+        );
+        assert_eq!(convert_color("rgb(0, 0, 255)", "HEX").unwrap(), "#0000FF");
+        assert!(convert_color("#00FF00", "cmyk").is_err());
+    }
+
+    #[test]
+    fn test_validate_color_invalid() {
+        assert!(validate_color("").is_err());
+        assert!(validate_color("00FF00").is_err());
+        assert!(validate_color("#GG0000").is_err());
+        assert!(validate_color("#1234").is_err());
+        assert!(validate_color("#1234567").is_err());
+        assert!(validate_color("red").is_err());
+    }
+}
@@ -2,8 +2,22 @@
 //!
 //! These commands are exposed to the JavaScript frontend via Tauri's invoke system.
 
-use crate::state::AppState;
+use crate::cli::StartupArgs;
+use crate::config::{
+    ColorBlindnessPreset, Theme, DEFAULT_WINDOW_HEIGHT, DEFAULT_WINDOW_WIDTH,
+    MAX_HIDE_ON_ADS_DELAY_MS, MAX_MOUSE_FOLLOW_FPS, MAX_MOVE_STEP_PX, MIN_MOUSE_FOLLOW_FPS,
+    MIN_MOVE_STEP_PX, RATE_LIMIT_CREATE_SHADOW_WINDOW, RATE_LIMIT_SET_COLOR,
+    RATE_LIMIT_SET_OPACITY, RATE_LIMIT_SET_SIZE,
+};
+use crate::error::AppError;
+use crate::ipc::IpcServerStatus;
+use crate::state::{
+    AppState, CrosshairEffect, CrosshairOffset, FollowMouseConfig, LockMode, PatchPreferences,
+    Preferences, ProcessRule, ShadowWindowOptions,
+};
 use crate::window;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 use tauri::{command, AppHandle, Emitter, Manager};
 
@@ -13,12 +27,17 @@ pub async fn set_crosshair(
     app: AppHandle,
     state: tauri::State<'_, Arc<AppState>>,
     crosshair: String,
-) -> Result<(), String> {
-    state.set_crosshair(crosshair.clone());
+) -> Result<(), AppError> {
+    crate::crosshair::validate_crosshair(&app, &crosshair).map_err(AppError::NotFound)?;
+    apply_crosshair(&app, &state, crosshair).map_err(AppError::StateError)
+}
 
-    // Emit event to all windows to update crosshair
-    app.emit("crosshair-changed", &crosshair)
-        .map_err(|e| e.to_string())?;
+/// Switch the crosshair, updating the tray and notifying all windows
+pub fn apply_crosshair(app: &AppHandle, state: &AppState, crosshair: String) -> Result<(), String> {
+    if state.set_crosshair(crosshair) {
+        crate::tray::rebuild_tray_menu(app)?;
+    }
+    crate::tray::update_tray_tooltip(app)?;
 
     Ok(())
 }
@@ -29,19 +48,115 @@ pub fn get_crosshair(state: tauri::State<'_, Arc<AppState>>) -> String {
     state.get_crosshair()
 }
 
+/// Mark a crosshair as a favorite
+#[command]
+pub fn add_crosshair_favorite(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    filename: String,
+) -> Result<(), AppError> {
+    crate::crosshair::validate_crosshair(&app, &filename)?;
+    state.add_crosshair_favorite(&filename);
+    Ok(())
+}
+
+/// Unmark a favorite crosshair
+#[command]
+pub fn remove_crosshair_favorite(
+    state: tauri::State<'_, Arc<AppState>>,
+    filename: String,
+) -> Result<(), AppError> {
+    if state.remove_crosshair_favorite(&filename) {
+        Ok(())
+    } else {
+        Err(AppError::NotFound(format!(
+            "Crosshair is not a favorite: {}",
+            filename
+        )))
+    }
+}
+
+/// Get the favorite crosshairs sorted by filename
+#[command]
+pub fn get_crosshair_favorites(state: tauri::State<'_, Arc<AppState>>) -> Vec<String> {
+    state.get_crosshair_favorites()
+}
+
+/// Check if a crosshair is a favorite
+#[command]
+pub fn is_crosshair_favorite(state: tauri::State<'_, Arc<AppState>>, filename: String) -> bool {
+    state.is_crosshair_favorite(&filename)
+}
+
+/// Flip whether a crosshair is a favorite, returning the new state
+///
+/// Only existing crosshairs can be added; removing always works so stale
+/// favorites can be cleaned up.
+#[command]
+pub fn toggle_crosshair_favorite(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    filename: String,
+) -> Result<bool, AppError> {
+    if !state.is_crosshair_favorite(&filename) {
+        crate::crosshair::validate_crosshair(&app, &filename).map_err(AppError::NotFound)?;
+    }
+    Ok(state.toggle_crosshair_favorite(&filename))
+}
+
+/// Get the crosshair history, oldest first
+#[command]
+pub fn get_crosshair_history(state: tauri::State<'_, Arc<AppState>>) -> Vec<String> {
+    state.get_crosshair_history()
+}
+
+/// Go back to the previously selected crosshair
+///
+/// Returns the crosshair switched to, or `None` at the start of the history.
+#[command]
+pub async fn prev_crosshair_from_history(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+) -> Result<Option<String>, AppError> {
+    let Some(crosshair) = state.prev_crosshair_from_history() else {
+        return Ok(None);
+    };
+    apply_crosshair(&app, &state, crosshair.clone())?;
+    Ok(Some(crosshair))
+}
+
+/// Go forward to the crosshair selected after the current one
+///
+/// Returns the crosshair switched to, or `None` at the end of the history.
+#[command]
+pub async fn next_crosshair_from_history(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+) -> Result<Option<String>, AppError> {
+    let Some(crosshair) = state.next_crosshair_from_history() else {
+        return Ok(None);
+    };
+    apply_crosshair(&app, &state, crosshair.clone())?;
+    Ok(Some(crosshair))
+}
+
+/// Get recently selected crosshairs, most recent first
+#[command]
+pub fn get_recent_crosshairs(state: tauri::State<'_, Arc<AppState>>) -> Vec<String> {
+    state.get_recent_crosshairs()
+}
+
 /// Set the crosshair opacity
 #[command]
 pub async fn set_opacity(
-    app: AppHandle,
     state: tauri::State<'_, Arc<AppState>>,
     opacity: f64,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
+    if !state.check_rate_limit("set_opacity", RATE_LIMIT_SET_OPACITY) {
+        return Err(AppError::StateError("Rate limit exceeded".to_string()));
+    }
     state.set_opacity(opacity);
 
-    // Emit event to all windows to update opacity
-    app.emit("opacity-changed", opacity)
-        .map_err(|e| e.to_string())?;
-
     Ok(())
 }
 
@@ -57,11 +172,15 @@ pub async fn set_size(
     app: AppHandle,
     state: tauri::State<'_, Arc<AppState>>,
     size: u32,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
+    if !state.check_rate_limit("set_size", RATE_LIMIT_SET_SIZE) {
+        return Err(AppError::StateError("Rate limit exceeded".to_string()));
+    }
     state.set_size(size);
 
-    // Emit event to all windows to update size
-    app.emit("size-changed", size).map_err(|e| e.to_string())?;
+    if state.get_auto_resize_window() {
+        window::resize_to_crosshair(&app, size)?;
+    }
 
     Ok(())
 }
@@ -75,15 +194,15 @@ pub fn get_size(state: tauri::State<'_, Arc<AppState>>) -> u32 {
 /// Set the crosshair color
 #[command]
 pub async fn set_color(
-    app: AppHandle,
     state: tauri::State<'_, Arc<AppState>>,
     color: String,
-) -> Result<(), String> {
-    state.set_color(color.clone());
-
-    // Emit event to all windows to update color
-    app.emit("color-changed", &color)
-        .map_err(|e| e.to_string())?;
+) -> Result<(), AppError> {
+    if !state.check_rate_limit("set_color", RATE_LIMIT_SET_COLOR) {
+        return Err(AppError::StateError("Rate limit exceeded".to_string()));
+    }
+    let color =
+        crate::color::validate_color(&color).map_err(|e| AppError::validation("color", e))?;
+    state.set_color(color);
 
     Ok(())
 }
@@ -94,12 +213,72 @@ pub fn get_color(state: tauri::State<'_, Arc<AppState>>) -> String {
     state.get_color()
 }
 
+/// Set the color format shown by the settings color picker (hex, rgb or hsl)
+#[command]
+pub fn set_color_format(
+    state: tauri::State<'_, Arc<AppState>>,
+    format: String,
+) -> Result<(), AppError> {
+    let format = crate::color::validate_color_format(&format)
+        .map_err(|e| AppError::validation("format", e))?;
+    state.set_color_format(format);
+    Ok(())
+}
+
+/// Get the color format shown by the settings color picker
+#[command]
+pub fn get_color_format(state: tauri::State<'_, Arc<AppState>>) -> String {
+    state.get_color_format()
+}
+
+/// Convert a hex, `rgb()` or `hsl()` color string to another format
+#[command]
+pub fn convert_color(input: String, format: String) -> Result<String, AppError> {
+    let format = crate::color::validate_color_format(&format)
+        .map_err(|e| AppError::validation("format", e))?;
+    crate::color::convert_color(&input, &format).map_err(|e| AppError::validation("input", e))
+}
+
+/// A color blindness preset and the color it applies
+#[derive(Debug, Clone, Serialize)]
+pub struct ColorPresetInfo {
+    pub name: String,
+    pub color: String,
+}
+
+/// Set the crosshair color recommended for a color vision deficiency
+#[command]
+pub async fn apply_color_blindness_preset(
+    state: tauri::State<'_, Arc<AppState>>,
+    preset: String,
+) -> Result<(), AppError> {
+    let color = preset
+        .parse::<ColorBlindnessPreset>()
+        .map_err(|e| AppError::validation("preset", e))?
+        .color()
+        .to_string();
+    state.set_color(color);
+    Ok(())
+}
+
+/// List the color blindness presets
+#[command]
+pub fn list_color_blindness_presets() -> Vec<ColorPresetInfo> {
+    ColorBlindnessPreset::ALL
+        .iter()
+        .map(|preset| ColorPresetInfo {
+            name: preset.as_str().to_string(),
+            color: preset.color().to_string(),
+        })
+        .collect()
+}
+
 /// Toggle the window lock state
 #[command]
 pub async fn toggle_lock(
     app: AppHandle,
     state: tauri::State<'_, Arc<AppState>>,
-) -> Result<bool, String> {
+) -> Result<bool, AppError> {
     let locked = state.toggle_locked();
 
     // Get main window and update ignore mouse events
@@ -115,8 +294,7 @@ pub async fn toggle_lock(
     }
 
     // Emit event to all windows
-    app.emit("lock-changed", locked)
-        .map_err(|e| e.to_string())?;
+    crate::tray::emit_lock_changed(&app, locked)?;
 
     Ok(locked)
 }
@@ -127,292 +305,1869 @@ pub fn is_locked(state: tauri::State<'_, Arc<AppState>>) -> bool {
     state.is_locked()
 }
 
+/// Set the lock mode: `unlocked`, `click_through` or `locked`
+#[command]
+pub async fn set_lock_mode(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    mode: String,
+) -> Result<(), AppError> {
+    let mode = mode.parse::<LockMode>()?;
+    apply_preferences_patch(
+        &app,
+        state.inner(),
+        PatchPreferences {
+            lock_mode: Some(mode),
+            ..Default::default()
+        },
+    )
+    .map_err(AppError::StateError)
+}
+
+/// Get the lock mode
+#[command]
+pub fn get_lock_mode(state: tauri::State<'_, Arc<AppState>>) -> String {
+    state.get_lock_mode().as_str().to_string()
+}
+
 /// Center the window on the current display
+///
+/// With `include_shadows`, each shadow window is centered on its own monitor.
 #[command]
-pub async fn center_window(app: AppHandle) -> Result<(), String> {
+pub async fn center_window(app: AppHandle, include_shadows: bool) -> Result<(), AppError> {
     if let Some(window) = app.get_webview_window("main") {
         window.center().map_err(|e| e.to_string())?;
     }
+    if include_shadows {
+        window::center_shadow_windows(&app)?;
+    }
     Ok(())
 }
 
+/// Move the window back to where it was before the last move
+#[command]
+pub async fn undo_position(app: AppHandle) -> Result<(), AppError> {
+    window::step_position_history(&app, true).map_err(AppError::WindowError)
+}
+
+/// Reapply the last undone move
+#[command]
+pub async fn redo_position(app: AppHandle) -> Result<(), AppError> {
+    window::step_position_history(&app, false).map_err(AppError::WindowError)
+}
+
 /// Move the window to the next display
+///
+/// With `include_shadows`, shadow windows move too, keeping their offset.
+/// When the main window wraps back to the first monitor and
+/// `sync_shadows_to_main_monitor` is on, shadow windows are centered there too.
 #[command]
-pub async fn move_to_next_display(app: AppHandle) -> Result<(), String> {
-    if let Some(window) = app.get_webview_window("main") {
-        window::move_to_next_display(&window)?;
-    }
-    Ok(())
+pub async fn move_to_next_display(app: AppHandle, include_shadows: bool) -> Result<(), AppError> {
+    window::move_main_to_next_display(&app, include_shadows).map_err(AppError::WindowError)
 }
 
-/// Toggle window visibility
+/// Use a crosshair whenever the window moves onto a monitor
 #[command]
-pub async fn toggle_visibility(
+pub async fn set_monitor_crosshair(
     app: AppHandle,
     state: tauri::State<'_, Arc<AppState>>,
-) -> Result<bool, String> {
-    let visible = state.toggle_visible();
-
-    // Get main window and update visibility
-    if let Some(window) = app.get_webview_window("main") {
-        if visible {
-            window.show().map_err(|e| e.to_string())?;
-        } else {
-            window.hide().map_err(|e| e.to_string())?;
-        }
-    }
-
-    // Update all shadow windows
-    for label in state.get_shadow_windows() {
-        if let Some(window) = app.get_webview_window(&label) {
-            if visible {
-                window.show().map_err(|e| e.to_string())?;
-            } else {
-                window.hide().map_err(|e| e.to_string())?;
-            }
-        }
+    monitor_name: String,
+    crosshair: String,
+) -> Result<(), AppError> {
+    let window = app
+        .get_webview_window("main")
+        .ok_or_else(|| AppError::not_found("Main window not found"))?;
+    if !window::get_display_info(&window)?
+        .iter()
+        .any(|display| display.name == monitor_name)
+    {
+        return Err(AppError::NotFound(format!(
+            "Unknown monitor: {}",
+            monitor_name
+        )));
     }
+    crate::crosshair::validate_crosshair(&app, &crosshair)?;
 
-    // Emit event to all windows
-    app.emit("visibility-changed", visible)
-        .map_err(|e| e.to_string())?;
+    state.set_monitor_crosshair(monitor_name, crosshair);
+    Ok(())
+}
 
-    Ok(visible)
+/// Get the crosshair used on a monitor, if any
+#[command]
+pub fn get_monitor_crosshair(
+    state: tauri::State<'_, Arc<AppState>>,
+    monitor_name: String,
+) -> Option<String> {
+    state.get_monitor_crosshair(&monitor_name)
 }
 
-/// Check if the window is visible
+/// Stop switching crosshairs when moving onto a monitor
 #[command]
-pub fn is_visible(state: tauri::State<'_, Arc<AppState>>) -> bool {
-    state.is_visible()
+pub fn clear_monitor_crosshair(state: tauri::State<'_, Arc<AppState>>, monitor_name: String) {
+    state.clear_monitor_crosshair(&monitor_name);
 }
 
-/// Get list of available crosshair images
+/// Enable or disable launching at login
 #[command]
-pub async fn get_crosshair_list(app: AppHandle) -> Result<Vec<String>, String> {
-    let mut crosshairs = Vec::new();
-    let file_extensions = ["png", "svg", "gif", "jpg", "jpeg", "webp"];
-
-    // Helper to read directory
-    let read_dir = |path: std::path::PathBuf, list: &mut Vec<String>| {
-        if let Ok(entries) = std::fs::read_dir(&path) {
-            for entry in entries.flatten() {
-                if let Some(name) = entry.file_name().to_str() {
-                    let lower_name = name.to_lowercase();
-                    if file_extensions.iter().any(|ext| lower_name.ends_with(ext)) {
-                        list.push(name.to_string());
-                    }
-                }
-            }
-        }
-    };
+pub async fn set_start_on_boot(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    enabled: bool,
+) -> Result<(), AppError> {
+    crate::autostart::sync(&app, enabled)?;
+    state.set_start_on_boot(enabled);
+    state.save_preferences(&app).map_err(AppError::IoError)
+}
 
-    // 1. Resource directory
-    if let Ok(resource_path) = app.path().resource_dir() {
-        read_dir(resource_path.join("crosshairs"), &mut crosshairs);
+/// Check whether the app is registered to launch at login
+#[command]
+pub async fn get_start_on_boot(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+) -> Result<bool, AppError> {
+    let enabled = crate::autostart::is_enabled(&app)?;
+    if enabled != state.get_start_on_boot() {
+        // The registration was changed outside the app
+        state.set_start_on_boot(enabled);
     }
+    Ok(enabled)
+}
 
-    // 2. App Data directory (UserData)
-    if let Ok(app_data_path) = app.path().app_data_dir() {
-        read_dir(app_data_path.join("crosshairs"), &mut crosshairs);
-    }
+/// Get metadata for all connected displays
+#[command]
+pub async fn get_display_info(app: AppHandle) -> Result<Vec<window::DisplayInfo>, AppError> {
+    let window = app
+        .get_webview_window("main")
+        .ok_or_else(|| AppError::not_found("Main window not found"))?;
+    window::get_display_info(&window).map_err(AppError::WindowError)
+}
 
-    // Sort alphabetically and deduplicate behavior if needed (names are unique keys in frontend usually)
-    crosshairs.sort();
-    crosshairs.dedup(); // In case name collides, though filesystem usually prevents exact collisions in same dir.
-                        // Here we might have collision between resource and app_data.
-                        // If we have same filename in both, frontend will probably just pick one by URL path logic.
-                        // Ideally we might want to prioritize one, but simple dedup is fine for now.
+/// Compile-time details about the running build
+#[derive(Debug, Clone, Serialize)]
+pub struct BuildInfo {
+    pub version: String,
+    pub build_date: String,
+    pub target_triple: String,
+    pub debug: bool,
+}
 
-    Ok(crosshairs)
+/// Get the running app version
+#[command]
+pub fn get_app_version() -> String {
+    env!("CARGO_PKG_VERSION").to_string()
 }
 
-/// Save current preferences to disk
+/// Get the version, build date, target and profile of the running build
 #[command]
-pub async fn save_preferences(
-    app: AppHandle,
-    state: tauri::State<'_, Arc<AppState>>,
-) -> Result<(), String> {
-    state.save_preferences(&app)
+pub fn get_build_info() -> BuildInfo {
+    BuildInfo {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        build_date: env!("CROSSOVER_BUILD_DATE").to_string(),
+        target_triple: env!("CROSSOVER_TARGET_TRIPLE").to_string(),
+        debug: cfg!(debug_assertions),
+    }
 }
 
-/// Load preferences from disk
+/// Platform details for troubleshooting overlay issues
+#[derive(Debug, Clone, Serialize)]
+pub struct PlatformInfo {
+    pub os: String,
+    pub os_version: String,
+    pub is_wayland: bool,
+    pub is_x11: bool,
+    pub primary_monitor_dpi: f64,
+    pub scale_factor: f64,
+}
+
+/// Get the OS, display server and DPI details
 #[command]
-pub async fn load_preferences(
-    app: AppHandle,
-    state: tauri::State<'_, Arc<AppState>>,
-) -> Result<(), String> {
-    state.load_preferences(&app)
+pub async fn get_platform_info(app: AppHandle) -> Result<PlatformInfo, AppError> {
+    let window = app
+        .get_webview_window("main")
+        .ok_or_else(|| AppError::not_found("Main window not found"))?;
+
+    let scale_factor = window
+        .scale_factor()
+        .map_err(|e| format!("Failed to get scale factor: {}", e))?;
+    let primary_scale = window
+        .primary_monitor()
+        .map_err(|e| format!("Failed to get primary monitor: {}", e))?
+        .map(|monitor| monitor.scale_factor())
+        .unwrap_or(scale_factor);
+
+    // Wayland sessions set WAYLAND_DISPLAY, X11 (including XWayland) sets DISPLAY
+    let is_linux = cfg!(target_os = "linux");
+    let is_wayland = is_linux && std::env::var_os("WAYLAND_DISPLAY").is_some();
+    let is_x11 = is_linux && !is_wayland && std::env::var_os("DISPLAY").is_some();
+
+    Ok(PlatformInfo {
+        os: tauri_plugin_os::platform().to_string(),
+        os_version: tauri_plugin_os::version().to_string(),
+        is_wayland,
+        is_x11,
+        // 96 DPI is the baseline for a scale factor of 1.0
+        primary_monitor_dpi: primary_scale * 96.0,
+        scale_factor,
+    })
 }
 
-/// Reset preferences to defaults
+/// Set the overlay window level for all windows
 #[command]
-pub async fn reset_preferences(
+pub async fn set_window_level(
     app: AppHandle,
     state: tauri::State<'_, Arc<AppState>>,
-) -> Result<(), String> {
-    state.reset_preferences();
+    level: u8,
+) -> Result<(), AppError> {
+    state.set_window_level(level)?;
 
-    // Emit events to update UI
-    let prefs = state.get_preferences();
-    app.emit("crosshair-changed", &prefs.crosshair)
-        .map_err(|e| e.to_string())?;
-    app.emit("opacity-changed", prefs.opacity)
-        .map_err(|e| e.to_string())?;
-    app.emit("size-changed", prefs.size)
-        .map_err(|e| e.to_string())?;
-    app.emit("color-changed", &prefs.color)
-        .map_err(|e| e.to_string())?;
-    app.emit("reticle-changed", &prefs.reticle) // Add this
-        .map_err(|e| e.to_string())?;
-    // No event for hide_on_ads as it's just a setting
+    for window in app.webview_windows().values() {
+        window::apply_window_level(window, level)?;
+    }
 
+    app.emit("window-level-changed", level)
+        .map_err(|e| e.to_string())?;
     Ok(())
 }
 
-/// Set follow mouse mode
+/// Get the overlay window level
 #[command]
-pub async fn set_follow_mouse(
+pub fn get_window_level(state: tauri::State<'_, Arc<AppState>>) -> u8 {
+    state.get_window_level()
+}
+
+/// Optional overlay features available on this platform
+#[derive(Debug, Clone, Serialize)]
+pub struct PlatformCapability {
+    pub dx_overlay_supported: bool,
+}
+
+/// Get the optional overlay features the frontend can offer
+#[command]
+pub fn get_platform_capabilities() -> PlatformCapability {
+    PlatformCapability {
+        dx_overlay_supported: window::dx_overlay_supported(),
+    }
+}
+
+/// Enable or disable DirectX fullscreen overlay mode
+#[command]
+pub async fn set_use_dx_overlay(
     app: AppHandle,
     state: tauri::State<'_, Arc<AppState>>,
-    follow: bool,
-) -> Result<(), String> {
-    state.set_follow_mouse(follow);
-    crate::mouse::update_mouse_listener_state(&app, state.inner().clone())?;
+    enabled: bool,
+) -> Result<(), AppError> {
+    let window = app
+        .get_webview_window("main")
+        .ok_or_else(|| AppError::not_found("Main window not found"))?;
+    window::apply_dx_overlay(&window, enabled)?;
+    state.set_use_dx_overlay(enabled);
     Ok(())
 }
 
-/// Get follow mouse state
+/// Check if DirectX fullscreen overlay mode is enabled
 #[command]
-pub fn get_follow_mouse(state: tauri::State<'_, Arc<AppState>>) -> bool {
-    state.get_follow_mouse()
+pub fn get_use_dx_overlay(state: tauri::State<'_, Arc<AppState>>) -> bool {
+    state.get_use_dx_overlay()
 }
 
-/// Set hide on ADS mode
+/// Move the window to a specific display
 #[command]
-pub async fn set_hide_on_ads(
-    app: AppHandle,
-    state: tauri::State<'_, Arc<AppState>>,
-    hide: bool,
-) -> Result<(), String> {
-    state.set_hide_on_ads(hide);
-    crate::mouse::update_mouse_listener_state(&app, state.inner().clone())?;
+pub async fn move_to_display(app: AppHandle, index: usize) -> Result<(), AppError> {
+    if let Some(window) = app.get_webview_window("main") {
+        window::move_to_display(&window, index)?;
+    }
     Ok(())
 }
 
-/// Get hide on ADS state
+/// Set whether the crosshair re-centers when displays change
 #[command]
-pub fn get_hide_on_ads(state: tauri::State<'_, Arc<AppState>>) -> bool {
-    state.get_hide_on_ads()
+pub fn set_auto_center_on_display_change(state: tauri::State<'_, Arc<AppState>>, enabled: bool) {
+    state.set_auto_center_on_display_change(enabled);
 }
 
-/// Set the reticle type
+/// Get whether the crosshair re-centers when displays change
 #[command]
-pub async fn set_reticle(
+pub fn get_auto_center_on_display_change(state: tauri::State<'_, Arc<AppState>>) -> bool {
+    state.get_auto_center_on_display_change()
+}
+
+/// Get the position and size of the main window or a shadow window
+#[command]
+pub async fn get_window_bounds(
+    app: AppHandle,
+    label: String,
+) -> Result<window::WindowBounds, AppError> {
+    let window = app
+        .get_webview_window(&label)
+        .ok_or_else(|| AppError::NotFound(format!("Window not found: {}", label)))?;
+    window::get_window_bounds(&window).map_err(AppError::WindowError)
+}
+
+/// Set the position and size of the main window or a shadow window
+#[command]
+pub async fn set_window_bounds(
+    app: AppHandle,
+    label: String,
+    bounds: window::WindowBounds,
+) -> Result<(), AppError> {
+    let window = app
+        .get_webview_window(&label)
+        .ok_or_else(|| AppError::NotFound(format!("Window not found: {}", label)))?;
+    window::set_window_bounds(&window, bounds).map_err(AppError::WindowError)
+}
+
+/// Resize the overlay window, keeping it square if the aspect ratio is locked
+#[command]
+pub async fn set_window_size(
     app: AppHandle,
     state: tauri::State<'_, Arc<AppState>>,
-    reticle: String,
-) -> Result<(), String> {
-    state.set_reticle(reticle.clone());
-    app.emit("reticle-changed", &reticle)
-        .map_err(|e| e.to_string())?;
+    width: u32,
+    height: u32,
+) -> Result<(), AppError> {
+    let window = app
+        .get_webview_window("main")
+        .ok_or_else(|| AppError::not_found("Main window not found"))?;
+    window::set_window_size(&window, width, height, state.get_lock_aspect_ratio())?;
     Ok(())
 }
 
-/// Get the reticle type
+/// Get the overlay window size in physical pixels
 #[command]
-pub fn get_reticle(state: tauri::State<'_, Arc<AppState>>) -> String {
-    state.get_reticle()
+pub fn get_window_size(app: AppHandle) -> Result<(u32, u32), AppError> {
+    let window = app
+        .get_webview_window("main")
+        .ok_or_else(|| AppError::not_found("Main window not found"))?;
+    let size = window.outer_size().map_err(|e| e.to_string())?;
+    Ok((size.width, size.height))
 }
 
-/// Import a custom crosshair
+/// Toggle window visibility
 #[command]
-pub async fn import_crosshair(app: AppHandle, path: String) -> Result<String, String> {
-    // Determine destination in app_data_dir (userData)
-    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let custom_dir = app_data_dir.join("crosshairs");
+pub async fn toggle_visibility(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+) -> Result<bool, AppError> {
+    let visible = state.toggle_visible();
 
-    // Ensure directory exists
-    if !custom_dir.exists() {
-        std::fs::create_dir_all(&custom_dir).map_err(|e| e.to_string())?;
-    }
+    // Update main and shadow windows, fading if configured
+    window::apply_visibility(&app, visible)?;
 
-    // Get filename from path
-    let src_path = std::path::Path::new(&path);
-    let filename = src_path
-        .file_name()
-        .ok_or("Invalid path")?
-        .to_str()
-        .ok_or("Invalid filename")?
-        .to_string();
+    // Emit event to all windows
+    app.emit("visibility-changed", visible)
+        .map_err(|e| e.to_string())?;
+    crate::tray::update_tray_menu(&app)?;
 
-    // Destination path
-    let dest_path = custom_dir.join(&filename);
+    Ok(visible)
+}
 
-    // Copy file
-    std::fs::copy(&path, &dest_path).map_err(|e| e.to_string())?;
+/// Set the show/hide fade duration in milliseconds
+#[command]
+pub fn set_fade_duration(state: tauri::State<'_, Arc<AppState>>, duration_ms: u64) {
+    state.set_fade_duration(duration_ms);
+}
 
-    // Return the filename to be set as current crosshair
-    Ok(filename)
+/// Get the show/hide fade duration in milliseconds
+#[command]
+pub fn get_fade_duration(state: tauri::State<'_, Arc<AppState>>) -> u64 {
+    state.get_fade_duration()
 }
 
-/// Create a shadow (duplicate) window
+/// Enable or disable blink mode
 #[command]
-pub async fn create_shadow_window(
+pub async fn set_blink_enabled(
     app: AppHandle,
     state: tauri::State<'_, Arc<AppState>>,
-) -> Result<String, String> {
-    // Limit to 14 shadow windows
-    if state.shadow_window_count() >= 14 {
-        return Err("Maximum shadow windows reached".to_string());
+    enabled: bool,
+) -> Result<(), AppError> {
+    state.set_blink_enabled(enabled);
+    crate::blink::update_blink_state(&app, state.inner().clone());
+
+    // Make sure the crosshair isn't left blinked off
+    if !enabled {
+        app.emit("blink-on", ()).map_err(|e| e.to_string())?;
     }
 
-    // Don't create shadow windows when locked
-    if state.is_locked() {
-        return Err("Cannot create shadow window while locked".to_string());
-    }
-
-    let label = state.next_shadow_id();
-
-    // Get main window position for offset
-    let main_window = app
-        .get_webview_window("main")
-        .ok_or("Main window not found")?;
-    let position = main_window.outer_position().map_err(|e| e.to_string())?;
-    let size = main_window.outer_size().map_err(|e| e.to_string())?;
+    Ok(())
+}
 
-    // Calculate offset based on number of shadow windows
-    let offset = (state.shadow_window_count() as i32 + 1) * 20;
+/// Check if blink mode is enabled
+#[command]
+pub fn get_blink_enabled(state: tauri::State<'_, Arc<AppState>>) -> bool {
+    state.get_blink_enabled()
+}
+
+/// Set the blink interval in milliseconds
+#[command]
+pub async fn set_blink_interval(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    interval_ms: u64,
+) -> Result<(), AppError> {
+    state.set_blink_interval(interval_ms);
+    crate::blink::update_blink_state(&app, state.inner().clone());
+    Ok(())
+}
+
+/// Check if the window is visible
+#[command]
+pub fn is_visible(state: tauri::State<'_, Arc<AppState>>) -> bool {
+    state.is_visible()
+}
+
+/// Get list of available crosshair images
+///
+/// Filenames present in both directories are listed once.
+#[command]
+pub async fn get_crosshair_list(app: AppHandle) -> Result<Vec<String>, AppError> {
+    let mut crosshairs: Vec<String> = crate::crosshair::list_crosshairs(&app)?
+        .into_iter()
+        .map(|info| info.filename)
+        .collect();
+    crosshairs.sort();
+
+    Ok(crosshairs)
+}
+
+/// Get available crosshairs with picker thumbnails, sorted by filename
+///
+/// Thumbnails are cached, so only the first call renders each image.
+/// Rendering runs on a blocking thread to keep the async runtime free.
+#[command]
+pub async fn get_crosshair_list_with_thumbnails(
+    app: AppHandle,
+) -> Result<Vec<crate::crosshair::CrosshairInfo>, AppError> {
+    let mut crosshairs = crate::crosshair::list_crosshairs(&app)?;
+    crosshairs.sort_by(|a, b| a.filename.cmp(&b.filename));
+
+    tauri::async_runtime::spawn_blocking(move || {
+        crosshairs
+            .into_iter()
+            .map(|info| info.with_thumbnail(&app))
+            .collect()
+    })
+    .await
+    .map_err(|e| AppError::StateError(format!("Thumbnail rendering failed: {}", e)))
+}
+
+/// One page of a larger list
+#[derive(Debug, Clone, Serialize)]
+pub struct PagedResult<T> {
+    pub items: Vec<T>,
+    pub total: usize,
+    pub page: u32,
+    pub page_size: u32,
+}
+
+/// Get one page of crosshairs, sorted by name (`page` starts at 0)
+#[command]
+pub async fn get_crosshair_list_paged(
+    app: AppHandle,
+    page: u32,
+    page_size: u32,
+) -> Result<PagedResult<crate::crosshair::CrosshairInfo>, AppError> {
+    if page_size == 0 {
+        return Err(AppError::validation(
+            "page_size",
+            "Page size must be at least 1",
+        ));
+    }
+
+    let offset = page as usize * page_size as usize;
+    let (items, total) = crate::crosshair::list_crosshairs_range(&app, offset, page_size as usize)?;
+    Ok(PagedResult {
+        items,
+        total,
+        page,
+        page_size,
+    })
+}
+
+/// Find crosshairs whose name contains the query, ignoring case
+#[command]
+pub async fn search_crosshairs(
+    app: AppHandle,
+    query: String,
+) -> Result<Vec<crate::crosshair::CrosshairInfo>, AppError> {
+    let crosshairs = crate::crosshair::list_crosshairs(&app)?;
+    Ok(crate::crosshair::filter_by_name(crosshairs, &query))
+}
+
+/// Save current preferences to disk
+#[command]
+pub async fn save_preferences(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+) -> Result<(), AppError> {
+    state.save_preferences(&app).map_err(AppError::IoError)
+}
+
+/// Load preferences from disk
+#[command]
+pub async fn load_preferences(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+) -> Result<(), AppError> {
+    replace_preferences(&app, state.inner(), |state| state.load_preferences(&app))
+        .map_err(AppError::IoError)
+}
+
+/// Restore preferences from a backup (0 is the most recent)
+#[command]
+pub async fn restore_preferences_backup(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    index: usize,
+) -> Result<(), AppError> {
+    replace_preferences(&app, state.inner(), |state| {
+        state.restore_preferences_backup(&app, index)
+    })
+    .map_err(AppError::IoError)
+}
+
+/// Reset preferences to defaults, optionally including keybinds
+#[command]
+pub async fn reset_preferences(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    reset_keybinds: bool,
+) -> Result<(), AppError> {
+    // Re-registers shortcuts if the reset changed any keybinds
+    replace_preferences(&app, state.inner(), |state| {
+        state.reset_preferences(reset_keybinds);
+        Ok(())
+    })
+    .map_err(AppError::StateError)
+}
+
+/// Reset preferences to defaults while keeping custom keybinds
+#[command]
+pub async fn reset_preferences_except_keybinds(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+) -> Result<(), AppError> {
+    reset_preferences(app, state, false).await
+}
+
+/// Replace all preferences in a single write
+///
+/// Opacity and color are validated before anything is stored so that a bad
+/// value never leaves the preferences partially applied.
+#[command]
+pub async fn set_preferences(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    prefs: Preferences,
+) -> Result<(), AppError> {
+    let prefs = prefs.validated()?;
+    replace_preferences(&app, state.inner(), |state| {
+        *state.preferences_mut() = prefs;
+        Ok(())
+    })
+    .map_err(AppError::StateError)
+}
+
+/// Export preferences to a user-chosen JSON file
+#[command]
+pub fn export_preferences(
+    state: tauri::State<'_, Arc<AppState>>,
+    path: String,
+) -> Result<(), AppError> {
+    state
+        .export_preferences(std::path::Path::new(&path))
+        .map_err(AppError::IoError)
+}
+
+/// Import preferences from a user-chosen JSON file
+#[command]
+pub async fn import_preferences(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    path: String,
+) -> Result<(), AppError> {
+    replace_preferences(&app, state.inner(), |state| {
+        state.import_preferences(std::path::Path::new(&path))
+    })
+    .map_err(AppError::IoError)
+}
+
+/// Update only the preferences present in the patch
+///
+/// Change events are emitted only for values that actually changed.
+#[command]
+pub async fn patch_preferences(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    patch: PatchPreferences,
+) -> Result<(), AppError> {
+    apply_preferences_patch(&app, state.inner(), patch).map_err(AppError::StateError)
+}
+
+/// Reset a single preference to its default value
+#[command]
+pub async fn reset_preference(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    key: String,
+) -> Result<(), AppError> {
+    let defaults = Preferences::default();
+    let mut patch = PatchPreferences::default();
+
+    match key.as_str() {
+        "crosshair" => patch.crosshair = Some(defaults.crosshair),
+        "size" => patch.size = Some(defaults.size),
+        "opacity" => patch.opacity = Some(defaults.opacity),
+        "color" => patch.color = Some(defaults.color),
+        "locked" => patch.locked = Some(defaults.locked),
+        "lock_mode" => patch.lock_mode = Some(defaults.lock_mode),
+        "save_on_lock" => patch.save_on_lock = Some(defaults.save_on_lock),
+        "per_monitor_crosshair" => {
+            patch.per_monitor_crosshair = Some(defaults.per_monitor_crosshair)
+        }
+        "normalize_on_import" => patch.normalize_on_import = Some(defaults.normalize_on_import),
+        "visible" => patch.visible = Some(defaults.visible),
+        "follow_mouse" => patch.follow_mouse = Some(defaults.follow_mouse),
+        "start_on_boot" => patch.start_on_boot = Some(defaults.start_on_boot),
+        "keybinds" => patch.keybinds = Some(defaults.keybinds),
+        "hide_on_ads" => patch.hide_on_ads = Some(defaults.hide_on_ads),
+        "reticle" => patch.reticle = Some(defaults.reticle),
+        "auto_center_on_display_change" => {
+            patch.auto_center_on_display_change = Some(defaults.auto_center_on_display_change)
+        }
+        "auto_save" => patch.auto_save = Some(defaults.auto_save),
+        "follow_mouse_config" => patch.follow_mouse_config = Some(defaults.follow_mouse_config),
+        "ads_button" => patch.ads_button = Some(defaults.ads_button),
+        "hide_on_ads_delay_ms" => patch.hide_on_ads_delay_ms = Some(defaults.hide_on_ads_delay_ms),
+        "scroll_wheel_opacity" => patch.scroll_wheel_opacity = Some(defaults.scroll_wheel_opacity),
+        "rotation_degrees" => patch.rotation_degrees = Some(defaults.rotation_degrees),
+        "flip_horizontal" => patch.flip_horizontal = Some(defaults.flip_horizontal),
+        "flip_vertical" => patch.flip_vertical = Some(defaults.flip_vertical),
+        "fade_duration_ms" => patch.fade_duration_ms = Some(defaults.fade_duration_ms),
+        "blink_enabled" => patch.blink_enabled = Some(defaults.blink_enabled),
+        "blink_interval_ms" => patch.blink_interval_ms = Some(defaults.blink_interval_ms),
+        "reticle_size" => patch.reticle_size = Some(defaults.reticle_size),
+        "reticle_thickness" => patch.reticle_thickness = Some(defaults.reticle_thickness),
+        "reticle_gap" => patch.reticle_gap = Some(defaults.reticle_gap),
+        "theme" => patch.theme = Some(defaults.theme),
+        "profiles" => patch.profiles = Some(defaults.profiles),
+        "process_rules" => patch.process_rules = Some(defaults.process_rules),
+        "use_dx_overlay" => patch.use_dx_overlay = Some(defaults.use_dx_overlay),
+        "window_level" => patch.window_level = Some(defaults.window_level),
+        "max_shadow_windows" => patch.max_shadow_windows = Some(defaults.max_shadow_windows),
+        "crosshair_offset_x" => patch.crosshair_offset_x = Some(defaults.crosshair_offset_x),
+        "crosshair_offset_y" => patch.crosshair_offset_y = Some(defaults.crosshair_offset_y),
+        "shadow_enabled" => patch.shadow_enabled = Some(defaults.shadow_enabled),
+        "shadow_blur" => patch.shadow_blur = Some(defaults.shadow_blur),
+        "shadow_color" => patch.shadow_color = Some(defaults.shadow_color),
+        "outline_enabled" => patch.outline_enabled = Some(defaults.outline_enabled),
+        "outline_width" => patch.outline_width = Some(defaults.outline_width),
+        "outline_color" => patch.outline_color = Some(defaults.outline_color),
+        "disable_sounds" => patch.disable_sounds = Some(defaults.disable_sounds),
+        "favorites" => patch.favorites = Some(defaults.favorites),
+        "sound_volume" => patch.sound_volume = Some(defaults.sound_volume),
+        "custom_sounds" => patch.custom_sounds = Some(defaults.custom_sounds),
+        "lock_aspect_ratio" => patch.lock_aspect_ratio = Some(defaults.lock_aspect_ratio),
+        "clamp_to_monitor" => patch.clamp_to_monitor = Some(defaults.clamp_to_monitor),
+        "color_format" => patch.color_format = Some(defaults.color_format),
+        "sync_shadows_to_main_monitor" => {
+            patch.sync_shadows_to_main_monitor = Some(defaults.sync_shadows_to_main_monitor)
+        }
+        "move_step_px" => patch.move_step_px = Some(defaults.move_step_px),
+        "fast_move_step_px" => patch.fast_move_step_px = Some(defaults.fast_move_step_px),
+        "auto_resize_window" => patch.auto_resize_window = Some(defaults.auto_resize_window),
+        "hide_for_processes" => patch.hide_for_processes = Some(defaults.hide_for_processes),
+        "mouse_follow_fps" => patch.mouse_follow_fps = Some(defaults.mouse_follow_fps),
+        // A patch cannot clear optional values, so these are reset directly
+        "position_x" => state.preferences_mut().position_x = defaults.position_x,
+        "position_y" => state.preferences_mut().position_y = defaults.position_y,
+        "active_profile" => state.preferences_mut().active_profile = defaults.active_profile,
+        _ => return Err(AppError::validation("key", "Unknown preference key")),
+    }
+
+    apply_preferences_patch(&app, state.inner(), patch).map_err(AppError::StateError)
+}
+
+/// Apply a patch, emitting events and updating subsystems for changed values
+fn apply_preferences_patch(
+    app: &AppHandle,
+    state: &Arc<AppState>,
+    patch: PatchPreferences,
+) -> Result<(), String> {
+    let (old, new) = {
+        let mut prefs = state.preferences_mut();
+        let old = prefs.clone();
+        let mut new = old.clone();
+        new.apply_patch(patch)?;
+        *prefs = new.clone();
+        (old, new)
+    };
+
+    apply_preference_side_effects(app, state, &old, &new)
+}
+
+/// Replace the preferences wholesale, then update subsystems for changed values
+///
+/// Used by every path that swaps the whole struct (reset, import, restore,
+/// load) so windows, listeners and the OS never drift from what is stored.
+pub fn replace_preferences(
+    app: &AppHandle,
+    state: &Arc<AppState>,
+    replace: impl FnOnce(&AppState) -> Result<(), String>,
+) -> Result<(), String> {
+    let old = state.get_preferences();
+    replace(state)?;
+    let new = state.get_preferences();
+    apply_preference_side_effects(app, state, &old, &new)
+}
+
+/// Restart blink, the mouse listener and shortcuts and re-apply lock,
+/// visibility, autostart and window level where they changed
+fn apply_preference_side_effects(
+    app: &AppHandle,
+    state: &Arc<AppState>,
+    old: &Preferences,
+    new: &Preferences,
+) -> Result<(), String> {
+    if old.blink_enabled != new.blink_enabled || old.blink_interval_ms != new.blink_interval_ms {
+        crate::blink::update_blink_state(app, state.clone());
+    }
+    if old.follow_mouse != new.follow_mouse
+        || old.hide_on_ads != new.hide_on_ads
+        || old.scroll_wheel_opacity != new.scroll_wheel_opacity
+    {
+        crate::mouse::update_mouse_listener_state(app, state.clone())?;
+    }
+    if old.lock_mode != new.lock_mode {
+        for window in app.webview_windows().values() {
+            window::apply_lock_mode(window, new.lock_mode)?;
+        }
+        app.emit("lock-mode-changed", new.lock_mode.as_str())
+            .map_err(|e| e.to_string())?;
+        crate::tray::emit_lock_changed(app, new.locked)?;
+    }
+    if old.visible != new.visible {
+        window::apply_visibility(app, new.visible)?;
+        app.emit("visibility-changed", new.visible)
+            .map_err(|e| e.to_string())?;
+        crate::tray::update_tray_menu(app)?;
+    }
+    if old.keybinds != new.keybinds {
+        crate::hotkeys::update_shortcuts_from_preferences(app)?;
+    }
+    if old.start_on_boot != new.start_on_boot {
+        crate::autostart::sync(app, new.start_on_boot)?;
+    }
+    if old.custom_sounds != new.custom_sounds {
+        crate::sound::allow_custom_sounds(app, new.custom_sounds.values())?;
+    }
+    if old.window_level != new.window_level {
+        for window in app.webview_windows().values() {
+            window::apply_window_level(window, new.window_level)?;
+        }
+        app.emit("window-level-changed", new.window_level)
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Enable or disable automatic saving of preferences
+#[command]
+pub fn set_auto_save(state: tauri::State<'_, Arc<AppState>>, enabled: bool) {
+    state.set_auto_save(enabled);
+}
+
+/// Get all preferences in a single call
+#[command]
+pub fn get_all_preferences(state: tauri::State<'_, Arc<AppState>>) -> Preferences {
+    state.get_preferences()
+}
+
+/// Emit change events to all windows whenever preferences change
+///
+/// The listener runs under the preferences lock, so it diffs against its own
+/// copy of the last preferences it saw instead of reading them from the state.
+pub fn register_preference_events(app: &AppHandle, state: &AppState) {
+    let app = app.clone();
+    let last = parking_lot::Mutex::new(state.get_preferences());
+    state.register_preference_listener(move |prefs| {
+        let old = std::mem::replace(&mut *last.lock(), prefs.clone());
+        if let Err(e) = emit_preference_changes(&app, &old, prefs) {
+            log::warn!("Failed to emit preference changes: {}", e);
+        }
+    });
+}
+
+/// Emit the change events for the crosshair-related values that differ
+fn emit_preference_changes(
+    app: &AppHandle,
+    old: &Preferences,
+    new: &Preferences,
+) -> Result<(), String> {
+    if old.crosshair != new.crosshair {
+        app.emit("crosshair-changed", &new.crosshair)
+            .map_err(|e| e.to_string())?;
+    }
+    if old.opacity != new.opacity {
+        app.emit("opacity-changed", new.opacity)
+            .map_err(|e| e.to_string())?;
+    }
+    if old.size != new.size {
+        app.emit("size-changed", new.size)
+            .map_err(|e| e.to_string())?;
+    }
+    if old.color != new.color {
+        app.emit("color-changed", &new.color)
+            .map_err(|e| e.to_string())?;
+    }
+    if old.reticle != new.reticle {
+        app.emit("reticle-changed", &new.reticle)
+            .map_err(|e| e.to_string())?;
+    }
+    if old.theme != new.theme {
+        app.emit("theme-changed", new.theme.as_str())
+            .map_err(|e| e.to_string())?;
+    }
+    if old.reticle_config() != new.reticle_config() {
+        app.emit("reticle-config-changed", new.reticle_config())
+            .map_err(|e| e.to_string())?;
+    }
+    if old.rotation_degrees != new.rotation_degrees {
+        app.emit("rotation-changed", new.rotation_degrees)
+            .map_err(|e| e.to_string())?;
+    }
+    if old.crosshair_effect() != new.crosshair_effect() {
+        app.emit("crosshair-effect-changed", new.crosshair_effect())
+            .map_err(|e| e.to_string())?;
+    }
+    if old.crosshair_offset() != new.crosshair_offset() {
+        app.emit("crosshair-offset-changed", new.crosshair_offset())
+            .map_err(|e| e.to_string())?;
+    }
+    if old.flip() != new.flip() {
+        app.emit("flip-changed", new.flip())
+            .map_err(|e| e.to_string())?;
+    }
+    // No event for hide_on_ads as it's just a setting
+
+    Ok(())
+}
+
+/// Set follow mouse mode
+#[command]
+pub async fn set_follow_mouse(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    follow: bool,
+) -> Result<(), AppError> {
+    state.set_follow_mouse(follow);
+    crate::mouse::update_mouse_listener_state(&app, state.inner().clone())?;
+    Ok(())
+}
+
+/// Get follow mouse state
+#[command]
+pub fn get_follow_mouse(state: tauri::State<'_, Arc<AppState>>) -> bool {
+    state.get_follow_mouse()
+}
+
+/// Get whether mouse following is on, running and how fast it updates
+#[command]
+pub fn get_follow_mouse_status(
+    state: tauri::State<'_, Arc<AppState>>,
+) -> crate::mouse::FollowMouseStatus {
+    crate::mouse::follow_mouse_status(&state)
+}
+
+/// Set the mouse following smoothing, dead zone and offset
+#[command]
+pub fn set_follow_mouse_config(state: tauri::State<'_, Arc<AppState>>, config: FollowMouseConfig) {
+    state.set_follow_mouse_config(config);
+}
+
+/// Get the mouse following configuration
+#[command]
+pub fn get_follow_mouse_config(state: tauri::State<'_, Arc<AppState>>) -> FollowMouseConfig {
+    state.get_follow_mouse_config()
+}
+
+/// Turn sound feedback off or on
+#[command]
+pub fn set_disable_sounds(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    disabled: bool,
+) -> Result<(), AppError> {
+    state.set_disable_sounds(disabled);
+    app.emit("sounds-disabled-changed", disabled)
+        .map_err(|e| AppError::WindowError(e.to_string()))
+}
+
+/// Check if sound feedback is turned off
+#[command]
+pub fn get_disable_sounds(state: tauri::State<'_, Arc<AppState>>) -> bool {
+    state.get_disable_sounds()
+}
+
+/// Set the sound feedback volume from 0.0 to 1.0
+#[command]
+pub fn set_sound_volume(state: tauri::State<'_, Arc<AppState>>, volume: f64) {
+    state.set_sound_volume(volume);
+}
+
+/// Get the sound feedback volume
+#[command]
+pub fn get_sound_volume(state: tauri::State<'_, Arc<AppState>>) -> f64 {
+    state.get_sound_volume()
+}
+
+/// Play a custom file instead of the bundled sound
+#[command]
+pub fn set_custom_sound(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    event: String,
+    path: String,
+) -> Result<(), AppError> {
+    crate::sound::validate_sound_event(&event)?;
+    let path = crate::sound::validate_sound_file(&path)?
+        .to_string_lossy()
+        .to_string();
+    crate::sound::allow_custom_sounds(&app, [&path])?;
+    state.set_custom_sound(event, path);
+    Ok(())
+}
+
+/// Go back to the bundled sound
+#[command]
+pub fn remove_custom_sound(
+    state: tauri::State<'_, Arc<AppState>>,
+    event: String,
+) -> Result<(), AppError> {
+    if state.remove_custom_sound(&event) {
+        Ok(())
+    } else {
+        Err(AppError::NotFound(format!(
+            "No custom sound for: {}",
+            event
+        )))
+    }
+}
+
+/// Get custom sound file paths keyed by sound name
+#[command]
+pub fn get_custom_sounds(state: tauri::State<'_, Arc<AppState>>) -> HashMap<String, String> {
+    state.get_custom_sounds()
+}
+
+/// Set how many times per second the crosshair moves while following the mouse
+#[command]
+pub async fn set_mouse_follow_fps(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    fps: u32,
+) -> Result<(), AppError> {
+    if !(MIN_MOUSE_FOLLOW_FPS..=MAX_MOUSE_FOLLOW_FPS).contains(&fps) {
+        return Err(AppError::validation(
+            "fps",
+            format!(
+                "Mouse follow FPS must be between {} and {}",
+                MIN_MOUSE_FOLLOW_FPS, MAX_MOUSE_FOLLOW_FPS
+            ),
+        ));
+    }
+
+    state.set_mouse_follow_fps(fps);
+
+    // The running listener reads the rate on every move, so only make sure
+    // it is running when it should be
+    crate::mouse::update_mouse_listener_state(&app, state.inner().clone())
+        .map_err(AppError::StateError)
+}
+
+/// Get the mouse following frame rate
+#[command]
+pub fn get_mouse_follow_fps(state: tauri::State<'_, Arc<AppState>>) -> u32 {
+    state.get_mouse_follow_fps()
+}
+
+/// Set whether scrolling over the unlocked crosshair adjusts opacity
+#[command]
+pub async fn set_scroll_wheel_opacity(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    enabled: bool,
+) -> Result<(), AppError> {
+    state.set_scroll_wheel_opacity(enabled);
+    crate::mouse::update_mouse_listener_state(&app, state.inner().clone())?;
+    Ok(())
+}
+
+/// Set how many pixels each keyboard move covers
+#[command]
+pub fn set_move_step_px(state: tauri::State<'_, Arc<AppState>>, step: u32) -> Result<(), AppError> {
+    validate_move_step(step)?;
+    state.set_move_step_px(step);
+    Ok(())
+}
+
+/// Get how many pixels each keyboard move covers
+#[command]
+pub fn get_move_step_px(state: tauri::State<'_, Arc<AppState>>) -> u32 {
+    state.get_move_step_px()
+}
+
+/// Set how many pixels each fast keyboard move covers
+#[command]
+pub fn set_fast_move_step_px(
+    state: tauri::State<'_, Arc<AppState>>,
+    step: u32,
+) -> Result<(), AppError> {
+    validate_move_step(step)?;
+    state.set_fast_move_step_px(step);
+    Ok(())
+}
+
+/// Get how many pixels each fast keyboard move covers
+#[command]
+pub fn get_fast_move_step_px(state: tauri::State<'_, Arc<AppState>>) -> u32 {
+    state.get_fast_move_step_px()
+}
+
+/// Check that a move step is within the allowed range
+fn validate_move_step(step: u32) -> Result<(), AppError> {
+    if !(MIN_MOVE_STEP_PX..=MAX_MOVE_STEP_PX).contains(&step) {
+        return Err(AppError::validation(
+            "step",
+            format!(
+                "Move step must be between {} and {} pixels",
+                MIN_MOVE_STEP_PX, MAX_MOVE_STEP_PX
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Set whether moving the crosshair keeps it on screen
+#[command]
+pub fn set_clamp_to_monitor(state: tauri::State<'_, Arc<AppState>>, enabled: bool) {
+    state.set_clamp_to_monitor(enabled);
+}
+
+/// Check whether moving the crosshair keeps it on screen
+#[command]
+pub fn get_clamp_to_monitor(state: tauri::State<'_, Arc<AppState>>) -> bool {
+    state.get_clamp_to_monitor()
+}
+
+/// Set whether shadow windows follow the main window when it wraps to the first monitor
+#[command]
+pub fn set_sync_shadows_to_main_monitor(state: tauri::State<'_, Arc<AppState>>, enabled: bool) {
+    state.set_sync_shadows_to_main_monitor(enabled);
+}
+
+/// Check whether shadow windows follow the main window when it wraps to the first monitor
+#[command]
+pub fn get_sync_shadows_to_main_monitor(state: tauri::State<'_, Arc<AppState>>) -> bool {
+    state.get_sync_shadows_to_main_monitor()
+}
+
+/// Get scroll wheel opacity state
+#[command]
+pub fn get_scroll_wheel_opacity(state: tauri::State<'_, Arc<AppState>>) -> bool {
+    state.get_scroll_wheel_opacity()
+}
+
+/// Set hide on ADS mode
+#[command]
+pub async fn set_hide_on_ads(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    hide: bool,
+) -> Result<(), AppError> {
+    state.set_hide_on_ads(hide);
+    crate::mouse::update_mouse_listener_state(&app, state.inner().clone())?;
+    Ok(())
+}
+
+/// Get hide on ADS state
+#[command]
+pub fn get_hide_on_ads(state: tauri::State<'_, Arc<AppState>>) -> bool {
+    state.get_hide_on_ads()
+}
+
+/// Set how long the ADS button must be held before the crosshair hides
+#[command]
+pub fn set_hide_on_ads_delay(
+    state: tauri::State<'_, Arc<AppState>>,
+    delay_ms: u64,
+) -> Result<(), AppError> {
+    if delay_ms > MAX_HIDE_ON_ADS_DELAY_MS {
+        return Err(AppError::validation(
+            "delay_ms",
+            format!(
+                "ADS hide delay must be at most {}ms",
+                MAX_HIDE_ON_ADS_DELAY_MS
+            ),
+        ));
+    }
+
+    state.set_hide_on_ads_delay_ms(delay_ms);
+    Ok(())
+}
+
+/// Get the delay before the crosshair hides on ADS
+#[command]
+pub fn get_hide_on_ads_delay(state: tauri::State<'_, Arc<AppState>>) -> u64 {
+    state.get_hide_on_ads_delay_ms()
+}
+
+/// Set the reticle type
+#[command]
+pub async fn set_reticle(
+    state: tauri::State<'_, Arc<AppState>>,
+    reticle: String,
+) -> Result<(), AppError> {
+    state.set_reticle(reticle);
+    Ok(())
+}
+
+/// Get the reticle type
+#[command]
+pub fn get_reticle(state: tauri::State<'_, Arc<AppState>>) -> String {
+    state.get_reticle()
+}
+
+/// Get an asset protocol URL for a crosshair the webview is allowed to load
+#[command]
+pub fn get_crosshair_url(app: AppHandle, filename: String) -> Result<String, AppError> {
+    crate::crosshair::get_crosshair_url(&app, &filename).map_err(AppError::StateError)
+}
+
+/// Stop the webview from loading a crosshair through the asset protocol
+///
+/// The asset scope has no way to lift a denial, so the file stays blocked
+/// until the app restarts, even if a crosshair with the same name is imported.
+#[command]
+pub fn revoke_crosshair_url(app: AppHandle, filename: String) -> Result<(), AppError> {
+    crate::crosshair::revoke_crosshair_url(&app, &filename).map_err(AppError::StateError)
+}
+
+/// Get a crosshair image as a base64 data URL
+#[command]
+pub async fn get_crosshair_data(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    filename: String,
+) -> Result<String, AppError> {
+    crate::crosshair::get_crosshair_data(&app, &state, &filename).map_err(AppError::IoError)
+}
+
+/// Get an SVG crosshair recolored with the current color as a data URL
+#[command]
+pub async fn get_crosshair_svg_colored(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    filename: String,
+) -> Result<String, AppError> {
+    let color = state.get_color();
+    if let Some(data_url) = state.get_cached_svg(&filename, &color) {
+        return Ok(data_url);
+    }
+
+    let data_url = crate::crosshair::get_colored_svg(&app, &filename, &color)?;
+    state.cache_svg(filename, color, data_url.clone());
+    Ok(data_url)
+}
+
+/// Render a small preview of a crosshair in the current color without applying it
+#[command]
+pub async fn preview_crosshair(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    filename: String,
+) -> Result<String, AppError> {
+    let color = state.get_color();
+    if let Some(data_url) = state.get_cached_preview(&filename, &color) {
+        return Ok(data_url);
+    }
+
+    let data_url = crate::crosshair::render_preview(&app, &filename, &color)?;
+    state.cache_preview(&filename, &color, data_url.clone());
+    Ok(data_url)
+}
+
+/// Get a crosshair's dimensions, format and file size
+#[command]
+pub async fn get_crosshair_metadata(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    filename: String,
+) -> Result<crate::crosshair::CrosshairMetadata, AppError> {
+    crate::crosshair::get_crosshair_metadata(&app, &state, &filename).map_err(AppError::IoError)
+}
+
+/// Sample the screen color at physical coordinates as `#RRGGBB`
+#[command]
+pub async fn sample_screen_color(x: i32, y: i32) -> Result<String, AppError> {
+    crate::color::sample_screen_color(x, y).map_err(AppError::WindowError)
+}
+
+/// Set the built-in reticle size
+#[command]
+pub async fn set_reticle_size(
+    state: tauri::State<'_, Arc<AppState>>,
+    size: u32,
+) -> Result<(), AppError> {
+    state.set_reticle_size(size)?;
+    Ok(())
+}
+
+/// Get the built-in reticle size
+#[command]
+pub fn get_reticle_size(state: tauri::State<'_, Arc<AppState>>) -> u32 {
+    state.get_reticle_config().size
+}
+
+/// Set the built-in reticle line thickness
+#[command]
+pub async fn set_reticle_thickness(
+    state: tauri::State<'_, Arc<AppState>>,
+    thickness: u32,
+) -> Result<(), AppError> {
+    state.set_reticle_thickness(thickness)?;
+    Ok(())
+}
+
+/// Get the built-in reticle line thickness
+#[command]
+pub fn get_reticle_thickness(state: tauri::State<'_, Arc<AppState>>) -> u32 {
+    state.get_reticle_config().thickness
+}
+
+/// Set the built-in reticle center gap
+#[command]
+pub async fn set_reticle_gap(
+    state: tauri::State<'_, Arc<AppState>>,
+    gap: u32,
+) -> Result<(), AppError> {
+    state.set_reticle_gap(gap);
+    Ok(())
+}
+
+/// Get the built-in reticle center gap
+#[command]
+pub fn get_reticle_gap(state: tauri::State<'_, Arc<AppState>>) -> u32 {
+    state.get_reticle_config().gap
+}
+
+/// Set the UI theme (light, dark or system)
+#[command]
+pub async fn set_theme(
+    state: tauri::State<'_, Arc<AppState>>,
+    theme: String,
+) -> Result<(), AppError> {
+    if !Theme::all().contains(&theme.to_lowercase().as_str()) {
+        return Err(AppError::validation(
+            "theme",
+            format!("Unknown theme: {}", theme),
+        ));
+    }
+
+    let theme = Theme::from_str(&theme);
+    state.set_theme(theme);
+    Ok(())
+}
+
+/// Get the UI theme
+#[command]
+pub fn get_theme(state: tauri::State<'_, Arc<AppState>>) -> String {
+    state.get_theme().as_str().to_string()
+}
+
+/// Get the list of available themes
+#[command]
+pub fn get_theme_list() -> Vec<String> {
+    Theme::all().iter().map(|t| t.to_string()).collect()
+}
+
+/// Set the crosshair rotation in degrees
+#[command]
+pub async fn set_rotation(
+    state: tauri::State<'_, Arc<AppState>>,
+    degrees: f64,
+) -> Result<(), AppError> {
+    if !degrees.is_finite() {
+        return Err(AppError::validation(
+            "degrees",
+            format!("Invalid rotation: {}", degrees),
+        ));
+    }
+
+    state.set_rotation(degrees);
+    Ok(())
+}
+
+/// Get the crosshair rotation in degrees
+#[command]
+pub fn get_rotation(state: tauri::State<'_, Arc<AppState>>) -> f64 {
+    state.get_rotation()
+}
+
+/// Rotate the crosshair relative to its current rotation
+#[command]
+pub async fn rotate_by(
+    state: tauri::State<'_, Arc<AppState>>,
+    delta: f64,
+) -> Result<f64, AppError> {
+    if !delta.is_finite() {
+        return Err(AppError::validation(
+            "delta",
+            format!("Invalid rotation: {}", delta),
+        ));
+    }
+
+    Ok(state.rotate_by(delta))
+}
+
+/// Set horizontal flip
+#[command]
+pub async fn set_flip_horizontal(
+    state: tauri::State<'_, Arc<AppState>>,
+    flip: bool,
+) -> Result<(), AppError> {
+    state.set_flip_horizontal(flip);
+    Ok(())
+}
+
+/// Get horizontal flip
+#[command]
+pub fn get_flip_horizontal(state: tauri::State<'_, Arc<AppState>>) -> bool {
+    state.get_flip().horizontal
+}
+
+/// Set vertical flip
+#[command]
+pub async fn set_flip_vertical(
+    state: tauri::State<'_, Arc<AppState>>,
+    flip: bool,
+) -> Result<(), AppError> {
+    state.set_flip_vertical(flip);
+    Ok(())
+}
+
+/// Get vertical flip
+#[command]
+pub fn get_flip_vertical(state: tauri::State<'_, Arc<AppState>>) -> bool {
+    state.get_flip().vertical
+}
+
+/// Enable or disable the crosshair drop shadow
+#[command]
+pub fn set_shadow_enabled(state: tauri::State<'_, Arc<AppState>>, enabled: bool) {
+    state.set_shadow_enabled(enabled);
+}
+
+/// Set the drop shadow blur radius in pixels
+#[command]
+pub async fn set_shadow_blur(
+    state: tauri::State<'_, Arc<AppState>>,
+    blur: u32,
+) -> Result<(), AppError> {
+    state.set_shadow_blur(blur)?;
+    Ok(())
+}
+
+/// Set the drop shadow color
+#[command]
+pub async fn set_shadow_color(
+    state: tauri::State<'_, Arc<AppState>>,
+    color: String,
+) -> Result<(), AppError> {
+    state.set_shadow_color(&color)?;
+    Ok(())
+}
+
+/// Enable or disable the crosshair outline
+#[command]
+pub fn set_outline_enabled(state: tauri::State<'_, Arc<AppState>>, enabled: bool) {
+    state.set_outline_enabled(enabled);
+}
+
+/// Set the outline width in pixels
+#[command]
+pub async fn set_outline_width(
+    state: tauri::State<'_, Arc<AppState>>,
+    width: u32,
+) -> Result<(), AppError> {
+    state.set_outline_width(width)?;
+    Ok(())
+}
+
+/// Set the outline color
+#[command]
+pub async fn set_outline_color(
+    state: tauri::State<'_, Arc<AppState>>,
+    color: String,
+) -> Result<(), AppError> {
+    state.set_outline_color(&color)?;
+    Ok(())
+}
+
+/// Get the drop shadow and outline settings
+#[command]
+pub fn get_crosshair_effect(state: tauri::State<'_, Arc<AppState>>) -> CrosshairEffect {
+    state.get_crosshair_effect()
+}
+
+/// Offset the crosshair from the center of its window
+#[command]
+pub async fn set_crosshair_offset(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    offset_x: i32,
+    offset_y: i32,
+) -> Result<(), AppError> {
+    let offset = CrosshairOffset {
+        x: offset_x,
+        y: offset_y,
+    };
+
+    let (width, height) = match app.get_webview_window("main") {
+        Some(window) => {
+            let scale = window.scale_factor().map_err(|e| e.to_string())?;
+            let size = window
+                .inner_size()
+                .map_err(|e| e.to_string())?
+                .to_logical::<u32>(scale);
+            (size.width, size.height)
+        }
+        None => (DEFAULT_WINDOW_WIDTH, DEFAULT_WINDOW_HEIGHT),
+    };
+    crate::state::validate_crosshair_offset(offset, width, height)?;
+
+    state.set_crosshair_offset(offset);
+    Ok(())
+}
+
+/// Get the crosshair offset from the center of its window
+#[command]
+pub fn get_crosshair_offset(state: tauri::State<'_, Arc<AppState>>) -> CrosshairOffset {
+    state.get_crosshair_offset()
+}
+
+/// List the images in a crosshair pack without importing them
+#[command]
+pub async fn list_crosshair_pack_contents(
+    app: AppHandle,
+    path: String,
+) -> Result<Vec<crate::crosshair::PackEntry>, AppError> {
+    let custom_dir = crate::crosshair::get_custom_crosshairs_dir(&app)?;
+    crate::crosshair::list_pack_contents(std::path::Path::new(&path), &custom_dir)
+        .map_err(AppError::IoError)
+}
+
+/// Import a custom crosshair
+#[command]
+pub async fn import_crosshair(app: AppHandle, path: String) -> Result<String, AppError> {
+    let path = std::path::Path::new(&path);
+    let replaces_existing = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| crate::crosshair::crosshair_exists(&app, name));
+
+    let info = crate::crosshair::import_crosshair(&app, path)?;
+    if replaces_existing {
+        log::info!(
+            "Imported crosshair {} replaces an existing one",
+            info.filename
+        );
+    }
+
+    // Return the filename to be set as current crosshair, which changes
+    // extension if the image was converted to PNG
+    Ok(info.filename)
+}
+
+/// Get all keybinds and whether each is currently registered
+#[command]
+pub fn get_registered_hotkeys(app: AppHandle) -> Vec<crate::hotkeys::RegisteredHotkey> {
+    crate::hotkeys::get_registered_hotkeys(&app)
+}
+
+/// Get why mouse following stopped working, if it did
+#[command]
+pub fn get_mouse_follow_error(state: tauri::State<'_, Arc<AppState>>) -> Option<String> {
+    state.get_mouse_follow_error()
+}
+
+/// Get the current global cursor position
+#[command]
+pub fn get_cursor_position(app: AppHandle) -> Result<crate::mouse::MousePosition, AppError> {
+    crate::mouse::cursor_position(&app).map_err(AppError::WindowError)
+}
+
+/// Check that a shortcut string is valid before saving it as a keybind
+#[command]
+pub fn validate_hotkey_string(shortcut: String) -> Result<(), AppError> {
+    crate::hotkeys::validate_shortcut(&shortcut).map_err(|e| AppError::validation("shortcut", e))
+}
+
+/// Remap the shortcut for a single action
+#[command]
+pub async fn update_single_hotkey(
+    app: AppHandle,
+    action: String,
+    shortcut: String,
+) -> Result<(), AppError> {
+    crate::hotkeys::update_single_hotkey(&app, &action, &shortcut)
+        .map_err(|e| AppError::validation("shortcut", e))
+}
+
+/// Check if the app is running in portable mode
+#[command]
+pub fn is_portable_mode(state: tauri::State<'_, Arc<AppState>>) -> bool {
+    state.portable_mode
+}
+
+/// Get call counts in the current second for rate-limited commands
+#[command]
+pub fn get_rate_limit_stats(state: tauri::State<'_, Arc<AppState>>) -> HashMap<String, u32> {
+    state.get_rate_limit_stats()
+}
+
+/// Check if this process holds the single instance lock
+#[command]
+pub fn is_single_instance(state: tauri::State<'_, Arc<AppState>>) -> bool {
+    state.single_instance
+}
+
+/// Start the external control server on a loopback port
+///
+/// Pass port 0 to let the system pick a free port.
+#[command]
+pub async fn start_ipc_server(app: AppHandle, port: u16) -> Result<(), AppError> {
+    crate::ipc::start(&app, port).map_err(AppError::IoError)
+}
+
+/// Stop the external control server
+#[command]
+pub fn stop_ipc_server(state: tauri::State<'_, Arc<AppState>>) -> Result<(), AppError> {
+    crate::ipc::stop(&state).map_err(AppError::StateError)
+}
+
+/// Get whether the external control server is running and on which port
+#[command]
+pub fn get_ipc_server_status(state: tauri::State<'_, Arc<AppState>>) -> IpcServerStatus {
+    let server = state.ipc_server_slot();
+    IpcServerStatus {
+        running: server.is_some(),
+        port: server.as_ref().map_or(0, |server| server.port),
+        token_path: server
+            .as_ref()
+            .map(|server| server.token_path.display().to_string()),
+    }
+}
+
+/// Get the overrides passed on the command line at launch
+#[command]
+pub fn get_startup_args(state: tauri::State<'_, Arc<AppState>>) -> StartupArgs {
+    state.startup_args.clone()
+}
+
+/// Check if the app is running without an overlay window
+#[command]
+pub fn is_headless(state: tauri::State<'_, Arc<AppState>>) -> bool {
+    state.headless
+}
+
+/// Check if the app was started with saved preferences ignored
+#[command]
+pub fn is_safe_mode(state: tauri::State<'_, Arc<AppState>>) -> bool {
+    state.safe_mode
+}
+
+/// Create a shadow (duplicate) window
+#[command]
+pub async fn create_shadow_window(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    options: Option<ShadowWindowOptions>,
+) -> Result<String, AppError> {
+    let options = options.unwrap_or_default();
+
+    if !state.check_rate_limit("create_shadow_window", RATE_LIMIT_CREATE_SHADOW_WINDOW) {
+        return Err(AppError::StateError("Rate limit exceeded".to_string()));
+    }
+
+    // Validate the overrides before they are stored
+    if let Some(crosshair) = &options.crosshair {
+        crate::crosshair::validate_crosshair(&app, crosshair)
+            .map_err(|e| AppError::validation("crosshair", e))?;
+    }
+    if let Some(opacity) = options.opacity {
+        if !(0.0..=1.0).contains(&opacity) {
+            return Err(AppError::validation(
+                "opacity",
+                "Opacity must be between 0.0 and 1.0",
+            ));
+        }
+    }
+
+    // Limit to the configured number of shadow windows
+    if state.shadow_window_count() >= state.preferences.read().max_shadow_windows {
+        return Err(AppError::StateError(
+            "Maximum shadow windows reached".to_string(),
+        ));
+    }
+
+    // Don't create shadow windows when locked
+    if state.is_locked() {
+        return Err(AppError::StateError(
+            "Cannot create shadow window while locked".to_string(),
+        ));
+    }
+
+    let label = state.next_shadow_id();
+
+    // Get main window position for offset
+    let main_window = app
+        .get_webview_window("main")
+        .ok_or_else(|| AppError::not_found("Main window not found"))?;
+    let position = main_window.outer_position().map_err(|e| e.to_string())?;
+    let size = main_window.outer_size().map_err(|e| e.to_string())?;
+
+    // Use the requested position, or offset based on number of shadow windows
+    let offset = (state.shadow_window_count() as i32 + 1) * 20;
+    let x = options.position_x.unwrap_or(position.x + offset);
+    let y = options.position_y.unwrap_or(position.y + offset);
+
+    // Register the window first, so its settings are ready when the page
+    // loads and fetches them with `get_shadow_settings`
+    state.add_shadow_window(label.clone());
+    state.set_shadow_options(&label, options);
 
     // Create the shadow window
     let shadow_window =
         tauri::WebviewWindowBuilder::new(&app, &label, tauri::WebviewUrl::App("index.html".into()))
             .title("Shadow")
             .inner_size(size.width as f64, size.height as f64)
-            .position((position.x + offset) as f64, (position.y + offset) as f64)
+            .position(x as f64, y as f64)
             .decorations(false)
             .always_on_top(true)
             .skip_taskbar(true)
             .shadow(false)
             .visible_on_all_workspaces(true)
             .build()
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| {
+                state.remove_shadow_window(&label);
+                e.to_string()
+            })?;
 
-    // Apply overlay settings
+    // Apply overlay settings. The builder already makes the window visible
+    // on all workspaces, but the platform setup re-applies it defensively.
     window::setup_overlay_window(&shadow_window)?;
 
-    // Apply lock state
-    if state.is_locked() {
-        window::set_click_through(&shadow_window, true)?;
+    // Start draggable like the main window, unless locked in the meantime
+    window::set_click_through(&shadow_window, state.is_locked())?;
+
+    crate::tray::update_tray_tooltip(&app)?;
+
+    Ok(label)
+}
+
+/// Get the settings for a shadow window, with its overrides applied
+#[command]
+pub fn get_shadow_settings(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    label: String,
+) -> Result<Preferences, AppError> {
+    get_shadow_window(&app, &state, &label)?;
+
+    let mut prefs = state.get_preferences();
+    state.get_shadow_options(&label).apply_to(&mut prefs);
+    Ok(prefs)
+}
+
+/// Set the maximum number of shadow windows
+#[command]
+pub fn set_max_shadow_windows(
+    state: tauri::State<'_, Arc<AppState>>,
+    max: usize,
+) -> Result<(), AppError> {
+    state
+        .set_max_shadow_windows(max)
+        .map_err(|e| AppError::validation("max", e))
+}
+
+/// Get the maximum number of shadow windows
+#[command]
+pub fn get_max_shadow_windows(state: tauri::State<'_, Arc<AppState>>) -> usize {
+    state.get_max_shadow_windows()
+}
+
+/// Copy the main window's settings to a shadow window
+#[command]
+pub async fn copy_settings_to_shadow_window(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    label: String,
+) -> Result<(), AppError> {
+    get_shadow_window(&app, &state, &label)?;
+
+    let mut prefs = state.get_preferences();
+    let mut options = state.get_shadow_options(&label);
+    options.copy_settings_from(&prefs);
+    options.apply_to(&mut prefs);
+    state.set_shadow_options(&label, options);
+
+    app.emit_to(label.as_str(), "sync-settings", prefs)
+        .map_err(|e| AppError::WindowError(e.to_string()))
+}
+
+/// Apply a shadow window's settings to the main window
+#[command]
+pub async fn copy_settings_from_shadow_window(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    label: String,
+) -> Result<(), AppError> {
+    get_shadow_window(&app, &state, &label)?;
+
+    let options = state.get_shadow_options(&label);
+    apply_preferences_patch(&app, state.inner(), options.settings_patch())
+        .map_err(AppError::StateError)
+}
+
+/// Get a shadow window by label
+fn get_shadow_window(
+    app: &AppHandle,
+    state: &AppState,
+    label: &str,
+) -> Result<tauri::WebviewWindow, AppError> {
+    if !state.get_shadow_windows().iter().any(|l| l == label) {
+        return Err(AppError::NotFound(format!(
+            "Not a shadow window: {}",
+            label
+        )));
     }
+    app.get_webview_window(label)
+        .ok_or_else(|| AppError::NotFound(format!("Window not found: {}", label)))
+}
 
-    state.add_shadow_window(label.clone());
+/// Focus a shadow window
+#[command]
+pub async fn focus_shadow_window(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    label: String,
+) -> Result<(), AppError> {
+    get_shadow_window(&app, &state, &label)?
+        .set_focus()
+        .map_err(|e| AppError::WindowError(e.to_string()))
+}
 
-    // Notify the shadow window to sync with main
-    shadow_window
-        .emit("sync-settings", state.get_preferences())
-        .map_err(|e| e.to_string())?;
+/// Bring a shadow window to the front or send it to the back
+#[command]
+pub async fn set_shadow_window_z_order(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    label: String,
+    front: bool,
+) -> Result<(), AppError> {
+    let shadow_window = get_shadow_window(&app, &state, &label)?;
+    window::set_z_order(&shadow_window, front).map_err(AppError::WindowError)
+}
 
-    Ok(label)
+/// Grid layout for `arrange_shadow_windows`
+#[derive(Debug, Clone, Deserialize)]
+pub struct GridArrangement {
+    pub columns: u32,
+    pub padding_px: u32,
+}
+
+/// Arrange the main and shadow windows in a grid on the main window's monitor
+#[command]
+pub async fn arrange_shadow_windows(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+    arrangement: GridArrangement,
+) -> Result<(), AppError> {
+    if arrangement.columns == 0 {
+        return Err(AppError::validation(
+            "columns",
+            "Grid must have at least one column",
+        ));
+    }
+
+    let main_window = app
+        .get_webview_window("main")
+        .ok_or_else(|| AppError::not_found("Main window not found"))?;
+
+    // The main window goes into the first cell
+    let mut labels = state.get_shadow_windows();
+    labels.sort();
+    let windows: Vec<tauri::WebviewWindow> = std::iter::once(main_window)
+        .chain(
+            labels
+                .iter()
+                .filter_map(|label| app.get_webview_window(label)),
+        )
+        .collect();
+
+    for arranged in window::arrange_in_grid(&windows, arrangement.columns, arrangement.padding_px)?
+    {
+        app.emit("window-arranged", arranged)
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Position, size and settings of a shadow window
+#[derive(Debug, Clone, Serialize)]
+pub struct ShadowWindowInfo {
+    pub label: String,
+    pub position_x: i32,
+    pub position_y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub crosshair: String,
+    pub opacity: f64,
+}
+
+/// Get the position and settings of all shadow windows
+#[command]
+pub async fn get_shadow_window_list(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<AppState>>,
+) -> Result<Vec<ShadowWindowInfo>, AppError> {
+    let mut labels = state.get_shadow_windows();
+    labels.sort();
+
+    let mut shadows = Vec::with_capacity(labels.len());
+    for label in labels {
+        let Some(window) = app.get_webview_window(&label) else {
+            continue;
+        };
+        let position = window.outer_position().map_err(|e| e.to_string())?;
+        let size = window.outer_size().map_err(|e| e.to_string())?;
+
+        let mut prefs = state.get_preferences();
+        state.get_shadow_options(&label).apply_to(&mut prefs);
+
+        shadows.push(ShadowWindowInfo {
+            label,
+            position_x: position.x,
+            position_y: position.y,
+            width: size.width,
+            height: size.height,
+            crosshair: prefs.crosshair,
+            opacity: prefs.opacity,
+        });
+    }
+
+    Ok(shadows)
 }
 
 /// Close a specific shadow window
@@ -421,11 +2176,12 @@ pub async fn close_shadow_window(
     app: AppHandle,
     state: tauri::State<'_, Arc<AppState>>,
     label: String,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     if let Some(window) = app.get_webview_window(&label) {
         window.close().map_err(|e| e.to_string())?;
     }
     state.remove_shadow_window(&label);
+    crate::tray::update_tray_tooltip(&app)?;
     Ok(())
 }
 
@@ -434,12 +2190,134 @@ pub async fn close_shadow_window(
 pub async fn close_all_shadow_windows(
     app: AppHandle,
     state: tauri::State<'_, Arc<AppState>>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     for label in state.get_shadow_windows() {
         if let Some(window) = app.get_webview_window(&label) {
             window.close().map_err(|e| e.to_string())?;
         }
     }
     state.clear_shadow_windows();
+    crate::tray::update_tray_tooltip(&app)?;
+    Ok(())
+}
+
+/// Save the current appearance as a named profile
+#[command]
+pub fn save_profile(state: tauri::State<'_, Arc<AppState>>, name: String) -> Result<(), AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::validation("name", "Profile name cannot be empty"));
+    }
+    state.save_profile(name);
+    Ok(())
+}
+
+/// Load a named profile
+#[command]
+pub async fn load_profile(
+    state: tauri::State<'_, Arc<AppState>>,
+    name: String,
+) -> Result<(), AppError> {
+    state
+        .load_profile(&name)
+        .map_err(|e| AppError::validation("name", e))
+}
+
+/// Get all saved profile names
+#[command]
+pub fn list_profiles(state: tauri::State<'_, Arc<AppState>>) -> Vec<String> {
+    state.list_profiles()
+}
+
+/// Switch to a profile while the given process is running
+#[command]
+pub fn add_process_rule(
+    state: tauri::State<'_, Arc<AppState>>,
+    process_name: String,
+    profile_name: String,
+) -> Result<(), AppError> {
+    let process_name = process_name.trim().to_string();
+    if process_name.is_empty() {
+        return Err(AppError::validation(
+            "process_name",
+            "Process name cannot be empty",
+        ));
+    }
+    if !state.list_profiles().contains(&profile_name) {
+        return Err(AppError::NotFound(format!(
+            "Profile not found: {}",
+            profile_name
+        )));
+    }
+
+    state.add_process_rule(ProcessRule {
+        process_name,
+        profile_name,
+    });
     Ok(())
 }
+
+/// Remove the rule for a process
+#[command]
+pub fn remove_process_rule(
+    state: tauri::State<'_, Arc<AppState>>,
+    process_name: String,
+) -> Result<(), AppError> {
+    if state.remove_process_rule(&process_name) {
+        Ok(())
+    } else {
+        Err(AppError::NotFound(format!(
+            "No rule for process: {}",
+            process_name
+        )))
+    }
+}
+
+/// Get all process rules
+#[command]
+pub fn list_process_rules(state: tauri::State<'_, Arc<AppState>>) -> Vec<ProcessRule> {
+    state.get_process_rules()
+}
+
+/// Hide the crosshair while a process is in the foreground
+#[command]
+pub fn add_hide_process(
+    state: tauri::State<'_, Arc<AppState>>,
+    name: String,
+) -> Result<(), AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::validation("name", "Process name cannot be empty"));
+    }
+
+    if state.add_hide_process(name) {
+        Ok(())
+    } else {
+        Err(AppError::validation(
+            "name",
+            format!("Process already hides the crosshair: {}", name),
+        ))
+    }
+}
+
+/// Stop hiding the crosshair for a process
+#[command]
+pub fn remove_hide_process(
+    state: tauri::State<'_, Arc<AppState>>,
+    name: String,
+) -> Result<(), AppError> {
+    if state.remove_hide_process(&name) {
+        Ok(())
+    } else {
+        Err(AppError::NotFound(format!(
+            "Process not in hide list: {}",
+            name
+        )))
+    }
+}
+
+/// Get the processes that hide the crosshair while focused
+#[command]
+pub fn get_hide_processes(state: tauri::State<'_, Arc<AppState>>) -> Vec<String> {
+    state.get_hide_processes()
+}
@@ -41,6 +41,15 @@ pub const DEFAULT_COLOR: &str = "#00FF00";
 /// Default crosshair image filename
 pub const DEFAULT_CROSSHAIR: &str = "crosshair-default.png";
 
+/// Maximum crosshair drop shadow blur in pixels
+pub const MAX_SHADOW_BLUR: u32 = 32;
+
+/// Minimum crosshair outline width in pixels
+pub const MIN_OUTLINE_WIDTH: u32 = 1;
+
+/// Maximum crosshair outline width in pixels
+pub const MAX_OUTLINE_WIDTH: u32 = 10;
+
 /// Maximum number of shadow (duplicate) windows allowed
 pub const MAX_SHADOW_WINDOWS: usize = 14;
 
@@ -53,14 +62,99 @@ pub const WINDOW_ASPECT_RATIO: f64 = 1.0;
 /// Debounce interval for mouse following in milliseconds
 pub const MOUSE_FOLLOW_DEBOUNCE_MS: u64 = 16; // ~60 FPS
 
+/// Default mouse following frame rate, matching `MOUSE_FOLLOW_DEBOUNCE_MS`
+pub const DEFAULT_MOUSE_FOLLOW_FPS: u32 = 60;
+
+/// Lowest allowed mouse following frame rate
+pub const MIN_MOUSE_FOLLOW_FPS: u32 = 1;
+
+/// Highest allowed mouse following frame rate
+pub const MAX_MOUSE_FOLLOW_FPS: u32 = 240;
+
+/// Number of recent follow moves used to measure the actual frame rate
+pub const FOLLOW_FPS_SAMPLE_COUNT: usize = 60;
+
+/// Follow moves older than this no longer count towards the frame rate
+pub const FOLLOW_FPS_STALE_MS: u64 = 1000;
+
+/// Number of times the global mouse listener is started before giving up
+pub const MOUSE_LISTEN_MAX_ATTEMPTS: u32 = 5;
+
+/// Delay before the first mouse listener retry, doubled after each failure
+pub const MOUSE_LISTEN_RETRY_MS: u64 = 500;
+
+/// Interval between display layout checks in milliseconds
+pub const DISPLAY_POLL_INTERVAL_MS: u64 = 2000;
+
+/// Interval between running process checks in milliseconds
+pub const PROCESS_POLL_INTERVAL_MS: u64 = 2000;
+
+/// Interval between foreground application checks in milliseconds
+pub const FOCUS_POLL_INTERVAL_MS: u64 = 500;
+
+/// Opacity change per scroll wheel notch
+pub const SCROLL_OPACITY_STEP: f64 = 0.05;
+
+/// Number of window positions kept for undo
+pub const MAX_POSITION_HISTORY: usize = 20;
+
+/// Number of recently used crosshairs listed in the tray
+pub const MAX_RECENT_CROSSHAIRS: usize = 5;
+
+/// Number of crosshairs kept for back and forward navigation
+pub const MAX_CROSSHAIR_HISTORY: usize = 10;
+
+/// Maximum `set_size` calls per second
+pub const RATE_LIMIT_SET_SIZE: u32 = 60;
+
+/// Maximum `set_opacity` calls per second
+pub const RATE_LIMIT_SET_OPACITY: u32 = 60;
+
+/// Maximum `set_color` calls per second
+pub const RATE_LIMIT_SET_COLOR: u32 = 30;
+
+/// Maximum `create_shadow_window` calls per second
+pub const RATE_LIMIT_CREATE_SHADOW_WINDOW: u32 = 5;
+
+/// Window level that behaves like a regular window
+pub const WINDOW_LEVEL_NORMAL: u8 = 0;
+
+/// Window level that floats above regular windows
+pub const WINDOW_LEVEL_FLOATING: u8 = 1;
+
+/// Window level above the screen saver and fullscreen apps
+pub const WINDOW_LEVEL_SCREEN_SAVER: u8 = 2;
+
+/// Maximum delay before hiding the crosshair on ADS in milliseconds
+pub const MAX_HIDE_ON_ADS_DELAY_MS: u64 = 2000;
+
+/// Minimum blink interval in milliseconds
+pub const MIN_BLINK_INTERVAL_MS: u64 = 50;
+
 /// Save debounce interval in milliseconds
 pub const SAVE_DEBOUNCE_MS: u64 = 500;
 
-/// Move increment in pixels for keyboard movement
-pub const MOVE_INCREMENT: i32 = 1;
+/// Default move step in pixels for keyboard movement
+pub const MOVE_INCREMENT: u32 = 1;
+
+/// Default fast move step in pixels
+pub const FAST_MOVE_INCREMENT: u32 = 10;
 
-/// Fast move increment in pixels (when shift is held)
-pub const FAST_MOVE_INCREMENT: i32 = 10;
+/// Minimum keyboard move step in pixels
+pub const MIN_MOVE_STEP_PX: u32 = 1;
+
+/// Maximum keyboard move step in pixels
+pub const MAX_MOVE_STEP_PX: u32 = 100;
+
+/// Pixels of the window kept on screen when clamping to a monitor
+pub const CLAMP_MARGIN_PX: i32 = 20;
+
+/// Number of preference backups kept when saving
+pub const BACKUP_COUNT: usize = 3;
+
+/// Minimum time between preference backups, so auto-save doesn't rotate
+/// every copy away within a few seconds
+pub const BACKUP_INTERVAL_SECS: u64 = 600;
 
 /// Application name
 pub const APP_NAME: &str = "CrossOver";
@@ -68,9 +162,36 @@ pub const APP_NAME: &str = "CrossOver";
 /// Application identifier
 pub const APP_ID: &str = "com.lacymorrow.crossover";
 
+/// Lock file, in the temp directory, held by the running instance
+pub const INSTANCE_LOCK_FILENAME: &str = "crossover-instance.lock";
+
 /// Settings store filename
 pub const SETTINGS_STORE_FILENAME: &str = "crossover-settings.json";
 
+/// Store key holding the preferences
+pub const STORE_KEY_PREFERENCES: &str = "crossover.v1.preferences";
+
+/// Store key used for the preferences before keys were namespaced
+pub const LEGACY_STORE_KEY_PREFERENCES: &str = "preferences";
+
+/// Store key holding the last shadow window ID, so IDs stay unique across sessions
+pub const STORE_KEY_SHADOW_COUNTER: &str = "crossover.v1.shadow_counter";
+
+/// Maximum total size of cached crosshair images in bytes
+pub const CROSSHAIR_CACHE_MAX_BYTES: usize = 50 * 1024 * 1024;
+
+/// Number of crosshair previews kept in memory
+pub const PREVIEW_CACHE_SIZE: usize = 20;
+
+/// Number of picker thumbnails kept in memory, enough for a full library
+pub const THUMBNAIL_CACHE_SIZE: usize = 200;
+
+/// Width and height of crosshair previews in pixels
+pub const PREVIEW_SIZE: u32 = 64;
+
+/// Width and height of crosshair picker thumbnails in pixels
+pub const THUMBNAIL_SIZE: u32 = 48;
+
 /// Supported crosshair image extensions
 pub const SUPPORTED_IMAGE_EXTENSIONS: &[&str] = &["png", "svg", "gif", "jpg", "jpeg", "webp"];
 
@@ -91,6 +212,11 @@ pub mod keybinds {
     pub const MOVE_LEFT: &str = "Control+Shift+Alt+Left";
     pub const MOVE_RIGHT: &str = "Control+Shift+Alt+Right";
     pub const NEXT_WINDOW: &str = "Control+Shift+Alt+O";
+    pub const ROTATE_CW: &str = "Control+Shift+Alt+E";
+    pub const ROTATE_CCW: &str = "Control+Shift+Alt+W";
+    pub const TOGGLE_FLIP_HORIZONTAL: &str = "Control+Shift+Alt+F";
+    pub const UNDO_MOVE: &str = "Control+Shift+Alt+Z";
+    pub const REDO_MOVE: &str = "Control+Shift+Alt+Y";
 }
 
 /// Sound effect names
@@ -98,8 +224,14 @@ pub mod sounds {
     pub const LOCK: &str = "lock";
     pub const UNLOCK: &str = "unlock";
     pub const CENTER: &str = "center";
+
+    /// Every sound the app can play
+    pub const ALL: &[&str] = &[LOCK, UNLOCK, CENTER];
 }
 
+/// Supported custom sound file extensions
+pub const SUPPORTED_SOUND_EXTENSIONS: &[&str] = &["wav", "mp3", "ogg"];
+
 /// Reticle types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum ReticleType {
@@ -137,6 +269,7 @@ impl ReticleType {
 
 /// Theme options
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Theme {
     Light,
     Dark,
@@ -158,6 +291,10 @@ impl Theme {
         }
     }
 
+    pub fn all() -> &'static [&'static str] {
+        &["light", "dark", "system"]
+    }
+
     pub fn from_str(s: &str) -> Self {
         match s.to_lowercase().as_str() {
             "light" => Theme::Light,
@@ -167,6 +304,58 @@ impl Theme {
     }
 }
 
+/// Crosshair color presets for common color vision deficiencies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorBlindnessPreset {
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+    HighContrast,
+}
+
+impl ColorBlindnessPreset {
+    pub const ALL: [Self; 4] = [
+        Self::Protanopia,
+        Self::Deuteranopia,
+        Self::Tritanopia,
+        Self::HighContrast,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Protanopia => "protanopia",
+            Self::Deuteranopia => "deuteranopia",
+            Self::Tritanopia => "tritanopia",
+            Self::HighContrast => "highcontrast",
+        }
+    }
+
+    /// Recommended crosshair color for the preset
+    pub fn color(&self) -> &'static str {
+        match self {
+            // Red-green deficiencies see blues and yellows most clearly
+            Self::Protanopia => "#0095FF",
+            Self::Deuteranopia => "#FFB000",
+            // Blue-yellow deficiency keeps reds and pinks distinct
+            Self::Tritanopia => "#FF1F5B",
+            Self::HighContrast => "#FFFF00",
+        }
+    }
+}
+
+impl std::str::FromStr for ColorBlindnessPreset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase().replace(['_', '-', ' '], "");
+        Self::ALL
+            .into_iter()
+            .find(|preset| preset.as_str() == name)
+            .ok_or_else(|| format!("Unknown color blindness preset: {}", s))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -192,4 +381,32 @@ mod tests {
         assert_eq!(Theme::from_str("dark"), Theme::Dark);
         assert_eq!(Theme::from_str("unknown"), Theme::System);
     }
+
+    #[test]
+    fn test_color_blindness_presets() {
+        let expected = [
+            (ColorBlindnessPreset::Protanopia, "#0095FF"),
+            (ColorBlindnessPreset::Deuteranopia, "#FFB000"),
+            (ColorBlindnessPreset::Tritanopia, "#FF1F5B"),
+            (ColorBlindnessPreset::HighContrast, "#FFFF00"),
+        ];
+        for (preset, color) in expected {
+            assert_eq!(preset.color(), color);
+            assert_eq!(crate::color::validate_color(color).unwrap(), color);
+            assert_eq!(preset.as_str().parse::<ColorBlindnessPreset>(), Ok(preset));
+        }
+
+        assert_eq!(
+            "High_Contrast".parse::<ColorBlindnessPreset>(),
+            Ok(ColorBlindnessPreset::HighContrast)
+        );
+        assert!("achromatopsia".parse::<ColorBlindnessPreset>().is_err());
+    }
+
+    #[test]
+    fn test_theme_all() {
+        for name in Theme::all() {
+            assert_eq!(Theme::from_str(name).as_str(), *name);
+        }
+    }
 }
@@ -7,11 +7,15 @@
 
 #![allow(dead_code)]
 
+use base64::{engine::general_purpose::STANDARD, Engine as _};
 use log::{debug, info};
+use std::collections::{HashMap, VecDeque};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use tauri::{AppHandle, Manager, Runtime};
 
-use crate::config::SUPPORTED_IMAGE_EXTENSIONS;
+use crate::config::{DEFAULT_COLOR, PREVIEW_SIZE, SUPPORTED_IMAGE_EXTENSIONS, THUMBNAIL_SIZE};
+use crate::state::{self, AppState};
 
 /// Crosshair image information
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -30,6 +34,46 @@ pub struct CrosshairInfo {
 
     /// Whether this is a custom user crosshair
     pub is_custom: bool,
+
+    /// Whether the user marked this crosshair as a favorite
+    #[serde(default)]
+    pub is_favorite: bool,
+
+    /// Small picker thumbnail, only filled in by [`CrosshairInfo::with_thumbnail`]
+    #[serde(default)]
+    pub thumbnail_data_url: Option<String>,
+}
+
+/// Which directory a crosshair was found in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrosshairSource {
+    Builtin,
+    Custom,
+}
+
+/// An image inside a crosshair pack archive
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PackEntry {
+    /// Filename the image would be imported as
+    pub filename: String,
+
+    /// Uncompressed size of the image
+    pub size_bytes: u64,
+
+    /// Whether a custom crosshair with this name already exists
+    pub conflicting: bool,
+}
+
+/// Image details for a crosshair file
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CrosshairMetadata {
+    pub filename: String,
+    pub width: u32,
+    pub height: u32,
+    pub format: String,
+    pub file_size_bytes: u64,
+    pub is_animated: bool,
 }
 
 impl CrosshairInfo {
@@ -44,8 +88,227 @@ impl CrosshairInfo {
             path,
             is_builtin,
             is_custom: !is_builtin,
+            is_favorite: false,
+            thumbnail_data_url: None,
         })
     }
+
+    /// Fill in the picker thumbnail, using the cached copy when possible
+    ///
+    /// Thumbnails that fail to render are left as `None` so one broken image
+    /// doesn't hide the rest of the list.
+    pub fn with_thumbnail<R: Runtime>(mut self, app: &AppHandle<R>) -> Self {
+        let app_state = app.try_state::<Arc<AppState>>();
+        if let Some(cached) = app_state
+            .as_ref()
+            .and_then(|state| state.get_cached_thumbnail(&self.filename))
+        {
+            self.thumbnail_data_url = Some(cached);
+            return self;
+        }
+
+        match render_thumbnail(&self.path) {
+            Ok(data_url) => {
+                if let Some(state) = app_state {
+                    state.cache_thumbnail(&self.filename, data_url.clone());
+                }
+                self.thumbnail_data_url = Some(data_url);
+            }
+            Err(e) => debug!("No thumbnail for {}: {}", self.filename, e),
+        }
+        self
+    }
+}
+
+/// Render a crosshair file as a `THUMBNAIL_SIZE` PNG data URL
+fn render_thumbnail(path: &Path) -> Result<String, String> {
+    let is_svg = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("svg"));
+    let bytes =
+        std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let png = if is_svg {
+        svg_thumbnail(&bytes, THUMBNAIL_SIZE)?
+    } else {
+        thumbnail(&bytes, THUMBNAIL_SIZE)?
+    };
+    Ok(format!("data:image/png;base64,{}", STANDARD.encode(png)))
+}
+
+/// Least-recently-added cache of picker thumbnails keyed by filename
+///
+/// The oldest thumbnail is dropped once the cache is full.
+#[derive(Debug, Default)]
+pub struct ThumbnailCache {
+    entries: HashMap<String, String>,
+    order: VecDeque<String>,
+    capacity: usize,
+}
+
+impl ThumbnailCache {
+    /// Create an empty cache holding at most `capacity` thumbnails
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ..Self::default()
+        }
+    }
+
+    /// Get a cached thumbnail
+    pub fn get(&self, filename: &str) -> Option<String> {
+        self.entries.get(filename).cloned()
+    }
+
+    /// Insert a thumbnail, dropping the oldest if the cache is full
+    pub fn insert(&mut self, filename: &str, data_url: String) {
+        if self
+            .entries
+            .insert(filename.to_string(), data_url)
+            .is_none()
+        {
+            self.order.push_back(filename.to_string());
+        }
+
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+
+    /// Remove the thumbnail for a file
+    pub fn remove(&mut self, filename: &str) {
+        self.entries.remove(filename);
+        self.order.retain(|cached| cached != filename);
+    }
+}
+
+/// Size-capped least-recently-used cache of crosshair image bytes
+#[derive(Debug, Default)]
+pub struct CrosshairCache {
+    entries: HashMap<String, Vec<u8>>,
+    order: VecDeque<String>,
+    total_bytes: usize,
+    max_bytes: usize,
+}
+
+impl CrosshairCache {
+    /// Create an empty cache holding at most `max_bytes`
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            max_bytes,
+            ..Self::default()
+        }
+    }
+
+    /// Get the cached bytes for a file, marking it as recently used
+    pub fn get(&mut self, filename: &str) -> Option<Vec<u8>> {
+        let bytes = self.entries.get(filename)?.clone();
+        self.touch(filename);
+        Some(bytes)
+    }
+
+    /// Insert bytes for a file, evicting the least recently used entries
+    /// until the cache fits within its size limit
+    pub fn insert(&mut self, filename: &str, bytes: Vec<u8>) {
+        self.remove(filename);
+
+        if bytes.len() > self.max_bytes {
+            return;
+        }
+
+        self.total_bytes += bytes.len();
+        self.entries.insert(filename.to_string(), bytes);
+        self.order.push_back(filename.to_string());
+
+        while self.total_bytes > self.max_bytes {
+            match self.order.pop_front() {
+                Some(oldest) => {
+                    if let Some(evicted) = self.entries.remove(&oldest) {
+                        self.total_bytes -= evicted.len();
+                    }
+                }
+                None => break,
+            }
+        }
+    }
+
+    /// Remove a file from the cache
+    pub fn remove(&mut self, filename: &str) {
+        if let Some(bytes) = self.entries.remove(filename) {
+            self.total_bytes -= bytes.len();
+            self.order.retain(|name| name != filename);
+        }
+    }
+
+    /// Total size of all cached entries in bytes
+    pub fn total_bytes(&self) -> usize {
+        self.total_bytes
+    }
+
+    fn touch(&mut self, filename: &str) {
+        self.order.retain(|name| name != filename);
+        self.order.push_back(filename.to_string());
+    }
+}
+
+/// Fixed-size cache of preview data URLs keyed by (filename, color)
+///
+/// The oldest preview is dropped once the cache is full.
+#[derive(Debug, Default)]
+pub struct PreviewCache {
+    entries: HashMap<(String, String), String>,
+    order: VecDeque<(String, String)>,
+    capacity: usize,
+}
+
+impl PreviewCache {
+    /// Create an empty cache holding at most `capacity` previews
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ..Self::default()
+        }
+    }
+
+    /// Get a cached preview
+    pub fn get(&self, filename: &str, color: &str) -> Option<String> {
+        self.entries
+            .get(&(filename.to_string(), color.to_string()))
+            .cloned()
+    }
+
+    /// Insert a preview, dropping the oldest if the cache is full
+    pub fn insert(&mut self, filename: &str, color: &str, data_url: String) {
+        let key = (filename.to_string(), color.to_string());
+        if self.entries.insert(key.clone(), data_url).is_none() {
+            self.order.push_back(key);
+        }
+
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+
+    /// Remove all previews for a file
+    pub fn remove(&mut self, filename: &str) {
+        self.entries.retain(|(cached, _), _| cached != filename);
+        self.order.retain(|(cached, _)| cached != filename);
+    }
+}
+
+/// Get the MIME type for a crosshair image extension
+pub fn mime_type(extension: &str) -> &'static str {
+    match extension.to_lowercase().as_str() {
+        "svg" => "image/svg+xml",
+        "gif" => "image/gif",
+        "jpg" | "jpeg" => "image/jpeg",
+        "webp" => "image/webp",
+        _ => "image/png",
+    }
 }
 
 /// Get the path to the built-in crosshairs directory
@@ -57,7 +320,15 @@ pub fn get_builtin_crosshairs_dir<R: Runtime>(app: &AppHandle<R>) -> Result<Path
 }
 
 /// Get the path to the custom crosshairs directory
+///
+/// In portable mode this is the `crosshairs` directory next to the executable.
 pub fn get_custom_crosshairs_dir<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    if let Some(app_state) = app.try_state::<Arc<AppState>>() {
+        if app_state.portable_mode {
+            return state::portable_dir().map(|p| p.join("crosshairs"));
+        }
+    }
+
     app.path()
         .app_data_dir()
         .map(|p| p.join("crosshairs"))
@@ -66,29 +337,84 @@ pub fn get_custom_crosshairs_dir<R: Runtime>(app: &AppHandle<R>) -> Result<PathB
 
 /// List all available crosshair images
 pub fn list_crosshairs<R: Runtime>(app: &AppHandle<R>) -> Result<Vec<CrosshairInfo>, String> {
-    let mut crosshairs = Vec::new();
+    let mut builtin = Vec::new();
+    let mut custom = Vec::new();
 
     // List built-in crosshairs
     if let Ok(builtin_dir) = get_builtin_crosshairs_dir(app) {
         if builtin_dir.exists() {
-            crosshairs.extend(list_crosshairs_in_dir(&builtin_dir, true)?);
+            builtin = list_crosshairs_in_dir(&builtin_dir, true)?;
         }
     }
 
     // List custom crosshairs
     if let Ok(custom_dir) = get_custom_crosshairs_dir(app) {
         if custom_dir.exists() {
-            crosshairs.extend(list_crosshairs_in_dir(&custom_dir, false)?);
+            custom = list_crosshairs_in_dir(&custom_dir, false)?;
         }
     }
 
-    // Sort by name
-    crosshairs.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    let mut crosshairs = merge_crosshairs(builtin, custom);
+    if let Some(state) = app.try_state::<Arc<AppState>>() {
+        mark_favorites(&mut crosshairs, &state.get_crosshair_favorites());
+    }
 
     info!("Found {} crosshairs", crosshairs.len());
     Ok(crosshairs)
 }
 
+/// Combine built-in and custom crosshairs sorted by name
+///
+/// A custom crosshair with the same filename as a built-in one overrides it.
+pub fn merge_crosshairs(
+    builtin: Vec<CrosshairInfo>,
+    custom: Vec<CrosshairInfo>,
+) -> Vec<CrosshairInfo> {
+    let mut by_filename: HashMap<String, CrosshairInfo> = HashMap::new();
+    for info in builtin.into_iter().chain(custom) {
+        by_filename.insert(info.filename.clone(), info);
+    }
+
+    let mut crosshairs: Vec<CrosshairInfo> = by_filename.into_values().collect();
+    crosshairs.sort_by(|a, b| {
+        a.name
+            .to_lowercase()
+            .cmp(&b.name.to_lowercase())
+            .then_with(|| a.filename.cmp(&b.filename))
+    });
+    crosshairs
+}
+
+/// Flag the crosshairs whose filename is in the favorites list
+pub fn mark_favorites(crosshairs: &mut [CrosshairInfo], favorites: &[String]) {
+    for info in crosshairs {
+        info.is_favorite = favorites.contains(&info.filename);
+    }
+}
+
+/// List a window of all crosshairs, also returning the total count
+pub fn list_crosshairs_range<R: Runtime>(
+    app: &AppHandle<R>,
+    offset: usize,
+    limit: usize,
+) -> Result<(Vec<CrosshairInfo>, usize), String> {
+    let crosshairs = list_crosshairs(app)?;
+    let total = crosshairs.len();
+    Ok((
+        crosshairs.into_iter().skip(offset).take(limit).collect(),
+        total,
+    ))
+}
+
+/// Keep crosshairs whose name contains the query, ignoring case
+pub fn filter_by_name(crosshairs: Vec<CrosshairInfo>, query: &str) -> Vec<CrosshairInfo> {
+    let query = query.trim().to_lowercase();
+    crosshairs
+        .into_iter()
+        .filter(|c| c.name.to_lowercase().contains(&query))
+        .collect()
+}
+
 /// List crosshair images in a specific directory
 fn list_crosshairs_in_dir(dir: &Path, is_builtin: bool) -> Result<Vec<CrosshairInfo>, String> {
     let mut crosshairs = Vec::new();
@@ -117,34 +443,102 @@ fn list_crosshairs_in_dir(dir: &Path, is_builtin: bool) -> Result<Vec<CrosshairI
     Ok(crosshairs)
 }
 
+/// Whether a crosshair filename is a single plain path component
+///
+/// Rejects separators, `.`/`..` and absolute paths so a name can never point
+/// outside the crosshair directories.
+fn is_plain_filename(filename: &str) -> bool {
+    let mut components = Path::new(filename).components();
+    matches!(
+        (components.next(), components.next()),
+        (Some(std::path::Component::Normal(_)), None)
+    ) && !filename.contains(['/', '\\'])
+}
+
+/// Find a crosshair file, checking the custom directory before the built-in one
+fn find_crosshair_in(
+    filename: &str,
+    custom_dir: Option<&Path>,
+    builtin_dir: Option<&Path>,
+) -> Option<(PathBuf, CrosshairSource)> {
+    if !is_plain_filename(filename) {
+        return None;
+    }
+
+    [
+        (custom_dir, CrosshairSource::Custom),
+        (builtin_dir, CrosshairSource::Builtin),
+    ]
+    .into_iter()
+    .filter_map(|(dir, source)| Some((dir?.join(filename), source)))
+    .find(|(path, _)| path.is_file())
+}
+
+/// Find a crosshair file and the directory it came from
+fn locate_crosshair<R: Runtime>(
+    app: &AppHandle<R>,
+    filename: &str,
+) -> Option<(PathBuf, CrosshairSource)> {
+    find_crosshair_in(
+        filename,
+        get_custom_crosshairs_dir(app).ok().as_deref(),
+        get_builtin_crosshairs_dir(app).ok().as_deref(),
+    )
+}
+
+/// Check whether a crosshair file exists in either directory
+pub fn crosshair_exists<R: Runtime>(app: &AppHandle<R>, filename: &str) -> bool {
+    locate_crosshair(app, filename).is_some()
+}
+
+/// Get which directory a crosshair would be loaded from
+///
+/// Custom crosshairs take priority over built-in ones with the same name.
+pub fn crosshair_source<R: Runtime>(app: &AppHandle<R>, filename: &str) -> Option<CrosshairSource> {
+    locate_crosshair(app, filename).map(|(_, source)| source)
+}
+
 /// Validate that a crosshair file exists and is a valid image
+///
+/// Custom crosshairs take priority over built-in ones with the same name.
 pub fn validate_crosshair<R: Runtime>(
     app: &AppHandle<R>,
     filename: &str,
 ) -> Result<PathBuf, String> {
-    // Check built-in crosshairs first
-    if let Ok(builtin_dir) = get_builtin_crosshairs_dir(app) {
-        let builtin_path = builtin_dir.join(filename);
-        if builtin_path.exists() && builtin_path.is_file() {
-            return Ok(builtin_path);
-        }
-    }
-
-    // Check custom crosshairs
-    if let Ok(custom_dir) = get_custom_crosshairs_dir(app) {
-        let custom_path = custom_dir.join(filename);
-        if custom_path.exists() && custom_path.is_file() {
-            return Ok(custom_path);
-        }
-    }
-
-    Err(format!("Crosshair not found: {}", filename))
+    locate_crosshair(app, filename)
+        .map(|(path, _)| path)
+        .ok_or_else(|| format!("Crosshair not found: {}", filename))
 }
 
 /// Import a custom crosshair from an external path
+///
+/// Raster images are converted to PNG when `normalize_on_import` is enabled.
 pub fn import_crosshair<R: Runtime>(
     app: &AppHandle<R>,
     source_path: &Path,
+) -> Result<CrosshairInfo, String> {
+    let state = app.try_state::<Arc<AppState>>();
+    let normalize = state
+        .as_ref()
+        .is_some_and(|state| state.get_normalize_on_import());
+
+    import_crosshair_into(
+        source_path,
+        &get_custom_crosshairs_dir(app)?,
+        normalize,
+        state.as_ref().map(|state| state.inner().as_ref()),
+    )
+}
+
+/// Import a crosshair into a directory, optionally re-encoding it as PNG
+///
+/// Cached data for a crosshair of the same name is dropped from `state`, so
+/// a replaced file is not shown from stale caches.
+pub fn import_crosshair_into(
+    source_path: &Path,
+    custom_dir: &Path,
+    normalize: bool,
+    state: Option<&AppState>,
 ) -> Result<CrosshairInfo, String> {
     // Validate it's an image file
     let ext = source_path
@@ -156,12 +550,9 @@ pub fn import_crosshair<R: Runtime>(
         return Err(format!("Unsupported image format: {}", ext));
     }
 
-    // Get the custom crosshairs directory
-    let custom_dir = get_custom_crosshairs_dir(app)?;
-
     // Create directory if it doesn't exist
     if !custom_dir.exists() {
-        std::fs::create_dir_all(&custom_dir)
+        std::fs::create_dir_all(custom_dir)
             .map_err(|e| format!("Failed to create custom crosshairs directory: {}", e))?;
     }
 
@@ -171,55 +562,695 @@ pub fn import_crosshair<R: Runtime>(
         .and_then(|n| n.to_str())
         .ok_or("Invalid filename")?;
 
-    let dest_path = custom_dir.join(filename);
+    let dest_path = if normalize && !matches!(ext.to_lowercase().as_str(), "png" | "svg") {
+        // Some webviews render GIF, JPEG or WEBP inconsistently
+        let dest_path = custom_dir.join(filename).with_extension("png");
+        image::open(source_path)
+            .map_err(|e| format!("Failed to decode crosshair: {}", e))?
+            .into_rgba8()
+            .save_with_format(&dest_path, image::ImageFormat::Png)
+            .map_err(|e| format!("Failed to write crosshair: {}", e))?;
+        dest_path
+    } else {
+        let dest_path = custom_dir.join(filename);
+        std::fs::copy(source_path, &dest_path)
+            .map_err(|e| format!("Failed to copy crosshair: {}", e))?;
+        dest_path
+    };
 
-    // Copy the file
-    std::fs::copy(source_path, &dest_path)
-        .map_err(|e| format!("Failed to copy crosshair: {}", e))?;
+    info!("Imported custom crosshair: {:?}", dest_path);
 
-    info!("Imported custom crosshair: {}", filename);
-
-    CrosshairInfo::from_path(dest_path, false)
-        .ok_or_else(|| "Failed to create crosshair info".to_string())
+    let info = CrosshairInfo::from_path(dest_path, false)
+        .ok_or_else(|| "Failed to create crosshair info".to_string())?;
+    if let Some(state) = state {
+        state.evict_crosshair(&info.filename);
+    }
+    Ok(info)
 }
 
 /// Delete a custom crosshair
 pub fn delete_crosshair<R: Runtime>(app: &AppHandle<R>, filename: &str) -> Result<(), String> {
-    let custom_dir = get_custom_crosshairs_dir(app)?;
-    let path = custom_dir.join(filename);
-
-    if !path.exists() {
-        return Err(format!("Crosshair not found: {}", filename));
-    }
-
-    // Make sure we're not deleting a built-in crosshair
-    if let Ok(builtin_dir) = get_builtin_crosshairs_dir(app) {
-        if path.starts_with(&builtin_dir) {
-            return Err("Cannot delete built-in crosshairs".to_string());
+    let path = match locate_crosshair(app, filename) {
+        Some((path, CrosshairSource::Custom)) => path,
+        Some((_, CrosshairSource::Builtin)) => {
+            return Err("Cannot delete built-in crosshairs".to_string())
         }
-    }
+        None => return Err(format!("Crosshair not found: {}", filename)),
+    };
 
     std::fs::remove_file(&path).map_err(|e| format!("Failed to delete crosshair: {}", e))?;
 
+    // The asset scope entry is left alone: denials can't be lifted, so a
+    // crosshair imported later under the same name would never load
+    if let Some(app_state) = app.try_state::<Arc<AppState>>() {
+        app_state.evict_crosshair(filename);
+    }
+
     info!("Deleted custom crosshair: {}", filename);
     Ok(())
 }
 
 /// Get the URL for a crosshair image (for use in the webview)
+///
+/// The file is added to the asset protocol scope so the webview is allowed to
+/// load it. This matters for custom crosshairs, which live outside the
+/// resource dir.
 pub fn get_crosshair_url<R: Runtime>(app: &AppHandle<R>, filename: &str) -> Result<String, String> {
     let path = validate_crosshair(app, filename)?;
 
-    // Convert to a file:// URL or asset URL depending on platform
-    // For Tauri, we use the asset protocol
-    let url = format!("asset://localhost/{}", path.display());
+    app.asset_protocol_scope()
+        .allow_file(&path)
+        .map_err(|e| format!("Failed to allow crosshair in asset scope: {}", e))?;
+
+    Ok(asset_url(&path))
+}
+
+/// Remove a crosshair from the asset protocol scope
+pub fn revoke_crosshair_url<R: Runtime>(app: &AppHandle<R>, filename: &str) -> Result<(), String> {
+    if !is_plain_filename(filename) {
+        return Err(format!("Invalid crosshair name: {}", filename));
+    }
+    let path = get_custom_crosshairs_dir(app)?.join(filename);
+    app.asset_protocol_scope()
+        .forbid_file(&path)
+        .map_err(|e| format!("Failed to revoke crosshair from asset scope: {}", e))
+}
+
+/// Build an asset protocol URL for a file, matching the frontend's `convertFileSrc`
+pub fn asset_url(path: &Path) -> String {
+    let base = if cfg!(target_os = "windows") {
+        "http://asset.localhost/"
+    } else {
+        "asset://localhost/"
+    };
+
+    let mut url = String::from(base);
+    for byte in path.to_string_lossy().bytes() {
+        match byte {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'_'
+            | b'.'
+            | b'!'
+            | b'~'
+            | b'*'
+            | b'\''
+            | b'('
+            | b')' => url.push(byte as char),
+            _ => url.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    url
+}
+
+/// Replace the sentinel colors in an SVG with the given color
+///
+/// Both the default crosshair color (matched case-insensitively) and the
+/// `currentColor` keyword are replaced.
+pub fn colorize_svg(svg: &str, color: &str) -> String {
+    let sentinel = DEFAULT_COLOR.to_ascii_lowercase();
+    let lower = svg.to_ascii_lowercase();
+
+    let mut result = String::with_capacity(svg.len());
+    let mut last = 0;
+    for (index, _) in lower.match_indices(&sentinel) {
+        result.push_str(&svg[last..index]);
+        result.push_str(color);
+        last = index + sentinel.len();
+    }
+    result.push_str(&svg[last..]);
+
+    result.replace("currentColor", color)
+}
+
+/// Get a crosshair image as a base64 data URL, using the cache when possible
+pub fn get_crosshair_data<R: Runtime>(
+    app: &AppHandle<R>,
+    app_state: &AppState,
+    filename: &str,
+) -> Result<String, String> {
+    let extension = Path::new(filename)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+
+    let bytes = match app_state.get_crosshair_bytes(filename) {
+        Some(bytes) => bytes,
+        None => {
+            let path = validate_crosshair(app, filename)?;
+            let bytes = std::fs::read(&path)
+                .map_err(|e| format!("Failed to read crosshair {}: {}", filename, e))?;
+            app_state.cache_crosshair(filename, bytes.clone());
+            bytes
+        }
+    };
+
+    Ok(format!(
+        "data:{};base64,{}",
+        mime_type(extension),
+        STANDARD.encode(bytes)
+    ))
+}
+
+/// Get a crosshair's metadata, reading and caching it on first use
+pub fn get_crosshair_metadata<R: Runtime>(
+    app: &AppHandle<R>,
+    app_state: &AppState,
+    filename: &str,
+) -> Result<CrosshairMetadata, String> {
+    if let Some(metadata) = app_state.get_cached_metadata(filename) {
+        return Ok(metadata);
+    }
+
+    let bytes = match app_state.get_crosshair_bytes(filename) {
+        Some(bytes) => bytes,
+        None => {
+            let path = validate_crosshair(app, filename)?;
+            let bytes = std::fs::read(&path)
+                .map_err(|e| format!("Failed to read crosshair {}: {}", filename, e))?;
+            app_state.cache_crosshair(filename, bytes.clone());
+            bytes
+        }
+    };
+
+    let metadata = read_metadata(filename, &bytes)?;
+    app_state.cache_metadata(filename, metadata.clone());
+    Ok(metadata)
+}
+
+/// Read the format, dimensions and animation state of an image
+pub fn read_metadata(filename: &str, bytes: &[u8]) -> Result<CrosshairMetadata, String> {
+    let is_svg = Path::new(filename)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("svg"));
+
+    let (format, (width, height), is_animated) = if is_svg {
+        let svg = std::str::from_utf8(bytes).map_err(|_| "SVG is not valid UTF-8")?;
+        ("svg".to_string(), svg_dimensions(svg), false)
+    } else {
+        let format =
+            image::guess_format(bytes).map_err(|e| format!("Unrecognized image format: {}", e))?;
+        let dimensions = image::ImageReader::with_format(std::io::Cursor::new(bytes), format)
+            .into_dimensions()
+            .map_err(|e| format!("Failed to read image dimensions: {}", e))?;
+        let is_animated = format == image::ImageFormat::Gif && gif_frame_count(bytes)? > 1;
+        let name = format.extensions_str().first().copied().unwrap_or_default();
+        (name.to_string(), dimensions, is_animated)
+    };
+
+    Ok(CrosshairMetadata {
+        filename: filename.to_string(),
+        width,
+        height,
+        format,
+        file_size_bytes: bytes.len() as u64,
+        is_animated,
+    })
+}
+
+/// Count GIF frames, stopping once it is known to be animated
+fn gif_frame_count(bytes: &[u8]) -> Result<usize, String> {
+    use image::AnimationDecoder;
+
+    let decoder = image::codecs::gif::GifDecoder::new(std::io::Cursor::new(bytes))
+        .map_err(|e| format!("Failed to decode GIF: {}", e))?;
+    Ok(decoder.into_frames().take(2).filter(|f| f.is_ok()).count())
+}
+
+/// Read the size of an SVG from its root `width`/`height` or `viewBox`
+fn svg_dimensions(svg: &str) -> (u32, u32) {
+    let Some(start) = svg.find("<svg") else {
+        return (0, 0);
+    };
+    let root = &svg[start..svg[start..].find('>').map_or(svg.len(), |end| start + end)];
+
+    let attr = |name: &str| {
+        let needle = format!(" {}=\"", name);
+        let value_start = root.find(&needle)? + needle.len();
+        let value_end = root[value_start..].find('"')? + value_start;
+        Some(&root[value_start..value_end])
+    };
+    let number = |value: &str| {
+        value
+            .trim_end_matches("px")
+            .parse::<f64>()
+            .ok()
+            .map(|n| n.round() as u32)
+    };
+
+    if let (Some(width), Some(height)) = (
+        attr("width").and_then(number),
+        attr("height").and_then(number),
+    ) {
+        return (width, height);
+    }
+
+    attr("viewBox")
+        .map(|view_box| {
+            view_box
+                .split([' ', ','])
+                .filter(|part| !part.is_empty())
+                .filter_map(number)
+                .collect::<Vec<_>>()
+        })
+        .and_then(|parts| Some((*parts.get(2)?, *parts.get(3)?)))
+        .unwrap_or((0, 0))
+}
+
+/// Encode an SVG document as a base64 data URL
+pub fn svg_data_url(svg: &str) -> String {
+    format!("data:image/svg+xml;base64,{}", STANDARD.encode(svg))
+}
+
+/// Read an SVG crosshair and return it as a data URL in the given color
+pub fn get_colored_svg<R: Runtime>(
+    app: &AppHandle<R>,
+    filename: &str,
+    color: &str,
+) -> Result<String, String> {
+    let path = validate_crosshair(app, filename)?;
+
+    let is_svg = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("svg"));
+    if !is_svg {
+        return Err(format!("Crosshair is not an SVG: {}", filename));
+    }
+
+    let svg = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read crosshair {}: {}", filename, e))?;
+
+    Ok(svg_data_url(&colorize_svg(&svg, color)))
+}
+
+/// Render a crosshair preview as a data URL without applying it
+///
+/// Raster images are tinted with `color`, scaled to `PREVIEW_SIZE` with
+/// nearest-neighbor sampling and encoded as PNG. SVGs are recolored and
+/// returned as SVG, since they scale to any preview size.
+pub fn render_preview<R: Runtime>(
+    app: &AppHandle<R>,
+    filename: &str,
+    color: &str,
+) -> Result<String, String> {
+    let path = validate_crosshair(app, filename)?;
+
+    let is_svg = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("svg"));
+    if is_svg {
+        let svg = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read crosshair {}: {}", filename, e))?;
+        return Ok(svg_data_url(&colorize_svg(&svg, color)));
+    }
+
+    let bytes = std::fs::read(&path)
+        .map_err(|e| format!("Failed to read crosshair {}: {}", filename, e))?;
+    let png = tinted_thumbnail(&bytes, color, PREVIEW_SIZE)?;
+    Ok(format!("data:image/png;base64,{}", STANDARD.encode(png)))
+}
+
+/// Tint an image with a color and scale it to a square PNG thumbnail
+///
+/// Each visible pixel takes the tint color scaled by its own brightness, so
+/// white areas become the tint color and shading is preserved.
+pub fn tinted_thumbnail(bytes: &[u8], color: &str, size: u32) -> Result<Vec<u8>, String> {
+    use image::imageops::FilterType;
+
+    let (r, g, b) = crate::color::parse_rgb(color)?;
+    let mut rgba = image::load_from_memory(bytes)
+        .map_err(|e| format!("Failed to decode image: {}", e))?
+        .to_rgba8();
+
+    for pixel in rgba.pixels_mut() {
+        let [pr, pg, pb, _] = pixel.0;
+        let brightness = (pr as u32 * 299 + pg as u32 * 587 + pb as u32 * 114) / 1000;
+        let scale = |channel: u8| (channel as u32 * brightness / 255) as u8;
+        pixel.0[0] = scale(r);
+        pixel.0[1] = scale(g);
+        pixel.0[2] = scale(b);
+    }
+
+    encode_png(fit_to_square(&rgba, size, FilterType::Nearest))
+}
+
+/// Scale an image to a square PNG thumbnail, keeping its colors
+pub fn thumbnail(bytes: &[u8], size: u32) -> Result<Vec<u8>, String> {
+    use image::imageops::FilterType;
+
+    let rgba = image::load_from_memory(bytes)
+        .map_err(|e| format!("Failed to decode image: {}", e))?
+        .to_rgba8();
+    encode_png(fit_to_square(&rgba, size, FilterType::Triangle))
+}
+
+/// Render an SVG to a square PNG thumbnail, keeping its aspect ratio
+pub fn svg_thumbnail(bytes: &[u8], size: u32) -> Result<Vec<u8>, String> {
+    use resvg::{tiny_skia, usvg};
+
+    let tree = usvg::Tree::from_data(bytes, &usvg::Options::default())
+        .map_err(|e| format!("Failed to parse SVG: {}", e))?;
+    let mut pixmap = tiny_skia::Pixmap::new(size, size).ok_or("Invalid thumbnail size")?;
+
+    let tree_size = tree.size();
+    let scale = size as f32 / tree_size.width().max(tree_size.height());
+    let transform = tiny_skia::Transform::from_scale(scale, scale).post_translate(
+        (size as f32 - tree_size.width() * scale) / 2.0,
+        (size as f32 - tree_size.height() * scale) / 2.0,
+    );
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
+
+    pixmap
+        .encode_png()
+        .map_err(|e| format!("Failed to encode image: {}", e))
+}
+
+/// Scale an image to fit a transparent square, centered and without stretching
+fn fit_to_square(
+    image: &image::RgbaImage,
+    size: u32,
+    filter: image::imageops::FilterType,
+) -> image::RgbaImage {
+    let (width, height) = image.dimensions();
+    let scale = size as f64 / width.max(height).max(1) as f64;
+    let fit_width = ((width as f64 * scale).round() as u32).clamp(1, size);
+    let fit_height = ((height as f64 * scale).round() as u32).clamp(1, size);
+
+    let resized = image::imageops::resize(image, fit_width, fit_height, filter);
+    let mut square = image::RgbaImage::new(size, size);
+    image::imageops::overlay(
+        &mut square,
+        &resized,
+        ((size - fit_width) / 2) as i64,
+        ((size - fit_height) / 2) as i64,
+    );
+    square
+}
 
-    Ok(url)
+/// Encode an image as PNG bytes
+fn encode_png(image: image::RgbaImage) -> Result<Vec<u8>, String> {
+    let mut png = Vec::new();
+    image::DynamicImage::ImageRgba8(image)
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode image: {}", e))?;
+    Ok(png)
+}
+
+/// List the supported images in a zip crosshair pack without extracting it
+pub fn list_pack_contents(pack_path: &Path, custom_dir: &Path) -> Result<Vec<PackEntry>, String> {
+    let file = std::fs::File::open(pack_path)
+        .map_err(|e| format!("Failed to open crosshair pack: {}", e))?;
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|e| format!("Invalid crosshair pack: {}", e))?;
+
+    let mut entries = Vec::new();
+    for i in 0..archive.len() {
+        let entry = archive
+            .by_index(i)
+            .map_err(|e| format!("Failed to read crosshair pack: {}", e))?;
+        if entry.is_dir() {
+            continue;
+        }
+
+        // Packs may nest images in folders, but they import flat
+        let Some(filename) = entry
+            .enclosed_name()
+            .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+        else {
+            continue;
+        };
+
+        let supported = Path::new(&filename)
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| SUPPORTED_IMAGE_EXTENSIONS.contains(&e.to_lowercase().as_str()));
+        if !supported {
+            continue;
+        }
+
+        entries.push(PackEntry {
+            conflicting: custom_dir.join(&filename).exists(),
+            size_bytes: entry.size(),
+            filename,
+        });
+    }
+
+    Ok(entries)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_asset_url() {
+        let base = if cfg!(target_os = "windows") {
+            "http://asset.localhost/"
+        } else {
+            "asset://localhost/"
+        };
+
+        // Built-in crosshair in the resource dir
+        assert_eq!(
+            asset_url(Path::new("/usr/lib/crossover/crosshairs/dot.png")),
+            format!("{}%2Fusr%2Flib%2Fcrossover%2Fcrosshairs%2Fdot.png", base)
+        );
+        // Custom crosshair with spaces in the path
+        assert_eq!(
+            asset_url(Path::new(
+                "/home/me/.config/crossover/custom/My Cross (2).svg"
+            )),
+            format!(
+                "{}%2Fhome%2Fme%2F.config%2Fcrossover%2Fcustom%2FMy%20Cross%20(2).svg",
+                base
+            )
+        );
+    }
+
+    #[test]
+    fn test_merge_crosshairs_prefers_custom() {
+        let builtin: Vec<CrosshairInfo> = ["builtin/dot.png", "builtin/Cross.png"]
+            .iter()
+            .filter_map(|p| CrosshairInfo::from_path(PathBuf::from(p), true))
+            .collect();
+        let custom: Vec<CrosshairInfo> = ["custom/dot.png", "custom/arrow.svg"]
+            .iter()
+            .filter_map(|p| CrosshairInfo::from_path(PathBuf::from(p), false))
+            .collect();
+
+        let merged = merge_crosshairs(builtin, custom);
+        let filenames: Vec<&str> = merged.iter().map(|c| c.filename.as_str()).collect();
+        assert_eq!(filenames, vec!["arrow.svg", "Cross.png", "dot.png"]);
+
+        let dot = &merged[2];
+        assert!(dot.is_custom);
+        assert!(!dot.is_builtin);
+        assert_eq!(dot.path, PathBuf::from("custom/dot.png"));
+        assert!(merged[1].is_builtin);
+    }
+
+    #[test]
+    fn test_mark_favorites() {
+        let mut crosshairs: Vec<CrosshairInfo> = ["dot.png", "cross.png"]
+            .iter()
+            .filter_map(|f| CrosshairInfo::from_path(PathBuf::from(f), true))
+            .collect();
+        assert!(crosshairs.iter().all(|c| !c.is_favorite));
+
+        mark_favorites(&mut crosshairs, &["cross.png".to_string()]);
+        assert!(!crosshairs[0].is_favorite);
+        assert!(crosshairs[1].is_favorite);
+    }
+
+    #[test]
+    fn test_filter_by_name() {
+        let crosshairs: Vec<CrosshairInfo> = ["Red Dot.png", "dotted.svg", "cross.png"]
+            .iter()
+            .filter_map(|f| CrosshairInfo::from_path(PathBuf::from(f), true))
+            .collect();
+
+        let names: Vec<String> = filter_by_name(crosshairs.clone(), "DOT")
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        assert_eq!(names, vec!["Red Dot", "dotted"]);
+        assert_eq!(filter_by_name(crosshairs.clone(), "").len(), 3);
+        assert!(filter_by_name(crosshairs, "circle").is_empty());
+    }
+
+    #[test]
+    fn test_read_metadata() {
+        let mut png = Vec::new();
+        image::DynamicImage::new_rgba8(12, 8)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+        let metadata = read_metadata("dot.png", &png).unwrap();
+        assert_eq!((metadata.width, metadata.height), (12, 8));
+        assert_eq!(metadata.format, "png");
+        assert_eq!(metadata.file_size_bytes, png.len() as u64);
+        assert!(!metadata.is_animated);
+
+        let mut gif = Vec::new();
+        {
+            let mut encoder = image::codecs::gif::GifEncoder::new(&mut gif);
+            let frames = (0..2).map(|_| image::Frame::new(image::RgbaImage::new(4, 4)));
+            encoder.encode_frames(frames).unwrap();
+        }
+        let metadata = read_metadata("spin.gif", &gif).unwrap();
+        assert_eq!(metadata.format, "gif");
+        assert!(metadata.is_animated);
+
+        let svg = br#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 32"></svg>"#;
+        let metadata = read_metadata("cross.svg", svg).unwrap();
+        assert_eq!((metadata.width, metadata.height), (24, 32));
+        assert_eq!(metadata.format, "svg");
+    }
+
+    #[test]
+    fn test_is_plain_filename() {
+        assert!(is_plain_filename("dot.png"));
+        assert!(is_plain_filename("My Cross (2).svg"));
+        assert!(!is_plain_filename(""));
+        assert!(!is_plain_filename("."));
+        assert!(!is_plain_filename(".."));
+        assert!(!is_plain_filename("../dot.png"));
+        assert!(!is_plain_filename("sub/dot.png"));
+        assert!(!is_plain_filename("sub\\dot.png"));
+        assert!(!is_plain_filename("/etc/passwd"));
+    }
+
+    #[test]
+    fn test_find_crosshair_in() {
+        let dir = std::env::temp_dir().join(format!("crossover-find-{}", std::process::id()));
+        let custom_dir = dir.join("custom");
+        let builtin_dir = dir.join("builtin");
+        std::fs::create_dir_all(&custom_dir).unwrap();
+        std::fs::create_dir_all(&builtin_dir).unwrap();
+        std::fs::write(custom_dir.join("dot.png"), b"").unwrap();
+        std::fs::write(builtin_dir.join("dot.png"), b"").unwrap();
+        std::fs::write(builtin_dir.join("cross.png"), b"").unwrap();
+
+        let find = |filename| {
+            find_crosshair_in(filename, Some(&custom_dir), Some(&builtin_dir))
+                .map(|(_, source)| source)
+        };
+        let dot = find("dot.png");
+        let cross = find("cross.png");
+        let missing = find("missing.png");
+        let escaped = find("../builtin/cross.png");
+        let absolute = find_crosshair_in(
+            builtin_dir.join("cross.png").to_str().unwrap(),
+            Some(&custom_dir),
+            None,
+        );
+        let no_custom_dir = find_crosshair_in("dot.png", None, Some(&builtin_dir));
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(dot, Some(CrosshairSource::Custom));
+        assert_eq!(cross, Some(CrosshairSource::Builtin));
+        assert_eq!(missing, None);
+        assert_eq!(escaped, None);
+        assert_eq!(absolute, None);
+        assert_eq!(
+            no_custom_dir,
+            Some((builtin_dir.join("dot.png"), CrosshairSource::Builtin))
+        );
+    }
+
+    #[test]
+    fn test_import_normalizes_to_png() {
+        let dir = std::env::temp_dir().join(format!("crossover-import-{}", std::process::id()));
+        let custom_dir = dir.join("custom");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let source = dir.join("dot.jpg");
+        image::RgbImage::from_pixel(10, 10, image::Rgb([255, 0, 0]))
+            .save_with_format(&source, image::ImageFormat::Jpeg)
+            .unwrap();
+
+        let info = import_crosshair_into(&source, &custom_dir, true, None).unwrap();
+        let bytes = std::fs::read(&info.path).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(info.filename, "dot.png");
+        assert_eq!(
+            image::guess_format(&bytes).unwrap(),
+            image::ImageFormat::Png
+        );
+        let decoded = image::load_from_memory(&bytes).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (10, 10));
+    }
+
+    #[test]
+    fn test_list_pack_contents() {
+        use std::io::Write;
+
+        let dir = std::env::temp_dir().join(format!("crossover-pack-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("dot.png"), b"existing").unwrap();
+
+        let pack = dir.join("pack.zip");
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&pack).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        zip.start_file("dot.png", options).unwrap();
+        zip.write_all(b"png").unwrap();
+        zip.add_directory("nested/", options).unwrap();
+        zip.start_file("nested/cross.SVG", options).unwrap();
+        zip.write_all(b"<svg/>").unwrap();
+        zip.start_file("readme.txt", options).unwrap();
+        zip.write_all(b"hello").unwrap();
+        zip.finish().unwrap();
+
+        let entries = list_pack_contents(&pack, &dir).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].filename, "dot.png");
+        assert_eq!(entries[0].size_bytes, 3);
+        assert!(entries[0].conflicting);
+        assert_eq!(entries[1].filename, "cross.SVG");
+        assert!(!entries[1].conflicting);
+    }
+
+    #[test]
+    fn test_preview_cache_evicts_oldest() {
+        let mut cache = PreviewCache::new(2);
+        cache.insert("a.png", "#FF0000", "a".to_string());
+        cache.insert("b.png", "#FF0000", "b".to_string());
+        cache.insert("c.png", "#FF0000", "c".to_string());
+
+        assert_eq!(cache.get("a.png", "#FF0000"), None);
+        assert_eq!(cache.get("c.png", "#FF0000"), Some("c".to_string()));
+
+        cache.remove("c.png");
+        assert_eq!(cache.get("c.png", "#FF0000"), None);
+    }
+
+    #[test]
+    fn test_tinted_thumbnail() {
+        let mut source = image::RgbaImage::new(2, 2);
+        source.put_pixel(0, 0, image::Rgba([255, 255, 255, 255]));
+        let mut bytes = Vec::new();
+        image::DynamicImage::ImageRgba8(source)
+            .write_to(
+                &mut std::io::Cursor::new(&mut bytes),
+                image::ImageFormat::Png,
+            )
+            .unwrap();
+
+        let png = tinted_thumbnail(&bytes, "#FF0000", 4).unwrap();
+        let thumbnail = image::load_from_memory(&png).unwrap().to_rgba8();
+
+        assert_eq!(thumbnail.dimensions(), (4, 4));
+        assert_eq!(thumbnail.get_pixel(0, 0).0, [255, 0, 0, 255]);
+        assert_eq!(thumbnail.get_pixel(3, 3).0[3], 0);
+    }
+
     #[test]
     fn test_crosshair_info_from_path() {
         let path = PathBuf::from("/path/to/crosshair.png");
@@ -229,6 +1260,58 @@ mod tests {
         assert_eq!(info.name, "crosshair");
         assert!(info.is_builtin);
         assert!(!info.is_custom);
+        assert!(info.thumbnail_data_url.is_none());
+    }
+
+    #[test]
+    fn test_thumbnail_keeps_aspect_ratio() {
+        let source = image::RgbaImage::from_pixel(100, 60, image::Rgba([255, 255, 255, 255]));
+        let mut bytes = Vec::new();
+        image::DynamicImage::ImageRgba8(source)
+            .write_to(
+                &mut std::io::Cursor::new(&mut bytes),
+                image::ImageFormat::Png,
+            )
+            .unwrap();
+
+        let png = thumbnail(&bytes, THUMBNAIL_SIZE).unwrap();
+        let decoded = image::load_from_memory(&png).unwrap().to_rgba8();
+
+        // 100x60 scales to 48x29, letterboxed in the 48x48 square
+        assert_eq!(decoded.dimensions(), (48, 48));
+        assert_eq!(decoded.get_pixel(0, 0).0[3], 0);
+        assert_eq!(decoded.get_pixel(0, 47).0[3], 0);
+        assert_eq!(decoded.get_pixel(0, 24).0[3], 255);
+        assert_eq!(decoded.get_pixel(47, 24).0[3], 255);
+    }
+
+    #[test]
+    fn test_svg_thumbnail() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100"><rect width="200" height="100" fill="#00FF00"/></svg>"##;
+
+        let png = svg_thumbnail(svg.as_bytes(), THUMBNAIL_SIZE).unwrap();
+        let decoded = image::load_from_memory(&png).unwrap().to_rgba8();
+
+        assert_eq!(decoded.dimensions(), (48, 48));
+        assert_eq!(decoded.get_pixel(0, 0).0[3], 0);
+        assert_eq!(decoded.get_pixel(24, 24).0, [0, 255, 0, 255]);
+        assert!(svg_thumbnail(b"not an svg", THUMBNAIL_SIZE).is_err());
+    }
+
+    #[test]
+    fn test_thumbnail_cache_eviction() {
+        let mut cache = ThumbnailCache::new(2);
+        cache.insert("a.png", "a".to_string());
+        cache.insert("b.png", "b".to_string());
+        cache.insert("a.png", "a2".to_string());
+        cache.insert("c.png", "c".to_string());
+
+        assert_eq!(cache.get("a.png"), None);
+        assert_eq!(cache.get("b.png"), Some("b".to_string()));
+        assert_eq!(cache.get("c.png"), Some("c".to_string()));
+
+        cache.remove("b.png");
+        assert_eq!(cache.get("b.png"), None);
     }
 
     #[test]
@@ -237,4 +1320,60 @@ mod tests {
         assert!(SUPPORTED_IMAGE_EXTENSIONS.contains(&"svg"));
         assert!(!SUPPORTED_IMAGE_EXTENSIONS.contains(&"txt"));
     }
+
+    #[test]
+    fn test_crosshair_cache_lru_eviction() {
+        let mut cache = CrosshairCache::new(10);
+        cache.insert("a.png", vec![0; 4]);
+        cache.insert("b.png", vec![0; 4]);
+
+        // Touch "a" so "b" becomes the least recently used
+        assert!(cache.get("a.png").is_some());
+        cache.insert("c.png", vec![0; 4]);
+
+        assert!(cache.get("a.png").is_some());
+        assert!(cache.get("b.png").is_none());
+        assert!(cache.get("c.png").is_some());
+        assert_eq!(cache.total_bytes(), 8);
+    }
+
+    #[test]
+    fn test_crosshair_cache_remove_and_oversized() {
+        let mut cache = CrosshairCache::new(10);
+        cache.insert("a.png", vec![0; 4]);
+        cache.insert("huge.png", vec![0; 11]);
+        assert!(cache.get("huge.png").is_none());
+
+        cache.remove("a.png");
+        assert!(cache.get("a.png").is_none());
+        assert_eq!(cache.total_bytes(), 0);
+    }
+
+    #[test]
+    fn test_mime_type() {
+        assert_eq!(mime_type("PNG"), "image/png");
+        assert_eq!(mime_type("svg"), "image/svg+xml");
+        assert_eq!(mime_type("jpeg"), "image/jpeg");
+    }
+
+    #[test]
+    fn test_colorize_svg() {
+        let svg = r##"<svg><path fill="#00ff00"/><path stroke="#00FF00"/><g fill="currentColor"/></svg>"##;
+        assert_eq!(
+            colorize_svg(svg, "#FF0000"),
+            r##"<svg><path fill="#FF0000"/><path stroke="#FF0000"/><g fill="#FF0000"/></svg>"##
+        );
+
+        // Non-ASCII text whose lowercase form has a different byte length
+        let svg = r##"<svg><title>İstanbul</title><path fill="#00FF00"/></svg>"##;
+        assert_eq!(
+            colorize_svg(svg, "#FF0000"),
+            r##"<svg><title>İstanbul</title><path fill="#FF0000"/></svg>"##
+        );
+    }
+
+    #[test]
+    fn test_svg_data_url() {
+        assert_eq!(svg_data_url("<svg/>"), "data:image/svg+xml;base64,PHN2Zy8+");
+    }
 }
@@ -0,0 +1,149 @@
+//! Errors returned to the frontend
+//!
+//! Commands fail with an [`AppError`] so the frontend can switch on a stable
+//! error code instead of matching on message text. Internal helpers still
+//! return `String` errors, which convert into [`AppError::StateError`].
+
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+/// A categorized command error
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppError {
+    /// The app state could not be read or updated
+    StateError(String),
+    /// A window operation failed
+    WindowError(String),
+    /// Reading or writing a file or socket failed
+    IoError(String),
+    /// A parameter was out of range or malformed
+    ValidationError { field: String, message: String },
+    /// The requested window, file or entry does not exist
+    NotFound(String),
+}
+
+impl AppError {
+    /// Create a validation error for a parameter
+    pub fn validation(field: &str, message: impl Into<String>) -> Self {
+        Self::ValidationError {
+            field: field.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Create a not found error
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::NotFound(message.into())
+    }
+
+    /// Stable code for the error category
+    pub fn get_error_code(&self) -> &'static str {
+        match self {
+            Self::StateError(_) => "state_error",
+            Self::WindowError(_) => "window_error",
+            Self::IoError(_) => "io_error",
+            Self::ValidationError { .. } => "validation_error",
+            Self::NotFound(_) => "not_found",
+        }
+    }
+
+    /// The human readable message
+    pub fn message(&self) -> &str {
+        match self {
+            Self::StateError(message)
+            | Self::WindowError(message)
+            | Self::IoError(message)
+            | Self::NotFound(message)
+            | Self::ValidationError { message, .. } => message,
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for AppError {}
+
+/// Serialized as `{ "code": ..., "message": ... }`, plus `field` for
+/// validation errors
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let field = match self {
+            Self::ValidationError { field, .. } => Some(field),
+            _ => None,
+        };
+
+        let mut state = serializer.serialize_struct("AppError", 2 + field.is_some() as usize)?;
+        state.serialize_field("code", self.get_error_code())?;
+        state.serialize_field("message", self.message())?;
+        if let Some(field) = field {
+            state.serialize_field("field", field)?;
+        }
+        state.end()
+    }
+}
+
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        Self::StateError(message)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        Self::StateError(message.to_string())
+    }
+}
+
+impl From<AppError> for String {
+    fn from(error: AppError) -> Self {
+        error.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_error_codes() {
+        assert_eq!(AppError::from("bad").get_error_code(), "state_error");
+        assert_eq!(
+            AppError::WindowError("bad".to_string()).get_error_code(),
+            "window_error"
+        );
+        assert_eq!(
+            AppError::IoError("bad".to_string()).get_error_code(),
+            "io_error"
+        );
+        assert_eq!(
+            AppError::validation("size", "bad").get_error_code(),
+            "validation_error"
+        );
+        assert_eq!(AppError::not_found("bad").get_error_code(), "not_found");
+    }
+
+    #[test]
+    fn test_serialize() {
+        assert_eq!(
+            serde_json::to_value(AppError::not_found("Window not found: shadow-1")).unwrap(),
+            json!({ "code": "not_found", "message": "Window not found: shadow-1" })
+        );
+        assert_eq!(
+            serde_json::to_value(AppError::validation("fps", "Too high")).unwrap(),
+            json!({ "code": "validation_error", "message": "Too high", "field": "fps" })
+        );
+    }
+
+    #[test]
+    fn test_string_conversions() {
+        let error = AppError::validation("fps", "Too high");
+        assert_eq!(error.to_string(), "Too high");
+        assert_eq!(String::from(error), "Too high");
+    }
+}
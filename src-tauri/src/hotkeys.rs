@@ -12,55 +12,41 @@
 
 #![allow(dead_code)]
 
-use crate::state::AppState;
+use crate::config::sounds;
+use crate::state::{AppState, KeybindPreferences};
 use crate::window;
 use log::{debug, error, info, warn};
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Manager};
 use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
 
+/// A keybind and whether its shortcut is currently registered
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RegisteredHotkey {
+    /// Action name, e.g. `toggle_lock`
+    pub action: String,
+
+    /// Configured shortcut string
+    pub shortcut: String,
+
+    /// Whether the shortcut is registered with the OS
+    pub active: bool,
+}
+
 /// Set up all global hotkeys for the application
-/// Note: The global-shortcut plugin must be registered in main.rs before calling this
+///
+/// Registers the keybinds from the loaded preferences, so this must run after
+/// they are loaded. The global-shortcut plugin must be registered in main.rs
+/// before calling this.
 pub fn setup_hotkeys(app: &AppHandle) -> Result<(), String> {
     info!("Setting up global hotkeys...");
 
-    // Register default shortcuts with their handlers
-    register_default_shortcuts(app)?;
+    update_shortcuts_from_preferences(app)?;
 
     info!("Global hotkeys setup complete");
     Ok(())
 }
 
-/// Register the default keyboard shortcuts
-fn register_default_shortcuts(app: &AppHandle) -> Result<(), String> {
-    let shortcuts_config = vec![
-        ("Control+Shift+Alt+X", "toggle_lock"),
-        ("Control+Shift+Alt+C", "center"),
-        ("Control+Shift+Alt+H", "hide"),
-        ("Control+Shift+Alt+R", "reset"),
-        ("Control+Shift+Alt+M", "change_display"),
-        ("Control+Shift+Alt+D", "duplicate"),
-        ("Control+Shift+Alt+Q", "quit"),
-        ("Control+Shift+Alt+Up", "move_up"),
-        ("Control+Shift+Alt+Down", "move_down"),
-        ("Control+Shift+Alt+Left", "move_left"),
-        ("Control+Shift+Alt+Right", "move_right"),
-    ];
-
-    for (shortcut_str, action) in shortcuts_config {
-        if let Err(e) = register_shortcut_with_handler(app, shortcut_str, action) {
-            warn!(
-                "Failed to register shortcut {} for {}: {}",
-                shortcut_str, action, e
-            );
-        } else {
-            debug!("Registered shortcut: {} -> {}", shortcut_str, action);
-        }
-    }
-
-    Ok(())
-}
-
 /// Register a single shortcut with its handler
 fn register_shortcut_with_handler(
     app: &AppHandle,
@@ -81,6 +67,10 @@ fn register_shortcut_with_handler(
         })
         .map_err(|e| format!("Failed to register shortcut '{}': {}", shortcut_str, e))?;
 
+    if let Some(state) = app.try_state::<Arc<AppState>>() {
+        state.set_registered_hotkey(action, shortcut_str);
+    }
+
     Ok(())
 }
 
@@ -116,16 +106,67 @@ pub fn unregister_all(app: &AppHandle) -> Result<(), String> {
         .unregister_all()
         .map_err(|e| format!("Failed to unregister all shortcuts: {}", e))?;
 
+    if let Some(state) = app.try_state::<Arc<AppState>>() {
+        state.clear_registered_hotkeys();
+    }
+
     Ok(())
 }
 
+/// Get the configured keybind for each action
+fn keybind_actions(keybinds: &KeybindPreferences) -> Vec<(&String, &'static str)> {
+    vec![
+        (&keybinds.toggle_lock, "toggle_lock"),
+        (&keybinds.center, "center"),
+        (&keybinds.hide, "hide"),
+        (&keybinds.reset, "reset"),
+        (&keybinds.change_display, "change_display"),
+        (&keybinds.duplicate, "duplicate"),
+        (&keybinds.quit, "quit"),
+        (&keybinds.move_up, "move_up"),
+        (&keybinds.move_down, "move_down"),
+        (&keybinds.move_left, "move_left"),
+        (&keybinds.move_right, "move_right"),
+        (&keybinds.rotate_cw, "rotate_cw"),
+        (&keybinds.rotate_ccw, "rotate_ccw"),
+        (&keybinds.toggle_flip_horizontal, "toggle_flip_horizontal"),
+        (&keybinds.lock_and_save, "lock_and_save"),
+        (&keybinds.undo_move, "undo_move"),
+        (&keybinds.redo_move, "redo_move"),
+        (&keybinds.mute_sounds, "mute_sounds"),
+        (&keybinds.unmute_sounds, "unmute_sounds"),
+        (&keybinds.next_favorite_crosshair, "next_favorite_crosshair"),
+        (&keybinds.move_up_fast, "move_up_fast"),
+        (&keybinds.move_down_fast, "move_down_fast"),
+        (&keybinds.move_left_fast, "move_left_fast"),
+        (&keybinds.move_right_fast, "move_right_fast"),
+    ]
+}
+
+/// List every configured keybind with its registration status
+pub fn get_registered_hotkeys(app: &AppHandle) -> Vec<RegisteredHotkey> {
+    let state = app.state::<Arc<AppState>>();
+    let prefs = state.get_preferences();
+    let registered = state.get_registered_hotkeys();
+
+    keybind_actions(&prefs.keybinds)
+        .into_iter()
+        .map(|(shortcut, action)| RegisteredHotkey {
+            action: action.to_string(),
+            shortcut: shortcut.clone(),
+            active: registered.get(action) == Some(shortcut),
+        })
+        .collect()
+}
+
 /// Handle a named action
 fn handle_action(app: &AppHandle, action: &str) {
     debug!("Action triggered: {}", action);
 
     let result = match action {
         "toggle_lock" => handle_toggle_lock(app),
-        "center" => handle_center(app),
+        "lock_and_save" => handle_lock_and_save(app),
+        "center" => handle_center(app, false),
         "hide" => handle_hide(app),
         "reset" => handle_reset(app),
         "change_display" => handle_change_display(app),
@@ -135,6 +176,18 @@ fn handle_action(app: &AppHandle, action: &str) {
         "move_down" => handle_move(app, 0, 1),
         "move_left" => handle_move(app, -1, 0),
         "move_right" => handle_move(app, 1, 0),
+        "move_up_fast" => handle_move_fast(app, 0, -1),
+        "move_down_fast" => handle_move_fast(app, 0, 1),
+        "move_left_fast" => handle_move_fast(app, -1, 0),
+        "move_right_fast" => handle_move_fast(app, 1, 0),
+        "rotate_cw" => handle_rotate(app, 90.0),
+        "rotate_ccw" => handle_rotate(app, -90.0),
+        "toggle_flip_horizontal" => handle_toggle_flip_horizontal(app),
+        "undo_move" => window::step_position_history(app, true),
+        "redo_move" => window::step_position_history(app, false),
+        "mute_sounds" => handle_set_sounds_disabled(app, true),
+        "unmute_sounds" => handle_set_sounds_disabled(app, false),
+        "next_favorite_crosshair" => handle_next_favorite(app),
         _ => {
             warn!("Unknown action: {}", action);
             Ok(())
@@ -146,10 +199,22 @@ fn handle_action(app: &AppHandle, action: &str) {
     }
 }
 
-/// Toggle the window lock state
+/// Toggle the window lock state, saving if `save_on_lock` is enabled
 fn handle_toggle_lock(app: &AppHandle) -> Result<(), String> {
     info!("Toggle lock triggered");
 
+    let save = app.state::<Arc<AppState>>().get_save_on_lock();
+    toggle_lock(app, save)
+}
+
+/// Toggle the window lock state and always save
+fn handle_lock_and_save(app: &AppHandle) -> Result<(), String> {
+    info!("Lock and save triggered");
+    toggle_lock(app, true)
+}
+
+/// Toggle the lock on all windows, optionally saving preferences afterwards
+pub(crate) fn toggle_lock(app: &AppHandle, save: bool) -> Result<(), String> {
     let state = app.state::<Arc<AppState>>();
     let locked = state.toggle_locked();
 
@@ -166,26 +231,67 @@ fn handle_toggle_lock(app: &AppHandle) -> Result<(), String> {
     }
 
     // Emit event to update UI
-    app.emit("lock-changed", locked)
-        .map_err(|e| e.to_string())?;
+    crate::tray::emit_lock_changed(app, locked)?;
 
     // Play sound feedback
-    let sound = if locked { "lock" } else { "unlock" };
-    app.emit("play-sound", sound).ok();
+    let sound = if locked { sounds::LOCK } else { sounds::UNLOCK };
+    crate::sound::play(app, sound);
+
+    if save {
+        state.save_preferences(app)?;
+    }
 
     Ok(())
 }
 
+/// Switch to the favorite crosshair after the current one
+fn handle_next_favorite(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<Arc<AppState>>();
+    let favorites = state.get_crosshair_favorites();
+
+    match next_in_cycle(&favorites, &state.get_crosshair()) {
+        Some(crosshair) => {
+            info!("Next favorite crosshair: {}", crosshair);
+            crate::commands::apply_crosshair(app, &state, crosshair.clone())
+        }
+        None => {
+            debug!("No favorite crosshairs to cycle through");
+            Ok(())
+        }
+    }
+}
+
+/// The item after `current`, wrapping around, or the first if it is not listed
+fn next_in_cycle<'a>(items: &'a [String], current: &str) -> Option<&'a String> {
+    let next = match items.iter().position(|item| item == current) {
+        Some(index) => (index + 1) % items.len(),
+        None => 0,
+    };
+    items.get(next)
+}
+
+/// Turn sound feedback off or back on
+fn handle_set_sounds_disabled(app: &AppHandle, disabled: bool) -> Result<(), String> {
+    info!("Sounds {}", if disabled { "muted" } else { "unmuted" });
+
+    app.state::<Arc<AppState>>().set_disable_sounds(disabled);
+    app.emit("sounds-disabled-changed", disabled)
+        .map_err(|e| e.to_string())
+}
+
 /// Center the crosshair on the current display
-fn handle_center(app: &AppHandle) -> Result<(), String> {
+fn handle_center(app: &AppHandle, include_shadows: bool) -> Result<(), String> {
     info!("Center triggered");
 
     if let Some(window) = app.get_webview_window("main") {
         window::center_on_current_monitor(&window)?;
     }
+    if include_shadows {
+        window::center_shadow_windows(app)?;
+    }
 
     // Play sound feedback
-    app.emit("play-sound", "center").ok();
+    crate::sound::play(app, sounds::CENTER);
 
     Ok(())
 }
@@ -204,29 +310,13 @@ fn handle_hide(app: &AppHandle) -> Result<(), String> {
 
     let visible = state.toggle_visible();
 
-    // Update main window
-    if let Some(window) = app.get_webview_window("main") {
-        if visible {
-            window.show().map_err(|e| e.to_string())?;
-        } else {
-            window.hide().map_err(|e| e.to_string())?;
-        }
-    }
-
-    // Update shadow windows
-    for label in state.get_shadow_windows() {
-        if let Some(window) = app.get_webview_window(&label) {
-            if visible {
-                window.show().map_err(|e| e.to_string())?;
-            } else {
-                window.hide().map_err(|e| e.to_string())?;
-            }
-        }
-    }
+    // Update main and shadow windows, fading if configured
+    window::apply_visibility(app, visible)?;
 
     // Emit event to update UI
     app.emit("visibility-changed", visible)
         .map_err(|e| e.to_string())?;
+    crate::tray::update_tray_menu(app)?;
 
     Ok(())
 }
@@ -235,22 +325,15 @@ fn handle_hide(app: &AppHandle) -> Result<(), String> {
 fn handle_reset(app: &AppHandle) -> Result<(), String> {
     info!("Reset triggered");
 
+    // Re-registers shortcuts if the reset changed any keybinds
     let state = app.state::<Arc<AppState>>();
-    state.reset_preferences();
-
-    // Emit events to update UI
-    let prefs = state.get_preferences();
-    app.emit("crosshair-changed", &prefs.crosshair)
-        .map_err(|e| e.to_string())?;
-    app.emit("opacity-changed", prefs.opacity)
-        .map_err(|e| e.to_string())?;
-    app.emit("size-changed", prefs.size)
-        .map_err(|e| e.to_string())?;
-    app.emit("color-changed", &prefs.color)
-        .map_err(|e| e.to_string())?;
+    crate::commands::replace_preferences(app, state.inner(), |state| {
+        state.reset_preferences(true);
+        Ok(())
+    })?;
 
     // Center the window
-    handle_center(app)?;
+    handle_center(app, false)?;
 
     Ok(())
 }
@@ -259,11 +342,7 @@ fn handle_reset(app: &AppHandle) -> Result<(), String> {
 fn handle_change_display(app: &AppHandle) -> Result<(), String> {
     info!("Change display triggered");
 
-    if let Some(window) = app.get_webview_window("main") {
-        window::move_to_next_display(&window)?;
-    }
-
-    Ok(())
+    window::move_main_to_next_display(app, true)
 }
 
 /// Create a duplicate (shadow) window
@@ -279,7 +358,7 @@ fn handle_duplicate(app: &AppHandle) -> Result<(), String> {
     }
 
     // Check limit
-    if state.shadow_window_count() >= 14 {
+    if state.shadow_window_count() >= state.get_max_shadow_windows() {
         warn!("Maximum shadow windows reached");
         return Ok(());
     }
@@ -299,28 +378,132 @@ fn handle_quit(app: &AppHandle) -> Result<(), String> {
     if let Err(e) = state.save_preferences(app) {
         error!("Failed to save preferences on quit: {}", e);
     }
+    if let Err(e) = state.mark_session_clean(app) {
+        error!("Failed to mark session clean: {}", e);
+    }
+    crate::blink::stop_blink(&state);
 
     app.exit(0);
     Ok(())
 }
 
-/// Move the crosshair by a pixel offset
+/// Move the crosshair one step in a direction
 fn handle_move(app: &AppHandle, dx: i32, dy: i32) -> Result<(), String> {
+    let step = app.state::<Arc<AppState>>().preferences.read().move_step_px;
+    move_by_step(app, dx, dy, step)
+}
+
+/// Move the crosshair one fast step in a direction
+fn handle_move_fast(app: &AppHandle, dx: i32, dy: i32) -> Result<(), String> {
+    let step = app
+        .state::<Arc<AppState>>()
+        .preferences
+        .read()
+        .fast_move_step_px;
+    move_by_step(app, dx, dy, step)
+}
+
+/// Move the crosshair by `step` pixels along a unit direction
+fn move_by_step(app: &AppHandle, dx: i32, dy: i32, step: u32) -> Result<(), String> {
     let state = app.state::<Arc<AppState>>();
+    let (dx, dy) = (dx * step as i32, dy * step as i32);
 
-    // Only allow movement when unlocked
-    if state.is_locked() {
+    // Only allow movement when the position isn't locked
+    if state.is_position_locked() {
         debug!("Move ignored - window locked");
         return Ok(());
     }
 
     if let Some(window) = app.get_webview_window("main") {
-        window::move_window_by(&window, dx, dy)?;
+        window::move_window_by(&window, dx, dy, true)?;
+    }
+
+    Ok(())
+}
+
+/// Remap a single action without touching the other shortcuts
+///
+/// Fails if the shortcut cannot be parsed or is already bound to a
+/// different action. On success the new keybind is saved to disk.
+pub fn update_single_hotkey(
+    app: &AppHandle,
+    action: &str,
+    shortcut_str: &str,
+) -> Result<(), String> {
+    let shortcut: Shortcut = shortcut_str
+        .parse()
+        .map_err(|e| format!("Failed to parse shortcut '{}': {:?}", shortcut_str, e))?;
+
+    let state = app.state::<Arc<AppState>>();
+    let prefs = state.get_preferences();
+
+    let mut old_shortcut = None;
+    let mut static_action = None;
+    for (existing, name) in keybind_actions(&prefs.keybinds) {
+        if name == action {
+            old_shortcut = Some(existing.clone());
+            static_action = Some(name);
+        } else if existing.parse::<Shortcut>().ok() == Some(shortcut) {
+            return Err(format!(
+                "Shortcut '{}' is already used by {}",
+                shortcut_str, name
+            ));
+        }
+    }
+
+    let action = static_action.ok_or_else(|| format!("Unknown action: {}", action))?;
+    let old_shortcut = old_shortcut.unwrap_or_default();
+
+    if !old_shortcut.is_empty() {
+        if let Err(e) = unregister_shortcut(app, &old_shortcut) {
+            debug!("Previous shortcut for {} was not registered: {}", action, e);
+        }
+    }
+
+    if let Err(e) = register_shortcut_with_handler(app, shortcut_str, action) {
+        // Put the old shortcut back so the action keeps working
+        if !old_shortcut.is_empty() {
+            let _ = register_shortcut_with_handler(app, &old_shortcut, action);
+        }
+        return Err(e);
     }
 
+    if let Some(keybind) = state.preferences_mut().keybinds.get_mut(action) {
+        *keybind = shortcut_str.to_string();
+    }
+    state.save_preferences(app)?;
+
+    info!(
+        "Remapped {} from '{}' to '{}'",
+        action, old_shortcut, shortcut_str
+    );
+    Ok(())
+}
+
+/// Rotate the crosshair by a number of degrees
+fn handle_rotate(app: &AppHandle, delta: f64) -> Result<(), String> {
+    app.state::<Arc<AppState>>().rotate_by(delta);
     Ok(())
 }
 
+/// Toggle horizontal mirroring of the crosshair
+fn handle_toggle_flip_horizontal(app: &AppHandle) -> Result<(), String> {
+    app.state::<Arc<AppState>>().toggle_flip_horizontal();
+    Ok(())
+}
+
+/// Check that a shortcut string can be parsed before it is saved
+pub fn validate_shortcut(shortcut_str: &str) -> Result<(), String> {
+    if shortcut_str.trim().is_empty() {
+        return Err("Shortcut is empty".to_string());
+    }
+
+    shortcut_str
+        .parse::<Shortcut>()
+        .map(|_| ())
+        .map_err(|e| format!("'{}' is not a valid shortcut: {}", shortcut_str, e))
+}
+
 /// Re-register shortcuts with custom keybinds from preferences
 pub fn update_shortcuts_from_preferences(app: &AppHandle) -> Result<(), String> {
     let state = app.state::<Arc<AppState>>();
@@ -330,25 +513,22 @@ pub fn update_shortcuts_from_preferences(app: &AppHandle) -> Result<(), String>
     unregister_all(app)?;
 
     // Register shortcuts from preferences with their handlers
-    let keybinds = &prefs.keybinds;
+    register_keybinds(&prefs.keybinds, |shortcut_str, action| {
+        register_shortcut_with_handler(app, shortcut_str, action)
+    });
 
-    let shortcuts_with_actions = vec![
-        (&keybinds.toggle_lock, "toggle_lock"),
-        (&keybinds.center, "center"),
-        (&keybinds.hide, "hide"),
-        (&keybinds.reset, "reset"),
-        (&keybinds.change_display, "change_display"),
-        (&keybinds.duplicate, "duplicate"),
-        (&keybinds.quit, "quit"),
-        (&keybinds.move_up, "move_up"),
-        (&keybinds.move_down, "move_down"),
-        (&keybinds.move_left, "move_left"),
-        (&keybinds.move_right, "move_right"),
-    ];
+    info!("Shortcuts updated from preferences");
+    Ok(())
+}
 
-    for (shortcut_str, action) in shortcuts_with_actions {
+/// Register every non-empty keybind through `register`, logging failures
+fn register_keybinds(
+    keybinds: &KeybindPreferences,
+    mut register: impl FnMut(&str, &'static str) -> Result<(), String>,
+) {
+    for (shortcut_str, action) in keybind_actions(keybinds) {
         if !shortcut_str.is_empty() {
-            if let Err(e) = register_shortcut_with_handler(app, shortcut_str, action) {
+            if let Err(e) = register(shortcut_str, action) {
                 warn!(
                     "Failed to register custom shortcut '{}' for {}: {}",
                     shortcut_str, action, e
@@ -356,15 +536,21 @@ pub fn update_shortcuts_from_preferences(app: &AppHandle) -> Result<(), String>
             }
         }
     }
-
-    info!("Shortcuts updated from preferences");
-    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_next_in_cycle() {
+        let items = vec!["a.png".to_string(), "b.png".to_string()];
+        assert_eq!(next_in_cycle(&items, "a.png"), Some(&items[1]));
+        assert_eq!(next_in_cycle(&items, "b.png"), Some(&items[0]));
+        assert_eq!(next_in_cycle(&items, "other.png"), Some(&items[0]));
+        assert!(next_in_cycle(&[], "a.png").is_none());
+    }
+
     #[test]
     fn test_shortcut_parsing() {
         // Test that default shortcuts can be parsed
@@ -379,4 +565,67 @@ mod tests {
             assert!(result.is_ok(), "Failed to parse shortcut: {}", s);
         }
     }
+
+    #[test]
+    fn test_reset_restores_registered_keybinds() {
+        let state = AppState::new();
+        state.preferences_mut().keybinds.toggle_lock = "Control+Alt+L".to_string();
+        state.reset_preferences(true);
+
+        // Run the registration path behind a fake registrar
+        let mut registered = Vec::new();
+        register_keybinds(&state.get_preferences().keybinds, |shortcut, action| {
+            registered.push((shortcut.to_string(), action));
+            Ok(())
+        });
+
+        let defaults = KeybindPreferences::default();
+        let expected: Vec<_> = keybind_actions(&defaults)
+            .into_iter()
+            .filter(|(shortcut, _)| !shortcut.is_empty())
+            .map(|(shortcut, action)| (shortcut.clone(), action))
+            .collect();
+        assert_eq!(registered, expected);
+        assert!(registered.contains(&("Control+Shift+Alt+X".to_string(), "toggle_lock")));
+    }
+
+    #[test]
+    fn test_register_loaded_custom_actions() {
+        let prefs = crate::state::Preferences::from_stored(serde_json::json!({
+            "keybinds": {
+                "lock_and_save": "Control+Alt+S",
+                "mute_sounds": "Control+Alt+M",
+                "next_favorite_crosshair": "Control+Alt+N",
+                "move_up_fast": "Control+Alt+Up"
+            }
+        }))
+        .unwrap();
+
+        let mut registered = Vec::new();
+        register_keybinds(&prefs.keybinds, |shortcut, action| {
+            registered.push((shortcut.to_string(), action));
+            Ok(())
+        });
+
+        for expected in [
+            ("Control+Alt+S", "lock_and_save"),
+            ("Control+Alt+M", "mute_sounds"),
+            ("Control+Alt+N", "next_favorite_crosshair"),
+            ("Control+Alt+Up", "move_up_fast"),
+        ] {
+            assert!(registered.contains(&(expected.0.to_string(), expected.1)));
+        }
+        // Actions without a binding are skipped
+        assert!(!registered
+            .iter()
+            .any(|(_, action)| *action == "unmute_sounds"));
+    }
+
+    #[test]
+    fn test_validate_shortcut() {
+        assert!(validate_shortcut("Control+Shift+Alt+X").is_ok());
+        assert!(validate_shortcut("").is_err());
+        assert!(validate_shortcut("Control+NotAKey").is_err());
+        assert!(validate_shortcut("Control+Shift+").is_err());
+    }
 }
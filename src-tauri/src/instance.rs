@@ -0,0 +1,186 @@
+//! Single instance enforcement
+//!
+//! Only one CrossOver may run at a time, otherwise both instances fight over
+//! the global hotkeys. The first instance holds a lock and listens on a
+//! loopback port; later launches ask it to focus its window and then exit.
+
+use crate::config::INSTANCE_LOCK_FILENAME;
+use log::{info, warn};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Message sent by a second launch to the running instance
+const FOCUS_MESSAGE: &str = "focus";
+
+/// Held by the running instance for as long as it runs
+pub struct InstanceLock {
+    listener: TcpListener,
+    /// Lock file holding an exclusive advisory lock, released on exit
+    #[cfg(not(target_os = "windows"))]
+    _file: std::fs::File,
+}
+
+/// Try to become the only running instance
+///
+/// Returns `None` if another instance already holds the lock.
+pub fn acquire() -> Result<Option<InstanceLock>, String> {
+    #[cfg(target_os = "windows")]
+    if !create_instance_mutex()? {
+        return Ok(None);
+    }
+
+    let path = lock_path();
+    #[cfg(not(target_os = "windows"))]
+    let mut file = {
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(|e| format!("Failed to open instance lock {:?}: {}", path, e))?;
+        // The OS drops the lock when the process exits, so a crashed
+        // instance never leaves a stale lock behind
+        match file.try_lock() {
+            Ok(()) => file,
+            Err(std::fs::TryLockError::WouldBlock) => return Ok(None),
+            Err(std::fs::TryLockError::Error(e)) => {
+                return Err(format!("Failed to lock instance lock {:?}: {}", path, e))
+            }
+        }
+    };
+
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+        .map_err(|e| format!("Failed to bind instance listener: {}", e))?;
+    let port = listener
+        .local_addr()
+        .map_err(|e| format!("Failed to get instance listener address: {}", e))?
+        .port();
+
+    let contents = format!("{}\n{}", std::process::id(), port);
+    #[cfg(not(target_os = "windows"))]
+    file.set_len(0)
+        .and_then(|_| file.write_all(contents.as_bytes()))
+        .map_err(|e| format!("Failed to write instance lock {:?}: {}", path, e))?;
+    #[cfg(target_os = "windows")]
+    std::fs::write(&path, contents)
+        .map_err(|e| format!("Failed to write instance lock {:?}: {}", path, e))?;
+
+    Ok(Some(InstanceLock {
+        listener,
+        #[cfg(not(target_os = "windows"))]
+        _file: file,
+    }))
+}
+
+/// Ask the running instance to show and focus its main window
+pub fn notify_existing() -> Result<(), String> {
+    let path = lock_path();
+    let (_, port) = read_lock(&path).ok_or("Running instance did not publish its port")?;
+
+    let mut stream =
+        TcpStream::connect_timeout(&(Ipv4Addr::LOCALHOST, port).into(), Duration::from_secs(1))
+            .map_err(|e| format!("Failed to reach running instance: {}", e))?;
+    writeln!(stream, "{}", FOCUS_MESSAGE)
+        .map_err(|e| format!("Failed to notify running instance: {}", e))
+}
+
+/// Handle requests from later launches in the background
+pub fn listen(app: &AppHandle, lock: InstanceLock) {
+    let app = app.clone();
+
+    std::thread::spawn(move || {
+        // Move the whole lock in so the lock file stays held while listening
+        let lock = lock;
+        for stream in lock.listener.incoming() {
+            let Ok(stream) = stream else {
+                continue;
+            };
+            let _ = stream.set_read_timeout(Some(Duration::from_secs(1)));
+
+            let mut line = String::new();
+            if BufReader::new(stream).read_line(&mut line).is_ok() && line.trim() == FOCUS_MESSAGE {
+                info!("Another instance was launched, focusing this one");
+                focus_main_window(&app);
+            }
+        }
+    });
+}
+
+/// Show and focus the main window unless running headless
+fn focus_main_window(app: &AppHandle) {
+    let headless = app
+        .try_state::<std::sync::Arc<crate::state::AppState>>()
+        .is_some_and(|state| state.headless);
+
+    if let Some(window) = app.get_webview_window("main") {
+        if !headless {
+            if let Err(e) = window.show().and_then(|_| window.set_focus()) {
+                warn!("Failed to focus main window: {}", e);
+            }
+        }
+    }
+    app.emit("second-instance", ()).ok();
+}
+
+/// Path of the lock file shared by all instances of this user
+///
+/// Lives in a per-user directory so another account on the same machine
+/// can neither block nor be redirected to this user's instance.
+fn lock_path() -> PathBuf {
+    dirs::runtime_dir()
+        .or_else(dirs::cache_dir)
+        .unwrap_or_else(std::env::temp_dir)
+        .join(INSTANCE_LOCK_FILENAME)
+}
+
+/// Read the process ID and port from a lock file
+fn read_lock(path: &std::path::Path) -> Option<(u32, u16)> {
+    parse_lock(&std::fs::read_to_string(path).ok()?)
+}
+
+/// Parse lock file contents of the form `<pid>\n<port>`
+fn parse_lock(contents: &str) -> Option<(u32, u16)> {
+    let mut lines = contents.lines();
+    let pid = lines.next()?.trim().parse().ok()?;
+    let port = lines.next()?.trim().parse().ok()?;
+    Some((pid, port))
+}
+
+/// Create the named instance mutex, returning false if it already exists
+///
+/// The handle is never closed, so the mutex is held until the process exits.
+#[cfg(target_os = "windows")]
+fn create_instance_mutex() -> Result<bool, String> {
+    use windows::core::w;
+    use windows::Win32::Foundation::{CloseHandle, GetLastError, ERROR_ALREADY_EXISTS};
+    use windows::Win32::System::Threading::CreateMutexW;
+
+    unsafe {
+        let handle = CreateMutexW(None, true, w!("Local\\CrossOverSingleInstance"))
+            .map_err(|e| format!("Failed to create instance mutex: {}", e))?;
+        if GetLastError() == ERROR_ALREADY_EXISTS {
+            let _ = CloseHandle(handle);
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lock() {
+        assert_eq!(parse_lock("1234\n5678"), Some((1234, 5678)));
+        assert_eq!(parse_lock("1234\n5678\n"), Some((1234, 5678)));
+        assert_eq!(parse_lock("1234"), None);
+        assert_eq!(parse_lock("abc\n5678"), None);
+        assert_eq!(parse_lock("1234\n70000"), None);
+    }
+}
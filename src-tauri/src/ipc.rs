@@ -0,0 +1,376 @@
+//! External control server
+//!
+//! Macros and game engines can drive CrossOver over a loopback TCP socket.
+//! Each line received is a JSON request such as
+//! `{ "id": 1, "method": "set_crosshair", "params": { "crosshair": "dot.png" } }`
+//! and is answered with a single line holding either `result` or `error`.
+//!
+//! Every request must carry the session token, which is regenerated on each
+//! start and written to `ipc-token` in the app data directory, readable only
+//! by the current user. A line that is not a valid request closes the
+//! connection, so other protocols (such as a browser POST) cannot drive it.
+
+use crate::commands;
+use crate::state::{AppState, PatchPreferences};
+use log::{debug, info, warn};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::io::Write;
+use std::net::Ipv4Addr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_util::sync::CancellationToken;
+
+/// Longest request line accepted, in bytes
+const MAX_LINE_LENGTH: u64 = 64 * 1024;
+
+/// Name of the file holding the session token
+const TOKEN_FILENAME: &str = "ipc-token";
+
+/// A running control server
+pub struct IpcServer {
+    pub port: u16,
+    pub token: CancellationToken,
+    pub token_path: PathBuf,
+}
+
+/// Status reported by `get_ipc_server_status`
+#[derive(Debug, Clone, Serialize)]
+pub struct IpcServerStatus {
+    pub running: bool,
+    pub port: u16,
+    pub token_path: Option<String>,
+}
+
+/// A request read from a client
+#[derive(Debug, Deserialize)]
+pub struct IpcRequest {
+    #[serde(default)]
+    pub id: Value,
+    #[serde(default)]
+    pub token: String,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+/// The reply to a request
+#[derive(Debug, Serialize)]
+pub struct IpcResponse {
+    pub id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl IpcResponse {
+    fn new(id: Value, result: Result<Value, String>) -> Self {
+        match result {
+            Ok(value) => Self {
+                id,
+                result: Some(value),
+                error: None,
+            },
+            Err(error) => Self {
+                id,
+                result: None,
+                error: Some(error),
+            },
+        }
+    }
+}
+
+/// Start listening on the given loopback port
+pub fn start(app: &AppHandle, port: u16) -> Result<(), String> {
+    let state = app.state::<Arc<AppState>>();
+    // Hold the slot for the whole start so two callers cannot both bind
+    let mut slot = state.ipc_server_slot();
+    if let Some(server) = slot.as_ref() {
+        return Err(format!(
+            "IPC server is already running on port {}",
+            server.port
+        ));
+    }
+
+    let listener = std::net::TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+        .and_then(|listener| listener.set_nonblocking(true).map(|_| listener))
+        .map_err(|e| format!("Failed to bind port {}: {}", port, e))?;
+    let listener = TcpListener::from_std(listener).map_err(|e| e.to_string())?;
+    let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+
+    let secret = generate_secret();
+    let token_path = write_token_file(app, &secret)?;
+
+    let token = CancellationToken::new();
+    *slot = Some(IpcServer {
+        port,
+        token: token.clone(),
+        token_path,
+    });
+    drop(slot);
+
+    let app = app.clone();
+    let secret: Arc<str> = secret.into();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = token.cancelled() => break,
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, addr)) => {
+                        debug!("IPC client connected from {}", addr);
+                        let client =
+                            serve_client(app.clone(), stream, token.clone(), secret.clone());
+                        tauri::async_runtime::spawn(client);
+                    }
+                    Err(e) => warn!("Failed to accept IPC client: {}", e),
+                },
+            }
+        }
+        info!("IPC server on port {} stopped", port);
+    });
+
+    info!("IPC server listening on port {}", port);
+    Ok(())
+}
+
+/// Stop the server and disconnect its clients
+pub fn stop(state: &AppState) -> Result<(), String> {
+    match state.take_ipc_server() {
+        Some(server) => {
+            server.token.cancel();
+            if let Err(e) = std::fs::remove_file(&server.token_path) {
+                debug!("Failed to remove IPC token file: {}", e);
+            }
+            Ok(())
+        }
+        None => Err("IPC server is not running".to_string()),
+    }
+}
+
+/// Generate a random session token as 32 hex digits
+fn generate_secret() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    (0..2)
+        .map(|i| {
+            // Each RandomState is keyed from the OS random source
+            let mut hasher = RandomState::new().build_hasher();
+            hasher.write_u128(nanos);
+            hasher.write_u32(std::process::id());
+            hasher.write_u8(i);
+            format!("{:016x}", hasher.finish())
+        })
+        .collect()
+}
+
+/// Write the session token to a file only the current user can read
+fn write_token_file(app: &AppHandle, secret: &str) -> Result<PathBuf, String> {
+    let state = app.state::<Arc<AppState>>();
+    let dir = if state.portable_mode {
+        crate::state::portable_dir()?
+    } else {
+        app.path()
+            .app_data_dir()
+            .map_err(|e| format!("Failed to get app data directory: {}", e))?
+    };
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let path = dir.join(TOKEN_FILENAME);
+    // Remove any old file so the permissions below apply to a fresh one
+    let _ = std::fs::remove_file(&path);
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(&path)
+        .and_then(|mut file| file.write_all(secret.as_bytes()))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+/// Compare tokens without stopping at the first differing byte
+fn token_matches(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+/// Answer requests from one client until it disconnects
+///
+/// The connection is closed on an over-long line, a line that is not a
+/// request, or a request without the session token.
+async fn serve_client(
+    app: AppHandle,
+    stream: TcpStream,
+    token: CancellationToken,
+    secret: Arc<str>,
+) {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+    let mut buf = Vec::new();
+
+    loop {
+        buf.clear();
+        let mut limited = (&mut reader).take(MAX_LINE_LENGTH + 1);
+        let read = tokio::select! {
+            _ = token.cancelled() => break,
+            read = limited.read_until(b'\n', &mut buf) => read,
+        };
+        match read {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(e) => {
+                debug!("IPC client read failed: {}", e);
+                break;
+            }
+        }
+        if buf.last() != Some(&b'\n') && buf.len() as u64 > MAX_LINE_LENGTH {
+            debug!("IPC client sent a line over {} bytes", MAX_LINE_LENGTH);
+            break;
+        }
+        let line = String::from_utf8_lossy(&buf);
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (response, close) = match serde_json::from_str::<IpcRequest>(&line) {
+            Ok(request) if token_matches(&request.token, &secret) => {
+                let result = dispatch(&app, &request.method, request.params).await;
+                (IpcResponse::new(request.id, result), false)
+            }
+            Ok(request) => (
+                IpcResponse::new(request.id, Err("Invalid token".to_string())),
+                true,
+            ),
+            Err(e) => (
+                IpcResponse::new(Value::Null, Err(format!("Invalid request: {}", e))),
+                true,
+            ),
+        };
+
+        let mut reply = match serde_json::to_string(&response) {
+            Ok(reply) => reply,
+            Err(e) => {
+                warn!("Failed to serialize IPC response: {}", e);
+                continue;
+            }
+        };
+        reply.push('\n');
+        if writer.write_all(reply.as_bytes()).await.is_err() || close {
+            break;
+        }
+    }
+}
+
+/// Run a method through the same code as the matching Tauri command
+async fn dispatch(app: &AppHandle, method: &str, params: Value) -> Result<Value, String> {
+    let state = app.state::<Arc<AppState>>();
+
+    match method {
+        "set_crosshair" => {
+            commands::set_crosshair(app.clone(), state, param(&params, "crosshair")?).await?
+        }
+        "set_opacity" => commands::set_opacity(state, param(&params, "opacity")?).await?,
+        "set_size" => commands::set_size(app.clone(), state, param(&params, "size")?).await?,
+        "set_color" => commands::set_color(state, param(&params, "color")?).await?,
+        "set_reticle" => commands::set_reticle(state, param(&params, "reticle")?).await?,
+        "set_lock_mode" => {
+            commands::set_lock_mode(app.clone(), state, param(&params, "mode")?).await?
+        }
+        "load_profile" => commands::load_profile(state, param(&params, "name")?).await?,
+        "patch_preferences" => {
+            let patch: PatchPreferences =
+                serde_json::from_value(params).map_err(|e| format!("Invalid params: {}", e))?;
+            commands::patch_preferences(app.clone(), state, patch).await?
+        }
+        "toggle_lock" => {
+            return Ok(Value::from(
+                commands::toggle_lock(app.clone(), state).await?,
+            ))
+        }
+        "toggle_visibility" => {
+            return Ok(Value::from(
+                commands::toggle_visibility(app.clone(), state).await?,
+            ))
+        }
+        "center_window" => {
+            let include_shadows = param(&params, "include_shadows").unwrap_or(false);
+            commands::center_window(app.clone(), include_shadows).await?
+        }
+        "get_preferences" => {
+            return serde_json::to_value(state.get_preferences()).map_err(|e| e.to_string())
+        }
+        _ => return Err(format!("Unknown method: {}", method)),
+    }
+
+    Ok(Value::Null)
+}
+
+/// Read a named parameter
+fn param<T: DeserializeOwned>(params: &Value, key: &str) -> Result<T, String> {
+    let value = params
+        .get(key)
+        .ok_or_else(|| format!("Missing parameter: {}", key))?;
+    serde_json::from_value(value.clone()).map_err(|e| format!("Invalid parameter {}: {}", key, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_request() {
+        let request: IpcRequest = serde_json::from_str(
+            r#"{ "method": "set_crosshair", "params": { "crosshair": "dot.png" } }"#,
+        )
+        .unwrap();
+        assert_eq!(request.method, "set_crosshair");
+        assert_eq!(request.id, Value::Null);
+        assert_eq!(request.token, "");
+        assert_eq!(
+            param::<String>(&request.params, "crosshair").unwrap(),
+            "dot.png"
+        );
+        assert!(param::<u32>(&request.params, "size").is_err());
+        assert!(param::<u32>(&request.params, "crosshair").is_err());
+    }
+
+    #[test]
+    fn test_token_matches() {
+        let secret = generate_secret();
+        assert_eq!(secret.len(), 32);
+        assert_ne!(secret, generate_secret());
+        assert!(token_matches(&secret, &secret));
+        assert!(!token_matches("", &secret));
+        assert!(!token_matches(&secret[1..], &secret));
+        assert!(!token_matches(&"0".repeat(32), &secret));
+    }
+
+    #[test]
+    fn test_response_shape() {
+        let ok = serde_json::to_value(IpcResponse::new(json!(1), Ok(json!(true)))).unwrap();
+        assert_eq!(ok, json!({ "id": 1, "result": true }));
+
+        let err = serde_json::to_value(IpcResponse::new(json!(2), Err("bad".to_string()))).unwrap();
+        assert_eq!(err, json!({ "id": 2, "error": "bad" }));
+    }
+}
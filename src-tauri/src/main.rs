@@ -1,19 +1,31 @@
 // Prevents additional console window on Windows in release
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod autostart;
+mod blink;
+mod cli;
+mod color;
 mod commands;
 mod config;
 mod crosshair;
+mod error;
 mod hotkeys;
+mod instance;
+mod ipc;
 mod mouse;
+mod process;
+mod sound;
 mod state;
 mod tray;
 mod window;
+#[cfg(target_os = "linux")]
+mod x11_errors;
 
 use log::info;
 use state::AppState;
 use std::sync::Arc;
-use tauri::Manager;
+use tauri::webview::PageLoadEvent;
+use tauri::{Emitter, Manager};
 
 fn main() {
     // Initialize logger
@@ -21,6 +33,47 @@ fn main() {
 
     info!("Starting CrossOver v{}", env!("CARGO_PKG_VERSION"));
 
+    // Portable mode keeps settings and custom crosshairs next to the executable
+    let portable_mode = std::env::args().any(|arg| arg == "--portable");
+    if portable_mode {
+        info!("Running in portable mode");
+    }
+
+    // Safe mode starts with defaults and leaves saved preferences untouched
+    let safe_mode = std::env::args().any(|arg| arg == "--safe-mode");
+    if safe_mode {
+        info!("Running in safe mode, saved preferences will be ignored");
+    }
+
+    // Headless mode runs only the tray and hotkeys, without an overlay
+    let headless = std::env::args().any(|arg| arg == "--headless");
+    if headless {
+        info!("Running in headless mode");
+    }
+
+    // Appearance overrides such as --crosshair and --opacity
+    let startup_args = cli::StartupArgs::from_env();
+    if !startup_args.is_empty() {
+        info!("Command line overrides: {:?}", startup_args);
+    }
+
+    // Only one instance may own the global hotkeys
+    let instance_lock = match instance::acquire() {
+        Ok(Some(lock)) => Some(lock),
+        Ok(None) => {
+            info!("CrossOver is already running, focusing the existing instance");
+            if let Err(e) = instance::notify_existing() {
+                log::warn!("Failed to notify the running instance: {}", e);
+            }
+            std::process::exit(0);
+        }
+        Err(e) => {
+            log::warn!("Failed to check for a running instance: {}", e);
+            None
+        }
+    };
+    let single_instance = instance_lock.is_some();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_store::Builder::default().build())
@@ -34,7 +87,13 @@ fn main() {
             tauri_plugin_autostart::MacosLauncher::LaunchAgent,
             Some(vec!["--minimized"]),
         ))
-        .manage(Arc::new(AppState::default()))
+        .manage(Arc::new(
+            AppState::with_portable_mode(portable_mode)
+                .with_safe_mode(safe_mode)
+                .with_headless(headless)
+                .with_single_instance(single_instance)
+                .with_startup_args(startup_args),
+        ))
         .setup(|app| {
             info!("Setting up application...");
 
@@ -43,30 +102,130 @@ fn main() {
                 .get_webview_window("main")
                 .expect("main window not found");
 
-            // Apply platform-specific window settings
-            window::setup_overlay_window(&main_window)?;
-
-            // Ensure window starts unlocked (not click-through)
-            // This is critical for dragging and interacting
-            if let Err(e) = main_window.set_ignore_cursor_events(false) {
-                log::warn!("Failed to set ignore cursor events to false: {}", e);
+            let state = app.state::<Arc<AppState>>();
+            if state.headless {
+                // Keep the window out of the way; it is never shown
+                main_window.hide().unwrap_or_default();
+                if let Err(e) = main_window.set_ignore_cursor_events(true) {
+                    log::warn!("Failed to set ignore cursor events to true: {}", e);
+                }
             } else {
-                info!("Window starts unlocked and draggable");
+                // Apply platform-specific window settings
+                window::setup_overlay_window(&main_window)?;
+
+                // Ensure window starts unlocked (not click-through)
+                // This is critical for dragging and interacting
+                if let Err(e) = main_window.set_ignore_cursor_events(false) {
+                    log::warn!("Failed to set ignore cursor events to false: {}", e);
+                } else {
+                    info!("Window starts unlocked and draggable");
+                }
             }
 
             // Setup system tray using app handle
             let app_handle = app.handle().clone();
-            tray::setup_tray(&app_handle)?;
 
-            // Setup global hotkeys using app handle
-            hotkeys::setup_hotkeys(&app_handle)?;
+            // Focus this instance when the app is launched again
+            if let Some(lock) = instance_lock {
+                instance::listen(&app_handle, lock);
+            }
+            tray::setup_tray(&app_handle)?;
 
             // Load saved preferences
-            let state = app.state::<Arc<AppState>>();
-            if let Err(e) = state.load_preferences(&app_handle) {
+            if state.safe_mode {
+                info!("Safe mode: skipping saved preferences");
+            } else if let Err(e) = state.load_preferences(&app_handle) {
                 log::warn!("Failed to load preferences: {}", e);
             }
 
+            // Command line flags take precedence over saved preferences
+            state.startup_args.apply(&app_handle, &state);
+
+            // Register the saved keybinds, including ones remapped at runtime
+            hotkeys::setup_hotkeys(&app_handle)?;
+
+            // Let the webview load the saved custom sounds
+            if let Err(e) =
+                sound::allow_custom_sounds(&app_handle, state.get_custom_sounds().values())
+            {
+                log::warn!("Failed to allow custom sounds: {}", e);
+            }
+
+            // Broadcast preference changes from here on
+            commands::register_preference_events(&app_handle, &state);
+
+            // Detect whether the previous run crashed
+            if let Err(e) = state.begin_session(&app_handle) {
+                log::warn!("Failed to check for an unclean shutdown: {}", e);
+            }
+
+            // Match the launch-at-login registration to the preference, or
+            // adopt the registration if the preference was never stored
+            if !state.safe_mode {
+                let result = if state.take_start_on_boot_unset() {
+                    autostart::is_enabled(&app_handle)
+                        .map(|enabled| state.set_start_on_boot(enabled))
+                } else {
+                    autostart::sync(&app_handle, state.get_start_on_boot())
+                };
+                if let Err(e) = result {
+                    log::warn!("Failed to sync start on boot: {}", e);
+                }
+            }
+
+            if !state.headless {
+                // Restore the saved window position
+                if let (Some(x), Some(y)) = state.get_position() {
+                    if let Err(e) = window::restore_position(&main_window, x, y) {
+                        log::warn!("Failed to restore window position: {}", e);
+                    }
+                }
+
+                // Apply the saved window level now that preferences are loaded
+                if let Err(e) = window::apply_window_level(&main_window, state.get_window_level()) {
+                    log::warn!("Failed to apply window level: {}", e);
+                }
+
+                // Re-apply the DirectX fullscreen workarounds if enabled
+                if state.get_use_dx_overlay() {
+                    if let Err(e) = window::apply_dx_overlay(&main_window, true) {
+                        log::warn!("Failed to enable DirectX overlay mode: {}", e);
+                    }
+                }
+
+                // Resume blink mode if it was enabled
+                blink::update_blink_state(&app_handle, state.inner().clone());
+
+                // Watch for monitors being connected or disconnected
+                window::start_display_watcher(&app_handle);
+
+                // Keep the overlay visible when switching macOS spaces
+                #[cfg(target_os = "macos")]
+                window::start_space_observer(&app_handle);
+            }
+
+            // Show the loaded crosshair and lock state in the tray
+            if let Err(e) = tray::update_tray_icon(&app_handle, state.is_locked()) {
+                log::warn!("Failed to update tray icon: {}", e);
+            }
+            if let Err(e) = tray::update_tray_tooltip(&app_handle) {
+                log::warn!("Failed to update tray tooltip: {}", e);
+            }
+            if let Err(e) = tray::update_tray_menu(&app_handle) {
+                log::warn!("Failed to update tray menu: {}", e);
+            }
+
+            // Persist preference changes in the background
+            state::start_auto_save(&app_handle);
+
+            // Switch profiles when a configured game is running
+            process::start_process_watcher(&app_handle);
+
+            // Hide the crosshair while a listed application is focused
+            if !state.headless {
+                process::start_focus_watcher(&app_handle);
+            }
+
             // Log initial state
             info!(
                 "Initial state - Locked: {}, Visible: {}",
@@ -90,31 +249,212 @@ fn main() {
             commands::is_locked,
             commands::center_window,
             commands::move_to_next_display,
+            commands::get_display_info,
+            commands::move_to_display,
+            commands::set_auto_center_on_display_change,
+            commands::get_auto_center_on_display_change,
+            commands::get_window_bounds,
+            commands::set_window_bounds,
             commands::toggle_visibility,
             commands::is_visible,
+            commands::set_fade_duration,
+            commands::get_fade_duration,
+            commands::set_blink_enabled,
+            commands::get_blink_enabled,
+            commands::set_blink_interval,
             commands::get_crosshair_list,
             commands::save_preferences,
             commands::load_preferences,
             commands::reset_preferences,
+            commands::restore_preferences_backup,
+            commands::set_preferences,
+            commands::patch_preferences,
+            commands::set_auto_save,
+            commands::get_all_preferences,
             commands::set_follow_mouse,
             commands::get_follow_mouse,
+            commands::set_follow_mouse_config,
+            commands::get_follow_mouse_config,
+            commands::set_scroll_wheel_opacity,
+            commands::get_scroll_wheel_opacity,
             commands::create_shadow_window,
+            commands::get_shadow_settings,
             commands::close_shadow_window,
             commands::close_all_shadow_windows,
             commands::set_hide_on_ads,
             commands::get_hide_on_ads,
             commands::set_reticle,
             commands::get_reticle,
+            commands::set_reticle_size,
+            commands::get_reticle_size,
+            commands::set_reticle_thickness,
+            commands::get_reticle_thickness,
+            commands::set_reticle_gap,
+            commands::get_reticle_gap,
+            commands::set_theme,
+            commands::get_theme,
+            commands::get_theme_list,
+            commands::set_rotation,
+            commands::get_rotation,
+            commands::rotate_by,
+            commands::set_flip_horizontal,
+            commands::get_flip_horizontal,
+            commands::set_flip_vertical,
+            commands::get_flip_vertical,
             commands::import_crosshair,
+            commands::get_crosshair_data,
+            commands::get_crosshair_svg_colored,
+            commands::is_portable_mode,
+            commands::get_registered_hotkeys,
+            commands::update_single_hotkey,
+            commands::save_profile,
+            commands::load_profile,
+            commands::list_profiles,
+            commands::add_process_rule,
+            commands::remove_process_rule,
+            commands::list_process_rules,
+            commands::is_safe_mode,
+            commands::get_platform_info,
+            commands::get_platform_capabilities,
+            commands::set_use_dx_overlay,
+            commands::get_use_dx_overlay,
+            commands::set_window_level,
+            commands::get_window_level,
+            commands::is_headless,
+            commands::get_rate_limit_stats,
+            commands::get_app_version,
+            commands::get_build_info,
+            commands::export_preferences,
+            commands::import_preferences,
+            commands::get_recent_crosshairs,
+            commands::get_shadow_window_list,
+            commands::set_max_shadow_windows,
+            commands::get_max_shadow_windows,
+            commands::focus_shadow_window,
+            commands::set_shadow_window_z_order,
+            commands::arrange_shadow_windows,
+            commands::preview_crosshair,
+            commands::sample_screen_color,
+            commands::reset_preference,
+            commands::list_crosshair_pack_contents,
+            commands::validate_hotkey_string,
+            commands::get_cursor_position,
+            commands::set_crosshair_offset,
+            commands::get_crosshair_offset,
+            commands::set_shadow_enabled,
+            commands::set_shadow_blur,
+            commands::set_shadow_color,
+            commands::set_outline_enabled,
+            commands::set_outline_width,
+            commands::set_outline_color,
+            commands::get_crosshair_effect,
+            commands::copy_settings_to_shadow_window,
+            commands::copy_settings_from_shadow_window,
+            commands::set_lock_mode,
+            commands::get_lock_mode,
+            commands::get_crosshair_metadata,
+            commands::set_monitor_crosshair,
+            commands::get_monitor_crosshair,
+            commands::clear_monitor_crosshair,
+            commands::set_start_on_boot,
+            commands::get_start_on_boot,
+            commands::is_single_instance,
+            commands::undo_position,
+            commands::redo_position,
+            commands::get_crosshair_list_paged,
+            commands::search_crosshairs,
+            commands::get_mouse_follow_error,
+            commands::set_mouse_follow_fps,
+            commands::get_mouse_follow_fps,
+            commands::start_ipc_server,
+            commands::stop_ipc_server,
+            commands::get_ipc_server_status,
+            commands::get_startup_args,
+            commands::add_hide_process,
+            commands::remove_hide_process,
+            commands::get_hide_processes,
+            commands::apply_color_blindness_preset,
+            commands::list_color_blindness_presets,
+            commands::set_window_size,
+            commands::get_window_size,
+            commands::set_sound_volume,
+            commands::get_sound_volume,
+            commands::set_custom_sound,
+            commands::remove_custom_sound,
+            commands::get_custom_sounds,
+            commands::set_disable_sounds,
+            commands::get_disable_sounds,
+            commands::get_crosshair_history,
+            commands::prev_crosshair_from_history,
+            commands::next_crosshair_from_history,
+            commands::add_crosshair_favorite,
+            commands::remove_crosshair_favorite,
+            commands::get_crosshair_favorites,
+            commands::is_crosshair_favorite,
+            commands::toggle_crosshair_favorite,
+            commands::set_hide_on_ads_delay,
+            commands::get_hide_on_ads_delay,
+            commands::set_clamp_to_monitor,
+            commands::get_clamp_to_monitor,
+            commands::reset_preferences_except_keybinds,
+            commands::set_move_step_px,
+            commands::get_move_step_px,
+            commands::set_fast_move_step_px,
+            commands::get_fast_move_step_px,
+            commands::get_follow_mouse_status,
+            commands::set_sync_shadows_to_main_monitor,
+            commands::get_sync_shadows_to_main_monitor,
+            commands::set_color_format,
+            commands::get_color_format,
+            commands::convert_color,
+            commands::get_crosshair_url,
+            commands::revoke_crosshair_url,
+            commands::get_crosshair_list_with_thumbnails,
         ])
-        .on_window_event(|window, event| {
-            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+        .on_page_load(|webview, payload| {
+            // Report startup problems once the frontend can listen for them
+            let finished = matches!(payload.event(), PageLoadEvent::Finished);
+            if webview.label() != "main" || !finished {
+                return;
+            }
+            let state = webview.state::<Arc<AppState>>();
+            if let Some(corrupt) = state.take_preferences_corrupt() {
+                if let Err(e) = webview.emit("preferences-corrupt", corrupt) {
+                    log::warn!("Failed to emit preferences-corrupt: {}", e);
+                }
+            }
+            if let Some(recovery) = state.take_crash_recovery() {
+                if let Err(e) = webview.emit("crash-recovery", recovery) {
+                    log::warn!("Failed to emit crash-recovery: {}", e);
+                }
+            }
+        })
+        .on_window_event(|window, event| match event {
+            tauri::WindowEvent::CloseRequested { api, .. } => {
                 // Hide window instead of closing when it's the main window
+                let state = window.state::<Arc<AppState>>();
+                if window.label() == "main" {
+                    if state.headless {
+                        // The main window is really closing, so this is a clean exit
+                        if let Err(e) = state.mark_session_clean(window.app_handle()) {
+                            log::warn!("Failed to mark session clean: {}", e);
+                        }
+                    } else {
+                        window.hide().unwrap_or_default();
+                        api.prevent_close();
+                    }
+                }
+            }
+            tauri::WindowEvent::Moved(position) => {
+                // Remember the position; it is saved once dragging stops
                 if window.label() == "main" {
-                    window.hide().unwrap_or_default();
-                    api.prevent_close();
+                    window
+                        .state::<Arc<AppState>>()
+                        .record_move(position.x, position.y);
+                    window.emit("window-moved", position).unwrap_or_default();
                 }
             }
+            _ => {}
         })
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
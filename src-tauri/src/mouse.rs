@@ -8,28 +8,138 @@
 
 #![allow(dead_code)]
 
-use crate::state::AppState;
+use crate::config::{
+    FOLLOW_FPS_SAMPLE_COUNT, FOLLOW_FPS_STALE_MS, MOUSE_FOLLOW_DEBOUNCE_MS,
+    MOUSE_LISTEN_MAX_ATTEMPTS, MOUSE_LISTEN_RETRY_MS, SCROLL_OPACITY_STEP,
+};
+use crate::state::{AppState, FollowMouseConfig};
+use crate::window;
 use log::{debug, error, info, warn};
 use parking_lot::Mutex;
 use rdev::{listen, Button, Event, EventType};
+use std::collections::VecDeque;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread::{self, JoinHandle};
-use tauri::{AppHandle, Manager};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
 
 /// Global flag to control the mouse listener thread
 static MOUSE_LISTENER_RUNNING: AtomicBool = AtomicBool::new(false);
 
+/// Whether the ADS button is currently held down
+static ADS_PRESSED: AtomicBool = AtomicBool::new(false);
+
+/// Last known cursor position
+static CURSOR_POSITION: Mutex<(f64, f64)> = Mutex::new((0.0, 0.0));
+
+/// When the window last moved to follow the cursor
+static LAST_FOLLOW_MOVE: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Latest cursor position throttled within the current follow interval
+///
+/// While set, a trailing flush is scheduled to move the window there.
+static PENDING_FOLLOW: Mutex<Option<(f64, f64)>> = Mutex::new(None);
+
+/// Whether a timer is stepping the window toward the cursor
+///
+/// While set, mouse events only record the cursor position and the timer
+/// moves the window, so smoothing carries on after the cursor stops.
+static FOLLOW_SMOOTHING: AtomicBool = AtomicBool::new(false);
+
+/// When the most recent follow moves happened, used to measure the frame rate
+static FOLLOW_MOVE_TIMES: Mutex<VecDeque<Instant>> = Mutex::new(VecDeque::new());
+
 /// Handle to the mouse listener thread
 static MOUSE_THREAD_HANDLE: Mutex<Option<JoinHandle<()>>> = Mutex::new(None);
 
+/// A global cursor position in physical pixels
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MousePosition {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// Get the current global cursor position
+///
+/// Queried from the OS so it works even while the mouse listener is stopped.
+pub fn cursor_position(app: &AppHandle) -> Result<MousePosition, String> {
+    let position = app
+        .cursor_position()
+        .map_err(|e| format!("Failed to get cursor position: {}", e))?;
+
+    Ok(MousePosition {
+        x: position.x,
+        y: position.y,
+    })
+}
+
+/// Detailed mouse following status for the settings window
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct FollowMouseStatus {
+    /// The preference is on
+    pub enabled: bool,
+    /// The window is currently following the cursor
+    pub active: bool,
+    /// The global mouse listener thread is running
+    pub thread_running: bool,
+    /// Why the listener stopped, if it failed
+    pub last_error: Option<String>,
+    /// Measured follow moves per second over recent moves
+    pub fps_actual: f32,
+}
+
+/// Collect the mouse following status
+pub fn follow_mouse_status(state: &AppState) -> FollowMouseStatus {
+    FollowMouseStatus {
+        enabled: state.get_follow_mouse(),
+        active: *state.mouse_following_active.read(),
+        thread_running: MOUSE_LISTENER_RUNNING.load(Ordering::SeqCst),
+        last_error: state.get_mouse_follow_error(),
+        fps_actual: follow_fps(&FOLLOW_MOVE_TIMES.lock(), Instant::now()),
+    }
+}
+
+/// Average moves per second over the samples, or 0 if following has stalled
+pub fn follow_fps(samples: &VecDeque<Instant>, now: Instant) -> f32 {
+    let (Some(first), Some(last)) = (samples.front(), samples.back()) else {
+        return 0.0;
+    };
+    if now.duration_since(*last) > Duration::from_millis(FOLLOW_FPS_STALE_MS) {
+        return 0.0;
+    }
+
+    let elapsed = last.duration_since(*first).as_secs_f32();
+    if elapsed <= 0.0 {
+        return 0.0;
+    }
+    (samples.len() - 1) as f32 / elapsed
+}
+
+/// Remember when a follow move happened, keeping the most recent samples
+fn record_follow_move(samples: &mut VecDeque<Instant>, at: Instant) {
+    if samples.len() == FOLLOW_FPS_SAMPLE_COUNT {
+        samples.pop_front();
+    }
+    samples.push_back(at);
+}
+
+/// Payload for the `mouse-follow-error` event
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct MouseFollowError {
+    pub reason: String,
+}
+
 /// Update mouse listener state based on preferences
 pub fn update_mouse_listener_state(app: &AppHandle, state: Arc<AppState>) -> Result<(), String> {
     let follow_mouse = state.get_follow_mouse();
     let hide_on_ads = state.get_hide_on_ads();
 
-    let should_run = follow_mouse || hide_on_ads;
+    let scroll_wheel_opacity = state.get_scroll_wheel_opacity();
+
+    let should_run = follow_mouse || hide_on_ads || scroll_wheel_opacity;
     let is_running = MOUSE_LISTENER_RUNNING.load(Ordering::SeqCst);
+    *state.mouse_following_active.write() = follow_mouse && is_running;
 
     if should_run && !is_running {
         start_listener(app, state)?;
@@ -50,6 +160,8 @@ fn start_listener(app: &AppHandle, state: Arc<AppState>) -> Result<(), String> {
 
     // Set the running flag
     MOUSE_LISTENER_RUNNING.store(true, Ordering::SeqCst);
+    state.set_mouse_follow_error(None);
+    *state.mouse_following_active.write() = state.get_follow_mouse();
 
     // Clone what we need for the thread
     let app_handle = app.clone();
@@ -77,6 +189,8 @@ fn stop_listener() -> Result<(), String> {
 
     // Clear the running flag - this will cause the thread to exit
     MOUSE_LISTENER_RUNNING.store(false, Ordering::SeqCst);
+    FOLLOW_MOVE_TIMES.lock().clear();
+    PENDING_FOLLOW.lock().take();
 
     // Note: We don't join the thread here because rdev::listen is blocking
     // The thread will exit on its own when it detects the flag is false
@@ -87,47 +201,265 @@ fn stop_listener() -> Result<(), String> {
 }
 
 /// The mouse listener thread function
+///
+/// `rdev::listen` can fail, e.g. without input permissions on Linux, so it is
+/// retried with exponential backoff before giving up and reporting an error.
 fn mouse_listener_thread(app: AppHandle, state: Arc<AppState>) {
     debug!("Mouse listener thread started");
 
-    // Set up the callback for mouse events
-    let callback = move |event: Event| {
-        // Check if we should stop
+    for attempt in 1..=MOUSE_LISTEN_MAX_ATTEMPTS {
+        let (app_handle, state_handle) = (app.clone(), state.clone());
+
+        // Start listening - this blocks until an error occurs
+        let error = match listen(move |event| handle_event(&app_handle, &state_handle, event)) {
+            Ok(()) => break,
+            Err(error) => format!("{:?}", error),
+        };
+        warn!(
+            "Mouse listener failed (attempt {}/{}): {}",
+            attempt, MOUSE_LISTEN_MAX_ATTEMPTS, error
+        );
+
         if !MOUSE_LISTENER_RUNNING.load(Ordering::SeqCst) {
-            return;
+            break;
+        }
+        if attempt == MOUSE_LISTEN_MAX_ATTEMPTS {
+            error!("Giving up on the mouse listener: {}", error);
+            state.set_mouse_follow_error(Some(error.clone()));
+            *state.mouse_following_active.write() = false;
+            if let Err(e) = app.emit("mouse-follow-error", MouseFollowError { reason: error }) {
+                warn!("Failed to emit mouse-follow-error: {}", e);
+            }
+            break;
         }
 
-        match event.event_type {
-            EventType::MouseMove { x, y } => {
-                if state.get_follow_mouse() {
-                    handle_mouse_move(&app, x, y);
-                }
+        thread::sleep(listen_retry_delay(attempt));
+    }
+
+    // Reset flag if we exited due to error
+    debug!("Mouse listener thread exiting");
+    MOUSE_LISTENER_RUNNING.store(false, Ordering::SeqCst);
+}
+
+/// Delay before retrying the listener after the given failed attempt
+fn listen_retry_delay(attempt: u32) -> Duration {
+    Duration::from_millis(MOUSE_LISTEN_RETRY_MS << attempt.saturating_sub(1).min(16))
+}
+
+/// Dispatch a global mouse event
+fn handle_event(app: &AppHandle, state: &Arc<AppState>, event: Event) {
+    // Check if we should stop
+    if !MOUSE_LISTENER_RUNNING.load(Ordering::SeqCst) {
+        return;
+    }
+
+    match event.event_type {
+        EventType::MouseMove { x, y } => {
+            *CURSOR_POSITION.lock() = (x, y);
+            if state.get_follow_mouse() {
+                follow_cursor(app, state, x, y);
+            }
+        }
+        EventType::ButtonPress(button) => {
+            if state.get_hide_on_ads() && button_index(&button) == state.get_ads_button() {
+                handle_ads_press(app, state);
             }
-            EventType::ButtonPress(Button::Right) => {
-                if state.get_hide_on_ads() {
-                    set_windows_visible(&app, &state, false);
-                }
+        }
+        EventType::ButtonRelease(button) => {
+            if state.get_hide_on_ads() && button_index(&button) == state.get_ads_button() {
+                handle_ads_release(app, state);
             }
-            EventType::ButtonRelease(Button::Right) => {
-                if state.get_hide_on_ads() {
-                    // Only show if globally visible
-                    if state.is_visible() {
-                        set_windows_visible(&app, &state, true);
-                    }
-                }
+        }
+        EventType::Wheel { delta_y, .. } => {
+            if state.get_scroll_wheel_opacity() && !state.is_locked() {
+                handle_wheel(app, state, delta_y);
             }
-            _ => {}
         }
+        _ => {}
+    }
+}
+
+/// Interval between window moves for a mouse following frame rate
+pub fn follow_interval_ms(fps: u32) -> u64 {
+    match fps {
+        0 => MOUSE_FOLLOW_DEBOUNCE_MS,
+        fps => 1000 / u64::from(fps),
+    }
+}
+
+/// How long to wait before the next follow move, or `None` to move now
+fn follow_delay(last: Option<Instant>, now: Instant, interval: Duration) -> Option<Duration> {
+    last.and_then(|at| interval.checked_sub(now.duration_since(at)))
+        .filter(|delay| !delay.is_zero())
+}
+
+/// Move the window to the cursor, throttled to the follow frame rate
+///
+/// Positions inside an interval are not dropped: the latest one is flushed
+/// when the interval ends, so the crosshair settles on the cursor once it
+/// stops moving.
+fn follow_cursor(app: &AppHandle, state: &Arc<AppState>, x: f64, y: f64) {
+    // The smoothing timer picks up the latest cursor position itself
+    if FOLLOW_SMOOTHING.load(Ordering::SeqCst) {
+        return;
+    }
+
+    let interval = Duration::from_millis(follow_interval_ms(state.get_mouse_follow_fps()));
+    let now = Instant::now();
+    let mut last = LAST_FOLLOW_MOVE.lock();
+
+    let Some(delay) = follow_delay(*last, now, interval) else {
+        *last = Some(now);
+        drop(last);
+        PENDING_FOLLOW.lock().take();
+        record_follow_move(&mut FOLLOW_MOVE_TIMES.lock(), now);
+        if handle_mouse_move(app, &state.get_follow_mouse_config(), x, y) {
+            start_follow_smoothing(app, state);
+        }
+        return;
     };
 
-    // Start listening - this blocks until an error occurs
-    if let Err(error) = listen(callback) {
-        error!("Error in mouse listener: {:?}", error);
+    // A flush is already scheduled while a position is pending
+    if PENDING_FOLLOW.lock().replace((x, y)).is_some() {
+        return;
     }
+    drop(last);
 
-    // Reset flag if we exited due to error
-    debug!("Mouse listener thread exiting");
-    MOUSE_LISTENER_RUNNING.store(false, Ordering::SeqCst);
+    let app = app.clone();
+    let state = state.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(delay).await;
+        flush_pending_follow(&app, &state);
+    });
+}
+
+/// Move the window to the position left pending at the end of an interval
+fn flush_pending_follow(app: &AppHandle, state: &Arc<AppState>) {
+    let Some((x, y)) = PENDING_FOLLOW.lock().take() else {
+        return;
+    };
+    if !MOUSE_LISTENER_RUNNING.load(Ordering::SeqCst)
+        || !state.get_follow_mouse()
+        || FOLLOW_SMOOTHING.load(Ordering::SeqCst)
+    {
+        return;
+    }
+
+    let now = Instant::now();
+    *LAST_FOLLOW_MOVE.lock() = Some(now);
+    record_follow_move(&mut FOLLOW_MOVE_TIMES.lock(), now);
+    if handle_mouse_move(app, &state.get_follow_mouse_config(), x, y) {
+        start_follow_smoothing(app, state);
+    }
+}
+
+/// Keep stepping the window toward the cursor until it arrives
+///
+/// Smoothing only covers part of the distance per move, so without this the
+/// window would stop short of the cursor as soon as the mouse stops moving.
+fn start_follow_smoothing(app: &AppHandle, state: &Arc<AppState>) {
+    if FOLLOW_SMOOTHING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let period = Duration::from_millis(follow_interval_ms(state.get_mouse_follow_fps()));
+    let app = app.clone();
+    let state = state.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        // The first tick completes immediately, but a move just happened
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+            if !MOUSE_LISTENER_RUNNING.load(Ordering::SeqCst) || !state.get_follow_mouse() {
+                break;
+            }
+
+            let (x, y) = *CURSOR_POSITION.lock();
+            let now = Instant::now();
+            *LAST_FOLLOW_MOVE.lock() = Some(now);
+            record_follow_move(&mut FOLLOW_MOVE_TIMES.lock(), now);
+            if !handle_mouse_move(&app, &state.get_follow_mouse_config(), x, y) {
+                break;
+            }
+        }
+
+        FOLLOW_SMOOTHING.store(false, Ordering::SeqCst);
+    });
+}
+
+/// Adjust opacity when the wheel is scrolled over the main window
+fn handle_wheel(app: &AppHandle, state: &AppState, delta_y: i64) {
+    let window = match app.get_webview_window("main") {
+        Some(w) => w,
+        None => return,
+    };
+
+    let (position, size) = match (window.outer_position(), window.outer_size()) {
+        (Ok(p), Ok(s)) => (p, s),
+        _ => return,
+    };
+
+    let (x, y) = *CURSOR_POSITION.lock();
+    let bounds = [(position.x, position.y, size.width, size.height)];
+    if !window::is_point_on_displays(x as i32, y as i32, &bounds) {
+        return;
+    }
+
+    let opacity = (state.get_opacity() + delta_y as f64 * SCROLL_OPACITY_STEP).clamp(0.0, 1.0);
+    state.set_opacity(opacity);
+}
+
+/// Map an rdev button to its index (1 = left, 2 = right, 3 = middle)
+pub fn button_index(button: &Button) -> u8 {
+    match button {
+        Button::Left => 1,
+        Button::Right => 2,
+        Button::Middle => 3,
+        Button::Unknown(index) => *index,
+    }
+}
+
+/// Hide the crosshair when the ADS button is pressed, after the configured delay
+fn handle_ads_press(app: &AppHandle, state: &Arc<AppState>) {
+    ADS_PRESSED.store(true, Ordering::SeqCst);
+
+    let delay = state.get_hide_on_ads_delay_ms();
+    if delay == 0 {
+        set_ads_active(app, state, true);
+        return;
+    }
+
+    let app = app.clone();
+    let state = state.clone();
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(delay));
+        // Skip if the button was released before the delay elapsed
+        if ADS_PRESSED.load(Ordering::SeqCst) {
+            set_ads_active(&app, &state, true);
+        }
+    });
+}
+
+/// Show the crosshair again when the ADS button is released
+fn handle_ads_release(app: &AppHandle, state: &AppState) {
+    ADS_PRESSED.store(false, Ordering::SeqCst);
+    set_ads_active(app, state, false);
+}
+
+/// Apply the ADS state to all windows and notify the frontend
+fn set_ads_active(app: &AppHandle, state: &AppState, is_ads: bool) {
+    if is_ads {
+        set_windows_visible(app, state, false);
+    } else if state.is_visible() {
+        // Only show if globally visible
+        set_windows_visible(app, state, true);
+    }
+
+    if let Err(e) = app.emit("ads-state-changed", is_ads) {
+        warn!("Failed to emit ads-state-changed: {}", e);
+    }
 }
 
 /// Helper to show/hide all windows
@@ -152,31 +484,214 @@ fn set_windows_visible(app: &AppHandle, state: &AppState, visible: bool) {
     }
 }
 
+/// Calculate where the window should move for a given cursor target
+///
+/// `current` is the window's position and `target` the position that
+/// would center it on the cursor. Returns `None` if the move falls inside
+/// the dead zone. Smoothed moves cover at least a pixel, so repeated steps
+/// always reach the target.
+pub fn follow_position(
+    current: (i32, i32),
+    target: (i32, i32),
+    config: &FollowMouseConfig,
+) -> Option<(i32, i32)> {
+    let target = (target.0 + config.offset_x, target.1 + config.offset_y);
+    let dx = target.0 - current.0;
+    let dy = target.1 - current.1;
+
+    if dx.unsigned_abs() + dy.unsigned_abs() < config.dead_zone_px {
+        return None;
+    }
+
+    let factor = 1.0 - config.smoothing.clamp(0.0, 1.0);
+    let step = |delta: i32| match (delta as f64 * factor).round() as i32 {
+        0 if factor > 0.0 => delta.signum(),
+        step => step,
+    };
+    Some((current.0 + step(dx), current.1 + step(dy)))
+}
+
+/// Check whether a window at `position` still has further to go
+///
+/// False once the window has reached the target, is inside the dead zone,
+/// or cannot move because smoothing is at its maximum.
+pub fn follow_pending(
+    position: (i32, i32),
+    target: (i32, i32),
+    config: &FollowMouseConfig,
+) -> bool {
+    follow_position(position, target, config).is_some_and(|next| next != position)
+}
+
 /// Handle a mouse move event by updating the window position
-fn handle_mouse_move(app: &AppHandle, x: f64, y: f64) {
+///
+/// Returns whether the window still has to move further to reach the cursor.
+fn handle_mouse_move(app: &AppHandle, config: &FollowMouseConfig, x: f64, y: f64) -> bool {
     // Get the main window
     let window = match app.get_webview_window("main") {
         Some(w) => w,
         None => {
             // Only warn once to avoid log spam
             // warn!("Main window not found for mouse following");
-            return;
+            return false;
         }
     };
 
     // Get window size to center it on the cursor
     let size = match window.outer_size() {
         Ok(s) => s,
-        Err(_) => return,
+        Err(_) => return false,
+    };
+
+    let current = match window.outer_position() {
+        Ok(p) => (p.x, p.y),
+        Err(_) => return false,
     };
 
     // Calculate position to center window on cursor
-    let new_x = x as i32 - (size.width as i32 / 2);
-    let new_y = y as i32 - (size.height as i32 / 2);
+    let target = (
+        x as i32 - (size.width as i32 / 2),
+        y as i32 - (size.height as i32 / 2),
+    );
+
+    let (new_x, new_y) = match follow_position(current, target, config) {
+        Some(p) => p,
+        None => return false,
+    };
 
     // Move the window
     let _ = window.set_position(tauri::Position::Physical(tauri::PhysicalPosition {
         x: new_x,
         y: new_y,
     }));
+
+    follow_pending((new_x, new_y), target, config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_listen_retry_delay() {
+        let delays: Vec<u64> = (1..=MOUSE_LISTEN_MAX_ATTEMPTS)
+            .map(|attempt| listen_retry_delay(attempt).as_millis() as u64)
+            .collect();
+        assert_eq!(delays, vec![500, 1000, 2000, 4000, 8000]);
+    }
+
+    #[test]
+    fn test_follow_interval_ms() {
+        assert_eq!(
+            follow_interval_ms(crate::config::DEFAULT_MOUSE_FOLLOW_FPS),
+            MOUSE_FOLLOW_DEBOUNCE_MS
+        );
+        assert_eq!(follow_interval_ms(1), 1000);
+        assert_eq!(follow_interval_ms(240), 4);
+        assert_eq!(follow_interval_ms(0), MOUSE_FOLLOW_DEBOUNCE_MS);
+    }
+
+    #[test]
+    fn test_follow_delay() {
+        let now = Instant::now();
+        let interval = Duration::from_millis(16);
+
+        assert_eq!(follow_delay(None, now, interval), None);
+        assert_eq!(
+            follow_delay(Some(now), now + Duration::from_millis(10), interval),
+            Some(Duration::from_millis(6))
+        );
+        assert_eq!(
+            follow_delay(Some(now), now + Duration::from_millis(16), interval),
+            None
+        );
+        assert_eq!(
+            follow_delay(Some(now), now + Duration::from_millis(40), interval),
+            None
+        );
+    }
+
+    #[test]
+    fn test_follow_fps() {
+        let start = Instant::now();
+        let mut samples = VecDeque::new();
+        assert_eq!(follow_fps(&samples, start), 0.0);
+
+        for i in 0..=FOLLOW_FPS_SAMPLE_COUNT as u64 {
+            record_follow_move(&mut samples, start + Duration::from_millis(i * 20));
+        }
+        assert_eq!(samples.len(), FOLLOW_FPS_SAMPLE_COUNT);
+
+        let last = *samples.back().unwrap();
+        let fps = follow_fps(&samples, last);
+        assert!((fps - 50.0).abs() < 0.01, "fps was {}", fps);
+
+        let stale = last + Duration::from_millis(FOLLOW_FPS_STALE_MS + 1);
+        assert_eq!(follow_fps(&samples, stale), 0.0);
+    }
+
+    #[test]
+    fn test_button_index() {
+        assert_eq!(button_index(&Button::Left), 1);
+        assert_eq!(button_index(&Button::Right), 2);
+        assert_eq!(button_index(&Button::Middle), 3);
+        assert_eq!(button_index(&Button::Unknown(8)), 8);
+    }
+
+    #[test]
+    fn test_follow_position_instant() {
+        let config = FollowMouseConfig::default();
+        assert_eq!(follow_position((0, 0), (100, 50), &config), Some((100, 50)));
+    }
+
+    #[test]
+    fn test_follow_position_smoothing_and_offset() {
+        let config = FollowMouseConfig {
+            smoothing: 0.5,
+            offset_x: 10,
+            offset_y: -10,
+            ..Default::default()
+        };
+        assert_eq!(follow_position((0, 0), (90, 110), &config), Some((50, 50)));
+
+        let frozen = FollowMouseConfig {
+            smoothing: 1.0,
+            ..Default::default()
+        };
+        assert_eq!(follow_position((5, 5), (100, 100), &frozen), Some((5, 5)));
+    }
+
+    #[test]
+    fn test_follow_position_dead_zone() {
+        let config = FollowMouseConfig {
+            dead_zone_px: 10,
+            ..Default::default()
+        };
+        assert_eq!(follow_position((0, 0), (4, 5), &config), None);
+        assert_eq!(follow_position((0, 0), (5, 5), &config), Some((5, 5)));
+    }
+
+    #[test]
+    fn test_follow_position_converges() {
+        let config = FollowMouseConfig {
+            smoothing: 0.9,
+            ..Default::default()
+        };
+        assert_eq!(follow_position((0, 0), (3, -3), &config), Some((1, -1)));
+
+        let mut position = (0, 0);
+        let mut steps = 0;
+        while follow_pending(position, (100, 40), &config) {
+            position = follow_position(position, (100, 40), &config).unwrap();
+            steps += 1;
+            assert!(steps < 1000, "smoothing never reached the target");
+        }
+        assert_eq!(position, (100, 40));
+
+        let frozen = FollowMouseConfig {
+            smoothing: 1.0,
+            ..Default::default()
+        };
+        assert!(!follow_pending((0, 0), (100, 40), &frozen));
+    }
 }
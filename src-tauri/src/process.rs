@@ -0,0 +1,393 @@
+//! Game process detection
+//!
+//! This module polls the running processes and switches to the crosshair
+//! profile configured for a game while it is running, reverting to the
+//! previous appearance once the game exits. It also hides the crosshair
+//! while one of the configured applications is in the foreground.
+
+use crate::config::{FOCUS_POLL_INTERVAL_MS, PROCESS_POLL_INTERVAL_MS};
+use crate::state::{AppState, CrosshairProfile, ProcessRule};
+use log::{debug, info};
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use sysinfo::System;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Payload for the `profile-auto-switched` event
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileSwitchEvent {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub reason: String,
+}
+
+/// Payload for the `process-hide-triggered` and `process-show-triggered` events
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessFocusEvent {
+    pub process_name: String,
+}
+
+/// A profile switch made by the watcher that should be reverted later
+struct AutoSwitch {
+    profile_name: String,
+    previous_name: Option<String>,
+    previous: CrosshairProfile,
+}
+
+/// Start the background process watcher
+pub fn start_process_watcher(app: &AppHandle) {
+    let app = app.clone();
+
+    std::thread::spawn(move || {
+        let mut system = System::new();
+        let mut switched: Option<AutoSwitch> = None;
+
+        loop {
+            if let Err(e) = check_processes(&app, &mut system, &mut switched) {
+                debug!("Process check failed: {}", e);
+            }
+            std::thread::sleep(Duration::from_millis(PROCESS_POLL_INTERVAL_MS));
+        }
+    });
+
+    info!("Process watcher started");
+}
+
+/// Apply the first matching rule, or revert a previous automatic switch
+fn check_processes(
+    app: &AppHandle,
+    system: &mut System,
+    switched: &mut Option<AutoSwitch>,
+) -> Result<(), String> {
+    let state = app.state::<Arc<AppState>>();
+    let rules = state.get_process_rules();
+    if rules.is_empty() && switched.is_none() {
+        return Ok(());
+    }
+
+    system.refresh_processes();
+    let running: Vec<String> = system
+        .processes()
+        .values()
+        .map(|process| process.name().to_string())
+        .collect();
+
+    let event = match find_matching_rule(&rules, &running) {
+        Some(rule) => {
+            if switched
+                .as_ref()
+                .is_some_and(|s| s.profile_name == rule.profile_name)
+            {
+                return Ok(());
+            }
+
+            let from = state.get_active_profile();
+
+            // Keep the original appearance when hopping between games
+            let (previous_name, previous) = match switched.as_ref() {
+                Some(s) => (s.previous_name.clone(), s.previous.clone()),
+                None => (from.clone(), state.profile_snapshot()),
+            };
+            state.load_profile(&rule.profile_name)?;
+            *switched = Some(AutoSwitch {
+                profile_name: rule.profile_name.clone(),
+                previous_name,
+                previous,
+            });
+
+            ProfileSwitchEvent {
+                from,
+                to: Some(rule.profile_name.clone()),
+                reason: format!("Process '{}' is running", rule.process_name),
+            }
+        }
+        None => {
+            let Some(s) = switched.take() else {
+                return Ok(());
+            };
+
+            state.restore_profile(&s.previous, s.previous_name.clone());
+
+            ProfileSwitchEvent {
+                from: Some(s.profile_name),
+                to: s.previous_name,
+                reason: "No matching process is running".to_string(),
+            }
+        }
+    };
+
+    info!(
+        "Auto-switched profile {:?} -> {:?}: {}",
+        event.from, event.to, event.reason
+    );
+
+    app.emit("profile-auto-switched", event)
+        .map_err(|e| e.to_string())
+}
+
+/// Start the background foreground application watcher
+pub fn start_focus_watcher(app: &AppHandle) {
+    let app = app.clone();
+
+    std::thread::spawn(move || {
+        let mut system = System::new();
+        let mut hidden_by: Option<String> = None;
+
+        loop {
+            if let Err(e) = check_foreground(&app, &mut system, &mut hidden_by) {
+                debug!("Foreground check failed: {}", e);
+            }
+            std::thread::sleep(Duration::from_millis(FOCUS_POLL_INTERVAL_MS));
+        }
+    });
+
+    info!("Focus watcher started");
+}
+
+/// Hide the overlay while a listed process is focused, and show it afterwards
+fn check_foreground(
+    app: &AppHandle,
+    system: &mut System,
+    hidden_by: &mut Option<String>,
+) -> Result<(), String> {
+    let state = app.state::<Arc<AppState>>();
+    let processes = state.get_hide_processes();
+    if processes.is_empty() && hidden_by.is_none() {
+        return Ok(());
+    }
+
+    let foreground = foreground_process_name(system);
+    let matched = foreground
+        .as_deref()
+        .filter(|name| find_hide_process(&processes, name).is_some());
+
+    match (matched, hidden_by.is_some()) {
+        (Some(name), false) => {
+            info!("Hiding crosshair while '{}' is focused", name);
+            crate::window::set_windows_shown(app, &state, false)?;
+            *hidden_by = Some(name.to_string());
+            app.emit(
+                "process-hide-triggered",
+                ProcessFocusEvent {
+                    process_name: name.to_string(),
+                },
+            )
+            .map_err(|e| e.to_string())
+        }
+        (None, true) => {
+            let Some(name) = hidden_by.take() else {
+                return Ok(());
+            };
+            info!("Showing crosshair after '{}' lost focus", name);
+            if state.is_visible() {
+                crate::window::set_windows_shown(app, &state, true)?;
+            }
+            app.emit(
+                "process-show-triggered",
+                ProcessFocusEvent { process_name: name },
+            )
+            .map_err(|e| e.to_string())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Find the listed process matching a foreground process name
+pub fn find_hide_process<'a>(processes: &'a [String], foreground: &str) -> Option<&'a String> {
+    processes
+        .iter()
+        .find(|name| process_name_matches(name, foreground))
+}
+
+/// Name of the process owning the focused window
+#[cfg(target_os = "windows")]
+fn foreground_process_name(system: &mut System) -> Option<String> {
+    use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+    let mut pid = 0u32;
+    unsafe {
+        let hwnd = GetForegroundWindow();
+        if hwnd.is_invalid() {
+            return None;
+        }
+        GetWindowThreadProcessId(hwnd, Some(&mut pid));
+    }
+    process_name_by_pid(system, pid)
+}
+
+/// Name of the frontmost application
+#[cfg(target_os = "macos")]
+fn foreground_process_name(_system: &mut System) -> Option<String> {
+    use objc2_app_kit::NSWorkspace;
+
+    unsafe {
+        let app = NSWorkspace::sharedWorkspace().frontmostApplication()?;
+        app.localizedName().map(|name| name.to_string())
+    }
+}
+
+/// Name of the process owning the active X11 window
+///
+/// Wayland does not expose the focused window, so nothing is ever hidden there.
+#[cfg(target_os = "linux")]
+fn foreground_process_name(system: &mut System) -> Option<String> {
+    use x11::xlib;
+
+    unsafe {
+        let display = xlib::XOpenDisplay(std::ptr::null());
+        if display.is_null() {
+            return None;
+        }
+
+        // The active window can close between the two reads, which is a
+        // BadWindow error that would otherwise exit the app
+        let root = xlib::XDefaultRootWindow(display);
+        let pid = crate::x11_errors::trap_errors(display, || {
+            read_x11_cardinal(display, root, "_NET_ACTIVE_WINDOW")
+                .filter(|&window| window != 0)
+                .and_then(|window| read_x11_cardinal(display, window, "_NET_WM_PID"))
+        });
+        xlib::XCloseDisplay(display);
+
+        let pid = match pid {
+            Ok(pid) => pid?,
+            Err(e) => {
+                debug!("Failed to read the active window: {}", e);
+                return None;
+            }
+        };
+        process_name_by_pid(system, pid as u32)
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+fn foreground_process_name(_system: &mut System) -> Option<String> {
+    None
+}
+
+/// Read the first 32-bit value of a window property
+#[cfg(target_os = "linux")]
+unsafe fn read_x11_cardinal(
+    display: *mut x11::xlib::Display,
+    window: x11::xlib::Window,
+    property: &str,
+) -> Option<x11::xlib::Window> {
+    use x11::xlib;
+
+    let name = std::ffi::CString::new(property).ok()?;
+    let atom = xlib::XInternAtom(display, name.as_ptr(), xlib::True);
+    if atom == 0 {
+        return None;
+    }
+
+    let mut actual_type = 0;
+    let mut actual_format = 0;
+    let mut count = 0;
+    let mut bytes_after = 0;
+    let mut data = std::ptr::null_mut();
+    let status = xlib::XGetWindowProperty(
+        display,
+        window,
+        atom,
+        0,
+        1,
+        xlib::False,
+        xlib::AnyPropertyType as xlib::Atom,
+        &mut actual_type,
+        &mut actual_format,
+        &mut count,
+        &mut bytes_after,
+        &mut data,
+    );
+    if status != xlib::Success as i32 || data.is_null() {
+        return None;
+    }
+
+    // 32-bit properties are returned as an array of longs
+    let value = (count > 0 && actual_format == 32).then(|| *(data as *const xlib::Window));
+    xlib::XFree(data as *mut _);
+    value
+}
+
+/// Look up a process name by its ID
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn process_name_by_pid(system: &mut System, pid: u32) -> Option<String> {
+    let pid = sysinfo::Pid::from_u32(pid);
+    if !system.refresh_process(pid) {
+        return None;
+    }
+    system
+        .process(pid)
+        .map(|process| process.name().to_string())
+}
+
+/// Find the first rule whose process is in the running list
+pub fn find_matching_rule<'a>(
+    rules: &'a [ProcessRule],
+    running: &[String],
+) -> Option<&'a ProcessRule> {
+    rules.iter().find(|rule| {
+        running
+            .iter()
+            .any(|name| process_name_matches(&rule.process_name, name))
+    })
+}
+
+/// Compare process names case-insensitively, ignoring a trailing `.exe`
+pub fn process_name_matches(rule_name: &str, process_name: &str) -> bool {
+    fn normalize(name: &str) -> String {
+        let name = name.trim().to_lowercase();
+        match name.strip_suffix(".exe") {
+            Some(stem) => stem.to_string(),
+            None => name,
+        }
+    }
+
+    normalize(rule_name) == normalize(process_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(process: &str, profile: &str) -> ProcessRule {
+        ProcessRule {
+            process_name: process.to_string(),
+            profile_name: profile.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_process_name_matches() {
+        assert!(process_name_matches("csgo.exe", "csgo.exe"));
+        assert!(process_name_matches("CSGO.EXE", "csgo.exe"));
+        assert!(process_name_matches("csgo", "csgo.exe"));
+        assert!(process_name_matches("csgo.exe", "csgo"));
+        assert!(!process_name_matches("csgo", "csgo2"));
+    }
+
+    #[test]
+    fn test_find_matching_rule() {
+        let rules = vec![rule("valorant.exe", "val"), rule("cs2", "cs")];
+        let running = vec!["explorer.exe".to_string(), "cs2.exe".to_string()];
+
+        assert_eq!(
+            find_matching_rule(&rules, &running).map(|r| r.profile_name.as_str()),
+            Some("cs")
+        );
+        assert!(find_matching_rule(&rules, &["explorer.exe".to_string()]).is_none());
+    }
+
+    #[test]
+    fn test_find_hide_process() {
+        let processes = vec!["explorer.exe".to_string(), "Finder".to_string()];
+
+        assert_eq!(
+            find_hide_process(&processes, "Explorer"),
+            Some(&processes[0])
+        );
+        assert_eq!(find_hide_process(&processes, "finder"), Some(&processes[1]));
+        assert!(find_hide_process(&processes, "cs2.exe").is_none());
+    }
+}
@@ -0,0 +1,118 @@
+//! Sound feedback
+//!
+//! Actions ask the frontend to play a short sound through the `play-sound`
+//! event. The payload carries the volume and, if the user picked one, the
+//! custom file to play instead of the bundled sound.
+
+use crate::config::{sounds, SUPPORTED_SOUND_EXTENSIONS};
+use crate::state::AppState;
+use log::debug;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Payload for the `play-sound` event
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SoundEvent {
+    pub event: String,
+    pub volume: f64,
+    pub custom_path: Option<String>,
+}
+
+/// Ask the frontend to play a sound, unless sounds are disabled
+pub fn play(app: &AppHandle, event: &str) {
+    let state = app.state::<Arc<AppState>>();
+    if state.get_disable_sounds() {
+        return;
+    }
+
+    let payload = SoundEvent {
+        event: event.to_string(),
+        volume: state.get_sound_volume(),
+        custom_path: state.get_custom_sounds().remove(event),
+    };
+
+    if let Err(e) = app.emit("play-sound", payload) {
+        debug!("Failed to emit play-sound: {}", e);
+    }
+}
+
+/// Let the webview load custom sound files through the asset protocol
+///
+/// The frontend plays them with `convertFileSrc`, which only works for files
+/// in the asset protocol scope.
+pub fn allow_custom_sounds<'a>(
+    app: &AppHandle,
+    paths: impl IntoIterator<Item = &'a String>,
+) -> Result<(), String> {
+    for path in paths {
+        app.asset_protocol_scope()
+            .allow_file(path)
+            .map_err(|e| format!("Failed to allow sound {} in asset scope: {}", path, e))?;
+    }
+    Ok(())
+}
+
+/// Check that a sound name is one the app plays
+pub fn validate_sound_event(event: &str) -> Result<(), String> {
+    if sounds::ALL.contains(&event) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Unknown sound '{}', expected one of: {}",
+            event,
+            sounds::ALL.join(", ")
+        ))
+    }
+}
+
+/// Check that a custom sound file exists and has a supported format
+pub fn validate_sound_file(path: &str) -> Result<PathBuf, String> {
+    let path = Path::new(path);
+    if !path.is_file() {
+        return Err(format!("Sound file not found: {}", path.display()));
+    }
+
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+    if !SUPPORTED_SOUND_EXTENSIONS.contains(&extension.as_str()) {
+        return Err(format!(
+            "Unsupported sound format '{}', expected one of: {}",
+            extension,
+            SUPPORTED_SOUND_EXTENSIONS.join(", ")
+        ));
+    }
+
+    Ok(path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_sound_event() {
+        assert!(validate_sound_event(sounds::LOCK).is_ok());
+        assert!(validate_sound_event("explode").is_err());
+    }
+
+    #[test]
+    fn test_validate_sound_file() {
+        let dir = std::env::temp_dir().join(format!("crossover-sound-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let wav = dir.join("click.WAV");
+        let txt = dir.join("click.txt");
+        std::fs::write(&wav, b"RIFF").unwrap();
+        std::fs::write(&txt, b"text").unwrap();
+
+        assert!(validate_sound_file(wav.to_str().unwrap()).is_ok());
+        assert!(validate_sound_file(txt.to_str().unwrap()).is_err());
+        assert!(validate_sound_file(dir.join("missing.wav").to_str().unwrap()).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
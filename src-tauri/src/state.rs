@@ -7,10 +7,29 @@
 
 #![allow(dead_code)]
 
-use parking_lot::RwLock;
+use crate::cli::StartupArgs;
+use crate::config::Theme;
+use crate::config::{
+    BACKUP_COUNT, BACKUP_INTERVAL_SECS, CROSSHAIR_CACHE_MAX_BYTES, DEFAULT_MOUSE_FOLLOW_FPS,
+    DEFAULT_WINDOW_HEIGHT, DEFAULT_WINDOW_WIDTH, FAST_MOVE_INCREMENT, LEGACY_STORE_KEY_PREFERENCES,
+    MAX_CROSSHAIR_HISTORY, MAX_HIDE_ON_ADS_DELAY_MS, MAX_MOUSE_FOLLOW_FPS, MAX_MOVE_STEP_PX,
+    MAX_OUTLINE_WIDTH, MAX_POSITION_HISTORY, MAX_RECENT_CROSSHAIRS, MAX_SHADOW_BLUR,
+    MAX_SHADOW_WINDOWS, MAX_WINDOW_HEIGHT, MAX_WINDOW_WIDTH, MIN_BLINK_INTERVAL_MS,
+    MIN_MOUSE_FOLLOW_FPS, MIN_MOVE_STEP_PX, MIN_OUTLINE_WIDTH, MIN_WINDOW_HEIGHT, MIN_WINDOW_WIDTH,
+    MOVE_INCREMENT, PREVIEW_CACHE_SIZE, SAVE_DEBOUNCE_MS, STORE_KEY_PREFERENCES,
+    STORE_KEY_SHADOW_COUNTER, THUMBNAIL_CACHE_SIZE, WINDOW_LEVEL_SCREEN_SAVER,
+};
+use crate::crosshair::{CrosshairCache, CrosshairMetadata, PreviewCache, ThumbnailCache};
+use crate::ipc::IpcServer;
+use parking_lot::{Mutex, MutexGuard, RwLock, RwLockWriteGuard};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
-use tauri::AppHandle;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
 use tauri_plugin_store::StoreExt;
 
 /// Default crosshair image
@@ -30,6 +49,7 @@ const STORE_FILENAME: &str = "crossover-settings.json";
 
 /// Serializable preferences that are persisted to disk
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Preferences {
     /// Current crosshair image filename
     pub crosshair: String,
@@ -44,6 +64,8 @@ pub struct Preferences {
     pub color: String,
 
     /// Whether the crosshair is locked (click-through)
+    ///
+    /// Kept in sync with `lock_mode` for older frontends.
     pub locked: bool,
 
     /// Whether the crosshair is visible
@@ -69,292 +91,3457 @@ pub struct Preferences {
 
     /// Helper reticle type (none, dot, cross, circle)
     pub reticle: String,
+
+    /// Whether to re-center the crosshair when displays are added or removed
+    pub auto_center_on_display_change: bool,
+
+    /// Whether to save preferences automatically after changes
+    pub auto_save: bool,
+
+    /// Smoothing, dead zone and offset used when following the mouse
+    pub follow_mouse_config: FollowMouseConfig,
+
+    /// Mouse button that triggers hide on ADS (1 = left, 2 = right, 3 = middle)
+    pub ads_button: u8,
+
+    /// Delay before hiding once the ADS button is pressed
+    pub hide_on_ads_delay_ms: u64,
+
+    /// Whether scrolling over the unlocked crosshair adjusts its opacity
+    pub scroll_wheel_opacity: bool,
+
+    /// Crosshair rotation in degrees (0.0 - 360.0)
+    pub rotation_degrees: f64,
+
+    /// Whether the crosshair is mirrored horizontally
+    pub flip_horizontal: bool,
+
+    /// Whether the crosshair is mirrored vertically
+    pub flip_vertical: bool,
+
+    /// Fade duration in milliseconds when showing or hiding (0 = instant)
+    pub fade_duration_ms: u64,
+
+    /// Whether the crosshair periodically flashes
+    pub blink_enabled: bool,
+
+    /// Time between blink-on and blink-off in milliseconds
+    pub blink_interval_ms: u64,
+
+    /// Reticle size in pixels
+    pub reticle_size: u32,
+
+    /// Reticle line thickness in pixels (must not exceed the size)
+    pub reticle_thickness: u32,
+
+    /// Gap in the center of the reticle in pixels
+    pub reticle_gap: u32,
+
+    /// UI theme
+    pub theme: Theme,
+
+    /// Saved crosshair profiles keyed by name
+    pub profiles: HashMap<String, CrosshairProfile>,
+
+    /// Rules that switch profile while a matching process is running
+    pub process_rules: Vec<ProcessRule>,
+
+    /// Name of the most recently loaded or saved profile
+    pub active_profile: Option<String>,
+
+    /// Try to stay above exclusive fullscreen DirectX games (Windows only)
+    pub use_dx_overlay: bool,
+
+    /// Overlay window level (0 = normal, 1 = floating, 2 = above screen saver)
+    pub window_level: u8,
+
+    /// Maximum number of shadow windows
+    pub max_shadow_windows: usize,
+
+    /// Horizontal crosshair offset within the window, in pixels
+    pub crosshair_offset_x: i32,
+
+    /// Vertical crosshair offset within the window, in pixels
+    pub crosshair_offset_y: i32,
+
+    /// Whether the crosshair has a drop shadow
+    pub shadow_enabled: bool,
+
+    /// Drop shadow blur radius in pixels
+    pub shadow_blur: u32,
+
+    /// Drop shadow color
+    pub shadow_color: String,
+
+    /// Whether the crosshair has an outline
+    pub outline_enabled: bool,
+
+    /// Outline width in pixels
+    pub outline_width: u32,
+
+    /// Outline color
+    pub outline_color: String,
+
+    /// How the crosshair is locked; `locked` mirrors whether it is click-through
+    pub lock_mode: LockMode,
+
+    /// Whether toggling the lock saves preferences immediately
+    pub save_on_lock: bool,
+
+    /// Whether imported raster crosshairs are converted to PNG
+    pub normalize_on_import: bool,
+
+    /// Crosshair to switch to when moving onto each monitor, keyed by monitor name
+    pub per_monitor_crosshair: HashMap<String, String>,
+
+    /// Maximum number of window moves per second while following the mouse
+    pub mouse_follow_fps: u32,
+
+    /// Processes that hide the crosshair while they are in the foreground
+    pub hide_for_processes: Vec<String>,
+
+    /// Keep the overlay window square when resizing it
+    pub lock_aspect_ratio: bool,
+
+    /// Resize the overlay window to match the crosshair size
+    pub auto_resize_window: bool,
+
+    /// Volume of sound feedback from 0.0 to 1.0
+    pub sound_volume: f64,
+
+    /// Custom sound file paths keyed by sound name
+    pub custom_sounds: HashMap<String, String>,
+
+    /// Never play sound feedback
+    pub disable_sounds: bool,
+
+    /// Filenames of favorite crosshairs
+    pub favorites: HashSet<String>,
+
+    /// Keep part of the window on screen when moving it
+    pub clamp_to_monitor: bool,
+
+    /// Pixels moved per keyboard move
+    pub move_step_px: u32,
+
+    /// Pixels moved per fast keyboard move
+    pub fast_move_step_px: u32,
+
+    /// Move shadow windows to the first monitor when the main window wraps back to it
+    pub sync_shadows_to_main_monitor: bool,
+
+    /// Color format the settings color picker shows: hex, rgb or hsl
+    pub color_format: String,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            crosshair: "target-dot.png".to_string(),
+            size: DEFAULT_SIZE,
+            opacity: DEFAULT_OPACITY,
+            color: DEFAULT_COLOR.to_string(),
+            locked: false,
+            visible: true,
+            follow_mouse: false,
+            position_x: None,
+            position_y: None,
+            start_on_boot: false,
+            keybinds: KeybindPreferences::default(),
+            hide_on_ads: false,
+            reticle: "dot".to_string(),
+            auto_center_on_display_change: true,
+            auto_save: true,
+            follow_mouse_config: FollowMouseConfig::default(),
+            ads_button: 2,
+            hide_on_ads_delay_ms: 0,
+            scroll_wheel_opacity: false,
+            rotation_degrees: 0.0,
+            flip_horizontal: false,
+            flip_vertical: false,
+            fade_duration_ms: 0,
+            blink_enabled: false,
+            blink_interval_ms: 500,
+            reticle_size: 4,
+            reticle_thickness: 2,
+            reticle_gap: 6,
+            theme: Theme::default(),
+            profiles: HashMap::new(),
+            process_rules: Vec::new(),
+            active_profile: None,
+            use_dx_overlay: false,
+            window_level: WINDOW_LEVEL_SCREEN_SAVER,
+            max_shadow_windows: MAX_SHADOW_WINDOWS,
+            crosshair_offset_x: 0,
+            crosshair_offset_y: 0,
+            shadow_enabled: false,
+            shadow_blur: 4,
+            shadow_color: "#000000".to_string(),
+            outline_enabled: false,
+            outline_width: 1,
+            outline_color: "#000000".to_string(),
+            lock_mode: LockMode::Unlocked,
+            save_on_lock: false,
+            normalize_on_import: false,
+            per_monitor_crosshair: HashMap::new(),
+            mouse_follow_fps: DEFAULT_MOUSE_FOLLOW_FPS,
+            hide_for_processes: Vec::new(),
+            lock_aspect_ratio: false,
+            auto_resize_window: false,
+            sound_volume: 1.0,
+            custom_sounds: HashMap::new(),
+            disable_sounds: false,
+            favorites: HashSet::new(),
+            clamp_to_monitor: false,
+            move_step_px: MOVE_INCREMENT,
+            fast_move_step_px: FAST_MOVE_INCREMENT,
+            sync_shadows_to_main_monitor: false,
+            color_format: "hex".to_string(),
+        }
+    }
+}
+
+/// Horizontal and vertical flip state, emitted with `flip-changed`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlipState {
+    pub horizontal: bool,
+    pub vertical: bool,
+}
+
+/// How the crosshair window reacts to the mouse and to movement
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LockMode {
+    /// Receives mouse events and can be moved
+    #[default]
+    Unlocked,
+    /// Mouse events pass through, but the window can still be moved
+    ClickThrough,
+    /// Mouse events pass through and the position is fixed
+    Locked,
+}
+
+impl LockMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LockMode::Unlocked => "unlocked",
+            LockMode::ClickThrough => "click_through",
+            LockMode::Locked => "locked",
+        }
+    }
+
+    /// Whether mouse events pass through the window
+    pub fn is_click_through(&self) -> bool {
+        *self != LockMode::Unlocked
+    }
+
+    /// Whether the window position is fixed
+    pub fn is_position_locked(&self) -> bool {
+        *self == LockMode::Locked
+    }
+}
+
+impl std::str::FromStr for LockMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "unlocked" => Ok(LockMode::Unlocked),
+            "click_through" => Ok(LockMode::ClickThrough),
+            "locked" => Ok(LockMode::Locked),
+            _ => Err(format!("Unknown lock mode: {}", s)),
+        }
+    }
+}
+
+/// Crosshair offset from the window center, emitted with `crosshair-offset-changed`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrosshairOffset {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// Drop shadow settings for the crosshair
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShadowEffect {
+    pub enabled: bool,
+    pub blur: u32,
+    pub color: String,
+}
+
+/// Outline settings for the crosshair
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutlineEffect {
+    pub enabled: bool,
+    pub width: u32,
+    pub color: String,
+}
+
+/// Crosshair visual effects, emitted with `crosshair-effect-changed`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrosshairEffect {
+    pub shadow: ShadowEffect,
+    pub outline: OutlineEffect,
+}
+
+/// Mouse following behaviour
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FollowMouseConfig {
+    /// Interpolation factor (0.0 = snap to cursor, 1.0 = never move)
+    pub smoothing: f64,
+
+    /// Minimum cursor travel in pixels before the window moves
+    pub dead_zone_px: u32,
+
+    /// Horizontal offset of the crosshair from the cursor
+    pub offset_x: i32,
+
+    /// Vertical offset of the crosshair from the cursor
+    pub offset_y: i32,
+}
+
+/// Partial preferences update where only the `Some` fields are applied
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PatchPreferences {
+    pub crosshair: Option<String>,
+    pub size: Option<u32>,
+    pub opacity: Option<f64>,
+    pub color: Option<String>,
+    pub locked: Option<bool>,
+    pub visible: Option<bool>,
+    pub follow_mouse: Option<bool>,
+    pub position_x: Option<i32>,
+    pub position_y: Option<i32>,
+    pub start_on_boot: Option<bool>,
+    pub keybinds: Option<KeybindPreferences>,
+    pub hide_on_ads: Option<bool>,
+    pub reticle: Option<String>,
+    pub auto_center_on_display_change: Option<bool>,
+    pub auto_save: Option<bool>,
+    pub follow_mouse_config: Option<FollowMouseConfig>,
+    pub ads_button: Option<u8>,
+    pub hide_on_ads_delay_ms: Option<u64>,
+    pub scroll_wheel_opacity: Option<bool>,
+    pub rotation_degrees: Option<f64>,
+    pub flip_horizontal: Option<bool>,
+    pub flip_vertical: Option<bool>,
+    pub fade_duration_ms: Option<u64>,
+    pub blink_enabled: Option<bool>,
+    pub blink_interval_ms: Option<u64>,
+    pub reticle_size: Option<u32>,
+    pub reticle_thickness: Option<u32>,
+    pub reticle_gap: Option<u32>,
+    pub theme: Option<Theme>,
+    pub profiles: Option<HashMap<String, CrosshairProfile>>,
+    pub process_rules: Option<Vec<ProcessRule>>,
+    pub use_dx_overlay: Option<bool>,
+    pub window_level: Option<u8>,
+    pub max_shadow_windows: Option<usize>,
+    pub crosshair_offset_x: Option<i32>,
+    pub crosshair_offset_y: Option<i32>,
+    pub shadow_enabled: Option<bool>,
+    pub shadow_blur: Option<u32>,
+    pub shadow_color: Option<String>,
+    pub outline_enabled: Option<bool>,
+    pub outline_width: Option<u32>,
+    pub outline_color: Option<String>,
+    pub lock_mode: Option<LockMode>,
+    pub save_on_lock: Option<bool>,
+    pub normalize_on_import: Option<bool>,
+    pub per_monitor_crosshair: Option<HashMap<String, String>>,
+    pub mouse_follow_fps: Option<u32>,
+    pub hide_for_processes: Option<Vec<String>>,
+    pub lock_aspect_ratio: Option<bool>,
+    pub auto_resize_window: Option<bool>,
+    pub sound_volume: Option<f64>,
+    pub custom_sounds: Option<HashMap<String, String>>,
+    pub disable_sounds: Option<bool>,
+    pub favorites: Option<HashSet<String>>,
+    pub clamp_to_monitor: Option<bool>,
+    pub move_step_px: Option<u32>,
+    pub fast_move_step_px: Option<u32>,
+    pub sync_shadows_to_main_monitor: Option<bool>,
+    pub color_format: Option<String>,
+}
+
+/// A named snapshot of the crosshair's appearance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrosshairProfile {
+    pub crosshair: String,
+    pub size: u32,
+    pub opacity: f64,
+    pub color: String,
+    pub reticle: String,
+}
+
+/// Commonly read fields copied under a single lock
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StateSnapshot {
+    pub locked: bool,
+    pub visible: bool,
+    pub follow_mouse: bool,
+    pub size: u32,
+    pub opacity: f64,
+    pub color: String,
+    pub crosshair: String,
+    pub reticle: String,
+}
+
+/// Switch to a profile while a process with the given name is running
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessRule {
+    pub process_name: String,
+    pub profile_name: String,
+}
+
+/// Initial position and settings for a new shadow window
+///
+/// Unset fields fall back to an offset from the main window and the main
+/// window's settings.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShadowWindowOptions {
+    pub position_x: Option<i32>,
+    pub position_y: Option<i32>,
+    pub crosshair: Option<String>,
+    pub size: Option<u32>,
+    pub opacity: Option<f64>,
+}
+
+impl ShadowWindowOptions {
+    /// Apply the setting overrides on top of inherited preferences
+    pub fn apply_to(&self, prefs: &mut Preferences) {
+        if let Some(crosshair) = &self.crosshair {
+            prefs.crosshair = crosshair.clone();
+        }
+        if let Some(size) = self.size {
+            prefs.size = size;
+        }
+        if let Some(opacity) = self.opacity {
+            prefs.opacity = opacity.clamp(0.0, 1.0);
+        }
+    }
+
+    /// Override the settings with the given preferences, keeping the position
+    pub fn copy_settings_from(&mut self, prefs: &Preferences) {
+        self.crosshair = Some(prefs.crosshair.clone());
+        self.size = Some(prefs.size);
+        self.opacity = Some(prefs.opacity);
+    }
+
+    /// Build a patch that applies the setting overrides to other preferences
+    pub fn settings_patch(&self) -> PatchPreferences {
+        PatchPreferences {
+            crosshair: self.crosshair.clone(),
+            size: self.size,
+            opacity: self.opacity,
+            ..Default::default()
+        }
+    }
+}
+
+/// Payload for the `preferences-corrupt` event
+#[derive(Debug, Clone, Serialize)]
+pub struct PreferencesCorrupt {
+    pub error: String,
+    pub backup_path: Option<String>,
+}
+
+/// Back and forward navigation through selected crosshairs
+#[derive(Debug, Clone, Default)]
+pub struct CrosshairHistory {
+    entries: VecDeque<String>,
+    position: usize,
+}
+
+impl CrosshairHistory {
+    /// Record a selection, dropping any forward entries and the oldest overflow
+    pub fn push(&mut self, crosshair: &str) {
+        if self.current() == Some(crosshair) {
+            return;
+        }
+
+        self.entries.truncate(self.position + 1);
+        self.entries.push_back(crosshair.to_string());
+        if self.entries.len() > MAX_CROSSHAIR_HISTORY {
+            self.entries.pop_front();
+        }
+        self.position = self.entries.len() - 1;
+    }
+
+    /// The entry the history is positioned on
+    pub fn current(&self) -> Option<&str> {
+        self.entries.get(self.position).map(String::as_str)
+    }
+
+    /// Step back, returning the previous crosshair
+    pub fn back(&mut self) -> Option<String> {
+        if self.position == 0 {
+            return None;
+        }
+        self.position -= 1;
+        self.current().map(str::to_string)
+    }
+
+    /// Step forward, returning the next crosshair
+    pub fn forward(&mut self) -> Option<String> {
+        if self.position + 1 >= self.entries.len() {
+            return None;
+        }
+        self.position += 1;
+        self.current().map(str::to_string)
+    }
+
+    /// All entries, oldest first
+    pub fn entries(&self) -> Vec<String> {
+        self.entries.iter().cloned().collect()
+    }
+}
+
+/// Payload for the `crash-recovery` event
+#[derive(Debug, Clone, Serialize)]
+pub struct CrashRecovery {
+    pub last_preferences_snapshot: Option<Preferences>,
+}
+
+/// Built-in reticle settings, emitted with `reticle-config-changed`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReticleConfig {
+    #[serde(rename = "type")]
+    pub reticle_type: String,
+    pub size: u32,
+    pub thickness: u32,
+    pub gap: u32,
+}
+
+/// Check that a reticle's thickness does not exceed its size
+pub fn validate_reticle_dimensions(size: u32, thickness: u32) -> Result<(), String> {
+    if thickness > size {
+        return Err(format!(
+            "Reticle thickness {} must not exceed size {}",
+            thickness, size
+        ));
+    }
+    Ok(())
+}
+
+/// Check that an offset keeps the crosshair center inside the window
+pub fn validate_crosshair_offset(
+    offset: CrosshairOffset,
+    window_width: u32,
+    window_height: u32,
+) -> Result<(), String> {
+    if offset.x.unsigned_abs() >= window_width / 2 || offset.y.unsigned_abs() >= window_height / 2 {
+        return Err(format!(
+            "Offset ({}, {}) would move the crosshair outside the {}x{} window",
+            offset.x, offset.y, window_width, window_height
+        ));
+    }
+    Ok(())
 }
 
-impl Default for Preferences {
-    fn default() -> Self {
-        Self {
-            crosshair: "target-dot.png".to_string(),
-            size: DEFAULT_SIZE,
-            opacity: DEFAULT_OPACITY,
-            color: DEFAULT_COLOR.to_string(),
-            locked: false,
-            visible: true,
-            follow_mouse: false,
-            position_x: None,
-            position_y: None,
-            start_on_boot: false,
-            keybinds: KeybindPreferences::default(),
-            hide_on_ads: false,
-            reticle: "dot".to_string(),
+impl Preferences {
+    /// Deserialize stored preferences, migrating fields from older versions
+    pub fn from_stored(value: serde_json::Value) -> Result<Self, serde_json::Error> {
+        let has_lock_mode = value.get("lock_mode").is_some();
+        let mut prefs: Preferences = serde_json::from_value(value)?;
+
+        // Stores written before lock modes only have the `locked` flag
+        if !has_lock_mode && prefs.locked {
+            prefs.lock_mode = LockMode::Locked;
+        }
+        prefs.locked = prefs.lock_mode.is_click_through();
+
+        Ok(prefs)
+    }
+
+    /// Get the built-in reticle settings
+    pub fn reticle_config(&self) -> ReticleConfig {
+        ReticleConfig {
+            reticle_type: self.reticle.clone(),
+            size: self.reticle_size,
+            thickness: self.reticle_thickness,
+            gap: self.reticle_gap,
+        }
+    }
+
+    /// Clamp and validate values that may come from outside the app
+    pub fn validated(mut self) -> Result<Self, String> {
+        self.opacity = self.opacity.clamp(0.0, 1.0);
+        self.color = crate::color::validate_color(&self.color)?;
+        self.window_level = self.window_level.min(WINDOW_LEVEL_SCREEN_SAVER);
+        self.max_shadow_windows = self.max_shadow_windows.min(MAX_SHADOW_WINDOWS);
+        self.shadow_blur = self.shadow_blur.min(MAX_SHADOW_BLUR);
+        self.shadow_color = crate::color::validate_color(&self.shadow_color)?;
+        self.outline_width = self
+            .outline_width
+            .clamp(MIN_OUTLINE_WIDTH, MAX_OUTLINE_WIDTH);
+        self.outline_color = crate::color::validate_color(&self.outline_color)?;
+        self.color_format = crate::color::validate_color_format(&self.color_format)?;
+        self.sound_volume = self.sound_volume.clamp(0.0, 1.0);
+        self.hide_on_ads_delay_ms = self.hide_on_ads_delay_ms.min(MAX_HIDE_ON_ADS_DELAY_MS);
+        self.mouse_follow_fps = self
+            .mouse_follow_fps
+            .clamp(MIN_MOUSE_FOLLOW_FPS, MAX_MOUSE_FOLLOW_FPS);
+        self.move_step_px = self.move_step_px.clamp(MIN_MOVE_STEP_PX, MAX_MOVE_STEP_PX);
+        self.fast_move_step_px = self
+            .fast_move_step_px
+            .clamp(MIN_MOVE_STEP_PX, MAX_MOVE_STEP_PX);
+        self.blink_interval_ms = self.blink_interval_ms.max(MIN_BLINK_INTERVAL_MS);
+        self.rotation_degrees = if self.rotation_degrees.is_finite() {
+            self.rotation_degrees.clamp(0.0, 360.0)
+        } else {
+            0.0
+        };
+        self.clamp_crosshair_offset();
+        validate_reticle_dimensions(self.reticle_size, self.reticle_thickness)?;
+        Ok(self)
+    }
+
+    /// Size of the main window these preferences open with
+    ///
+    /// The window is fitted to the crosshair when `auto_resize_window` is on,
+    /// otherwise it starts at the default size.
+    pub fn window_size(&self) -> (u32, u32) {
+        if self.auto_resize_window {
+            (
+                self.size.clamp(MIN_WINDOW_WIDTH, MAX_WINDOW_WIDTH),
+                self.size.clamp(MIN_WINDOW_HEIGHT, MAX_WINDOW_HEIGHT),
+            )
+        } else {
+            (DEFAULT_WINDOW_WIDTH, DEFAULT_WINDOW_HEIGHT)
+        }
+    }
+
+    /// Pull the crosshair offset back inside the window
+    ///
+    /// Uses the same bounds as [`validate_crosshair_offset`].
+    pub fn clamp_crosshair_offset(&mut self) {
+        let (width, height) = self.window_size();
+        let max_x = (width / 2).saturating_sub(1) as i32;
+        let max_y = (height / 2).saturating_sub(1) as i32;
+        self.crosshair_offset_x = self.crosshair_offset_x.clamp(-max_x, max_x);
+        self.crosshair_offset_y = self.crosshair_offset_y.clamp(-max_y, max_y);
+    }
+
+    /// Capture the current appearance as a profile
+    pub fn profile_snapshot(&self) -> CrosshairProfile {
+        CrosshairProfile {
+            crosshair: self.crosshair.clone(),
+            size: self.size,
+            opacity: self.opacity,
+            color: self.color.clone(),
+            reticle: self.reticle.clone(),
+        }
+    }
+
+    /// Apply a profile's appearance
+    pub fn apply_profile(&mut self, profile: &CrosshairProfile) {
+        self.crosshair = profile.crosshair.clone();
+        self.size = profile.size;
+        self.opacity = profile.opacity;
+        self.color = profile.color.clone();
+        self.reticle = profile.reticle.clone();
+    }
+
+    /// Get the drop shadow and outline settings
+    pub fn crosshair_effect(&self) -> CrosshairEffect {
+        CrosshairEffect {
+            shadow: ShadowEffect {
+                enabled: self.shadow_enabled,
+                blur: self.shadow_blur,
+                color: self.shadow_color.clone(),
+            },
+            outline: OutlineEffect {
+                enabled: self.outline_enabled,
+                width: self.outline_width,
+                color: self.outline_color.clone(),
+            },
+        }
+    }
+
+    /// Get the crosshair offset from the window center
+    pub fn crosshair_offset(&self) -> CrosshairOffset {
+        CrosshairOffset {
+            x: self.crosshair_offset_x,
+            y: self.crosshair_offset_y,
+        }
+    }
+
+    /// Get the horizontal and vertical flip state
+    pub fn flip(&self) -> FlipState {
+        FlipState {
+            horizontal: self.flip_horizontal,
+            vertical: self.flip_vertical,
+        }
+    }
+
+    /// Apply a patch, validating values the same way the individual setters do
+    ///
+    /// Nothing is modified if validation fails.
+    pub fn apply_patch(&mut self, patch: PatchPreferences) -> Result<(), String> {
+        let color = patch
+            .color
+            .as_deref()
+            .map(crate::color::validate_color)
+            .transpose()?;
+        let shadow_color = patch
+            .shadow_color
+            .as_deref()
+            .map(crate::color::validate_color)
+            .transpose()?;
+        let outline_color = patch
+            .outline_color
+            .as_deref()
+            .map(crate::color::validate_color)
+            .transpose()?;
+        let color_format = patch
+            .color_format
+            .as_deref()
+            .map(crate::color::validate_color_format)
+            .transpose()?;
+        validate_reticle_dimensions(
+            patch.reticle_size.unwrap_or(self.reticle_size),
+            patch.reticle_thickness.unwrap_or(self.reticle_thickness),
+        )?;
+
+        if let Some(crosshair) = patch.crosshair {
+            self.crosshair = crosshair;
+        }
+        if let Some(size) = patch.size {
+            self.size = size;
+        }
+        if let Some(opacity) = patch.opacity {
+            self.opacity = opacity.clamp(0.0, 1.0);
+        }
+        if let Some(color) = color {
+            self.color = color;
+        }
+        if let Some(locked) = patch.locked {
+            self.lock_mode = if locked {
+                LockMode::Locked
+            } else {
+                LockMode::Unlocked
+            };
+        }
+        if let Some(visible) = patch.visible {
+            self.visible = visible;
+        }
+        if let Some(follow_mouse) = patch.follow_mouse {
+            self.follow_mouse = follow_mouse;
+        }
+        if let Some(x) = patch.position_x {
+            self.position_x = Some(x);
+        }
+        if let Some(y) = patch.position_y {
+            self.position_y = Some(y);
+        }
+        if let Some(start_on_boot) = patch.start_on_boot {
+            self.start_on_boot = start_on_boot;
+        }
+        if let Some(keybinds) = patch.keybinds {
+            self.keybinds = keybinds;
+        }
+        if let Some(hide_on_ads) = patch.hide_on_ads {
+            self.hide_on_ads = hide_on_ads;
+        }
+        if let Some(reticle) = patch.reticle {
+            self.reticle = reticle;
+        }
+        if let Some(auto_center) = patch.auto_center_on_display_change {
+            self.auto_center_on_display_change = auto_center;
+        }
+        if let Some(auto_save) = patch.auto_save {
+            self.auto_save = auto_save;
+        }
+        if let Some(mut config) = patch.follow_mouse_config {
+            config.smoothing = config.smoothing.clamp(0.0, 1.0);
+            self.follow_mouse_config = config;
+        }
+        if let Some(ads_button) = patch.ads_button {
+            self.ads_button = ads_button;
+        }
+        if let Some(delay) = patch.hide_on_ads_delay_ms {
+            self.hide_on_ads_delay_ms = delay.min(MAX_HIDE_ON_ADS_DELAY_MS);
+        }
+        if let Some(scroll_wheel_opacity) = patch.scroll_wheel_opacity {
+            self.scroll_wheel_opacity = scroll_wheel_opacity;
+        }
+        if let Some(rotation_degrees) = patch.rotation_degrees {
+            self.rotation_degrees = rotation_degrees.clamp(0.0, 360.0);
+        }
+        if let Some(flip_horizontal) = patch.flip_horizontal {
+            self.flip_horizontal = flip_horizontal;
+        }
+        if let Some(flip_vertical) = patch.flip_vertical {
+            self.flip_vertical = flip_vertical;
+        }
+        if let Some(fade_duration_ms) = patch.fade_duration_ms {
+            self.fade_duration_ms = fade_duration_ms;
+        }
+        if let Some(blink_enabled) = patch.blink_enabled {
+            self.blink_enabled = blink_enabled;
+        }
+        if let Some(blink_interval_ms) = patch.blink_interval_ms {
+            self.blink_interval_ms = blink_interval_ms.max(MIN_BLINK_INTERVAL_MS);
+        }
+        if let Some(reticle_size) = patch.reticle_size {
+            self.reticle_size = reticle_size;
+        }
+        if let Some(reticle_thickness) = patch.reticle_thickness {
+            self.reticle_thickness = reticle_thickness;
+        }
+        if let Some(reticle_gap) = patch.reticle_gap {
+            self.reticle_gap = reticle_gap;
+        }
+        if let Some(theme) = patch.theme {
+            self.theme = theme;
+        }
+        if let Some(profiles) = patch.profiles {
+            self.profiles = profiles;
+        }
+        if let Some(process_rules) = patch.process_rules {
+            self.process_rules = process_rules;
+        }
+        if let Some(use_dx_overlay) = patch.use_dx_overlay {
+            self.use_dx_overlay = use_dx_overlay;
+        }
+        if let Some(window_level) = patch.window_level {
+            self.window_level = window_level.min(WINDOW_LEVEL_SCREEN_SAVER);
+        }
+        if let Some(max_shadow_windows) = patch.max_shadow_windows {
+            self.max_shadow_windows = max_shadow_windows.min(MAX_SHADOW_WINDOWS);
+        }
+        if let Some(crosshair_offset_x) = patch.crosshair_offset_x {
+            self.crosshair_offset_x = crosshair_offset_x;
+        }
+        if let Some(crosshair_offset_y) = patch.crosshair_offset_y {
+            self.crosshair_offset_y = crosshair_offset_y;
+        }
+        if let Some(shadow_enabled) = patch.shadow_enabled {
+            self.shadow_enabled = shadow_enabled;
+        }
+        if let Some(shadow_blur) = patch.shadow_blur {
+            self.shadow_blur = shadow_blur.min(MAX_SHADOW_BLUR);
+        }
+        if let Some(shadow_color) = shadow_color {
+            self.shadow_color = shadow_color;
+        }
+        if let Some(outline_enabled) = patch.outline_enabled {
+            self.outline_enabled = outline_enabled;
+        }
+        if let Some(outline_width) = patch.outline_width {
+            self.outline_width = outline_width.clamp(MIN_OUTLINE_WIDTH, MAX_OUTLINE_WIDTH);
+        }
+        if let Some(outline_color) = outline_color {
+            self.outline_color = outline_color;
+        }
+        if let Some(lock_mode) = patch.lock_mode {
+            self.lock_mode = lock_mode;
+        }
+        self.locked = self.lock_mode.is_click_through();
+        if let Some(save_on_lock) = patch.save_on_lock {
+            self.save_on_lock = save_on_lock;
+        }
+        if let Some(normalize_on_import) = patch.normalize_on_import {
+            self.normalize_on_import = normalize_on_import;
+        }
+        if let Some(per_monitor_crosshair) = patch.per_monitor_crosshair {
+            self.per_monitor_crosshair = per_monitor_crosshair;
+        }
+        if let Some(mouse_follow_fps) = patch.mouse_follow_fps {
+            self.mouse_follow_fps =
+                mouse_follow_fps.clamp(MIN_MOUSE_FOLLOW_FPS, MAX_MOUSE_FOLLOW_FPS);
+        }
+        if let Some(hide_for_processes) = patch.hide_for_processes {
+            self.hide_for_processes = hide_for_processes;
+        }
+        if let Some(lock_aspect_ratio) = patch.lock_aspect_ratio {
+            self.lock_aspect_ratio = lock_aspect_ratio;
+        }
+        if let Some(auto_resize_window) = patch.auto_resize_window {
+            self.auto_resize_window = auto_resize_window;
+        }
+        if let Some(sound_volume) = patch.sound_volume {
+            self.sound_volume = sound_volume.clamp(0.0, 1.0);
+        }
+        if let Some(custom_sounds) = patch.custom_sounds {
+            self.custom_sounds = custom_sounds;
+        }
+        if let Some(disable_sounds) = patch.disable_sounds {
+            self.disable_sounds = disable_sounds;
+        }
+        if let Some(favorites) = patch.favorites {
+            self.favorites = favorites;
+        }
+        if let Some(clamp_to_monitor) = patch.clamp_to_monitor {
+            self.clamp_to_monitor = clamp_to_monitor;
+        }
+        if let Some(move_step_px) = patch.move_step_px {
+            self.move_step_px = move_step_px.clamp(MIN_MOVE_STEP_PX, MAX_MOVE_STEP_PX);
+        }
+        if let Some(fast_move_step_px) = patch.fast_move_step_px {
+            self.fast_move_step_px = fast_move_step_px.clamp(MIN_MOVE_STEP_PX, MAX_MOVE_STEP_PX);
+        }
+        if let Some(sync_shadows_to_main_monitor) = patch.sync_shadows_to_main_monitor {
+            self.sync_shadows_to_main_monitor = sync_shadows_to_main_monitor;
+        }
+        if let Some(color_format) = color_format {
+            self.color_format = color_format;
+        }
+        self.clamp_crosshair_offset();
+
+        Ok(())
+    }
+}
+
+/// Keybind preferences
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeybindPreferences {
+    pub toggle_lock: String,
+    pub center: String,
+    pub hide: String,
+    pub reset: String,
+    pub move_up: String,
+    pub move_down: String,
+    pub move_left: String,
+    pub move_right: String,
+    pub change_display: String,
+    pub duplicate: String,
+    pub quit: String,
+    pub rotate_cw: String,
+    pub rotate_ccw: String,
+    pub toggle_flip_horizontal: String,
+    /// Lock or unlock and save immediately; empty when unbound
+    pub lock_and_save: String,
+    pub undo_move: String,
+    pub redo_move: String,
+    /// Turn sound feedback off or back on; empty when unbound
+    pub mute_sounds: String,
+    pub unmute_sounds: String,
+    /// Switch to the next favorite crosshair; empty when unbound
+    pub next_favorite_crosshair: String,
+    /// Move by the fast step; empty when unbound
+    pub move_up_fast: String,
+    pub move_down_fast: String,
+    pub move_left_fast: String,
+    pub move_right_fast: String,
+}
+
+impl Default for KeybindPreferences {
+    fn default() -> Self {
+        Self {
+            toggle_lock: "Control+Shift+Alt+X".to_string(),
+            center: "Control+Shift+Alt+C".to_string(),
+            hide: "Control+Shift+Alt+H".to_string(),
+            reset: "Control+Shift+Alt+R".to_string(),
+            move_up: "Control+Shift+Alt+Up".to_string(),
+            move_down: "Control+Shift+Alt+Down".to_string(),
+            move_left: "Control+Shift+Alt+Left".to_string(),
+            move_right: "Control+Shift+Alt+Right".to_string(),
+            change_display: "Control+Shift+Alt+M".to_string(),
+            duplicate: "Control+Shift+Alt+D".to_string(),
+            quit: "Control+Shift+Alt+Q".to_string(),
+            rotate_cw: "Control+Shift+Alt+E".to_string(),
+            rotate_ccw: "Control+Shift+Alt+W".to_string(),
+            toggle_flip_horizontal: "Control+Shift+Alt+F".to_string(),
+            lock_and_save: String::new(),
+            undo_move: "Control+Shift+Alt+Z".to_string(),
+            redo_move: "Control+Shift+Alt+Y".to_string(),
+            mute_sounds: String::new(),
+            unmute_sounds: String::new(),
+            next_favorite_crosshair: String::new(),
+            move_up_fast: String::new(),
+            move_down_fast: String::new(),
+            move_left_fast: String::new(),
+            move_right_fast: String::new(),
+        }
+    }
+}
+
+impl KeybindPreferences {
+    /// Get mutable access to the keybind for an action
+    pub fn get_mut(&mut self, action: &str) -> Option<&mut String> {
+        match action {
+            "toggle_lock" => Some(&mut self.toggle_lock),
+            "center" => Some(&mut self.center),
+            "hide" => Some(&mut self.hide),
+            "reset" => Some(&mut self.reset),
+            "move_up" => Some(&mut self.move_up),
+            "move_down" => Some(&mut self.move_down),
+            "move_left" => Some(&mut self.move_left),
+            "move_right" => Some(&mut self.move_right),
+            "change_display" => Some(&mut self.change_display),
+            "duplicate" => Some(&mut self.duplicate),
+            "quit" => Some(&mut self.quit),
+            "rotate_cw" => Some(&mut self.rotate_cw),
+            "rotate_ccw" => Some(&mut self.rotate_ccw),
+            "toggle_flip_horizontal" => Some(&mut self.toggle_flip_horizontal),
+            "lock_and_save" => Some(&mut self.lock_and_save),
+            "undo_move" => Some(&mut self.undo_move),
+            "redo_move" => Some(&mut self.redo_move),
+            "mute_sounds" => Some(&mut self.mute_sounds),
+            "unmute_sounds" => Some(&mut self.unmute_sounds),
+            "next_favorite_crosshair" => Some(&mut self.next_favorite_crosshair),
+            "move_up_fast" => Some(&mut self.move_up_fast),
+            "move_down_fast" => Some(&mut self.move_down_fast),
+            "move_left_fast" => Some(&mut self.move_left_fast),
+            "move_right_fast" => Some(&mut self.move_right_fast),
+            _ => None,
+        }
+    }
+}
+
+/// Callback run after the preferences change
+pub type PreferenceListener = Box<dyn Fn(&Preferences) + Send + Sync>;
+
+/// Write access to the preferences that notifies listeners when dropped
+///
+/// The preferences are marked unsaved while the write lock is still held, so
+/// auto-save can never clear the flag before the change lands. The write lock
+/// is then downgraded before the listeners run, so they see the final
+/// preferences and no other write can slip in between. Listeners must not
+/// lock the preferences again through `AppState`.
+pub struct PreferencesGuard<'a> {
+    guard: Option<RwLockWriteGuard<'a, Preferences>>,
+    listeners: &'a RwLock<Vec<PreferenceListener>>,
+    dirty: &'a AtomicBool,
+    snapshot_dirty: &'a AtomicBool,
+}
+
+impl Deref for PreferencesGuard<'_> {
+    type Target = Preferences;
+
+    fn deref(&self) -> &Preferences {
+        self.guard.as_ref().expect("guard is only taken on drop")
+    }
+}
+
+impl DerefMut for PreferencesGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Preferences {
+        self.guard.as_mut().expect("guard is only taken on drop")
+    }
+}
+
+impl Drop for PreferencesGuard<'_> {
+    fn drop(&mut self) {
+        let Some(guard) = self.guard.take() else {
+            return;
+        };
+        self.dirty.store(true, Ordering::SeqCst);
+        self.snapshot_dirty.store(true, Ordering::SeqCst);
+
+        let listeners = self.listeners.read();
+        if listeners.is_empty() {
+            return;
+        }
+
+        let prefs = RwLockWriteGuard::downgrade(guard);
+        for listener in listeners.iter() {
+            listener(&prefs);
+        }
+    }
+}
+
+/// Global application state
+pub struct AppState {
+    /// Current preferences
+    pub preferences: RwLock<Preferences>,
+
+    /// Callbacks run after every preferences change
+    on_preference_changed: RwLock<Vec<PreferenceListener>>,
+
+    /// Set of shadow window labels
+    pub shadow_windows: RwLock<HashSet<String>>,
+
+    /// Counter for shadow window IDs, persisted so IDs are never reused
+    shadow_counter: RwLock<u64>,
+
+    /// Labels of windows that have already had overlay setup applied
+    setup_complete_windows: RwLock<HashSet<String>>,
+
+    /// Setting overrides for shadow windows, keyed by label
+    shadow_options: RwLock<HashMap<String, ShadowWindowOptions>>,
+
+    /// Whether mouse following is currently active
+    pub mouse_following_active: RwLock<bool>,
+
+    /// Why the mouse listener could not be started, if it failed
+    mouse_follow_error: RwLock<Option<String>>,
+
+    /// The external control server, while it runs
+    ipc_server: Mutex<Option<IpcServer>>,
+
+    /// Last known display layout, used to detect monitor changes
+    known_displays: RwLock<Vec<String>>,
+
+    /// Whether settings and custom crosshairs live next to the executable
+    pub portable_mode: bool,
+
+    /// Whether preferences changed since the last save
+    dirty: AtomicBool,
+
+    /// Whether preferences changed since the last crash recovery snapshot
+    snapshot_dirty: AtomicBool,
+
+    /// Whether the loaded preferences predate the `start_on_boot` setting
+    start_on_boot_unset: AtomicBool,
+
+    /// Preferences before and after the command line overrides, as JSON
+    session_overrides: Mutex<Option<(serde_json::Value, serde_json::Value)>>,
+
+    /// Currently registered shortcuts, keyed by action
+    registered_hotkeys: RwLock<HashMap<String, String>>,
+
+    /// When the position should be saved if the window stops moving
+    move_save_deadline: Mutex<Option<Instant>>,
+
+    /// When the preference backups were last rotated
+    last_backup: Mutex<Option<Instant>>,
+
+    /// Colored SVG data URLs keyed by (filename, color)
+    svg_cache: RwLock<HashMap<(String, String), String>>,
+
+    /// Raw crosshair image bytes keyed by filename
+    crosshair_cache: Mutex<CrosshairCache>,
+
+    /// Recent crosshair previews keyed by (filename, color)
+    preview_cache: Mutex<PreviewCache>,
+
+    /// Crosshair image metadata keyed by filename
+    metadata_cache: RwLock<HashMap<String, CrosshairMetadata>>,
+
+    /// Picker thumbnail data URLs keyed by filename
+    thumbnail_cache: Mutex<ThumbnailCache>,
+
+    /// Running blink task, if blink mode is active
+    pub blink_task: Mutex<Option<tauri::async_runtime::JoinHandle<()>>>,
+
+    /// Whether saved preferences are ignored for this session
+    pub safe_mode: bool,
+
+    /// Whether the app runs with only the tray and hotkeys, without an overlay
+    pub headless: bool,
+
+    /// Whether this process holds the single instance lock
+    pub single_instance: bool,
+
+    /// Appearance overrides passed on the command line
+    pub startup_args: StartupArgs,
+
+    /// ID of the system tray icon
+    pub tray_id: String,
+
+    /// ID of the tray icon once it has been built
+    pub tray_icon_id: RwLock<Option<String>>,
+
+    /// IDs of the tray menu items that show the lock and hidden state
+    pub tray_lock_item_id: String,
+    pub tray_hide_item_id: String,
+
+    /// Tray menu, kept so check items can be updated
+    pub tray_menu: Mutex<Option<tauri::menu::Menu<tauri::Wry>>>,
+
+    /// Recently selected crosshairs, most recent first
+    recent_crosshairs: RwLock<VecDeque<String>>,
+
+    /// Selected crosshairs for back and forward navigation
+    crosshair_history: RwLock<CrosshairHistory>,
+
+    /// Positions the window moved away from, most recent last
+    position_history: RwLock<VecDeque<(i32, i32)>>,
+
+    /// Positions undone, most recent last
+    position_redo: RwLock<Vec<(i32, i32)>>,
+
+    /// Whether the next move comes from undo/redo and should not be recorded
+    restoring_position: AtomicBool,
+
+    /// Per-command call counts for the current one-second window
+    rate_limit: RwLock<HashMap<String, (Instant, u32)>>,
+
+    /// Parse failure from startup, reported once the frontend is loaded
+    preferences_corrupt: Mutex<Option<PreferencesCorrupt>>,
+
+    /// Unclean shutdown detected at startup, reported once the frontend is loaded
+    crash_recovery: Mutex<Option<CrashRecovery>>,
+}
+
+impl Default for AppState {
+    fn default() -> Self {
+        Self {
+            preferences: RwLock::new(Preferences::default()),
+            on_preference_changed: RwLock::new(Vec::new()),
+            shadow_windows: RwLock::new(HashSet::new()),
+            shadow_counter: RwLock::new(0),
+            setup_complete_windows: RwLock::new(HashSet::new()),
+            shadow_options: RwLock::new(HashMap::new()),
+            mouse_following_active: RwLock::new(false),
+            mouse_follow_error: RwLock::new(None),
+            ipc_server: Mutex::new(None),
+            known_displays: RwLock::new(Vec::new()),
+            portable_mode: false,
+            dirty: AtomicBool::new(false),
+            snapshot_dirty: AtomicBool::new(true),
+            start_on_boot_unset: AtomicBool::new(false),
+            session_overrides: Mutex::new(None),
+            registered_hotkeys: RwLock::new(HashMap::new()),
+            move_save_deadline: Mutex::new(None),
+            last_backup: Mutex::new(None),
+            svg_cache: RwLock::new(HashMap::new()),
+            crosshair_cache: Mutex::new(CrosshairCache::new(CROSSHAIR_CACHE_MAX_BYTES)),
+            preview_cache: Mutex::new(PreviewCache::new(PREVIEW_CACHE_SIZE)),
+            metadata_cache: RwLock::new(HashMap::new()),
+            thumbnail_cache: Mutex::new(ThumbnailCache::new(THUMBNAIL_CACHE_SIZE)),
+            blink_task: Mutex::new(None),
+            safe_mode: false,
+            headless: false,
+            single_instance: false,
+            startup_args: StartupArgs::default(),
+            tray_id: "main_tray".to_string(),
+            tray_icon_id: RwLock::new(None),
+            tray_lock_item_id: "toggle_lock".to_string(),
+            tray_hide_item_id: "hide".to_string(),
+            tray_menu: Mutex::new(None),
+            recent_crosshairs: RwLock::new(VecDeque::new()),
+            crosshair_history: RwLock::new(CrosshairHistory::default()),
+            position_history: RwLock::new(VecDeque::new()),
+            position_redo: RwLock::new(Vec::new()),
+            restoring_position: AtomicBool::new(false),
+            rate_limit: RwLock::new(HashMap::new()),
+            preferences_corrupt: Mutex::new(None),
+            crash_recovery: Mutex::new(None),
+        }
+    }
+}
+
+/// Get the directory containing the executable, used as the portable data root
+pub fn portable_dir() -> Result<PathBuf, String> {
+    let exe = std::env::current_exe().map_err(|e| format!("Failed to get executable: {}", e))?;
+    exe.parent()
+        .map(|p| p.to_path_buf())
+        .ok_or_else(|| "Executable has no parent directory".to_string())
+}
+
+impl AppState {
+    /// Create a new AppState with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new AppState, optionally in portable mode
+    pub fn with_portable_mode(portable_mode: bool) -> Self {
+        Self {
+            portable_mode,
+            ..Self::default()
+        }
+    }
+
+    /// Ignore saved preferences and never overwrite them this session
+    pub fn with_safe_mode(self, safe_mode: bool) -> Self {
+        Self { safe_mode, ..self }
+    }
+
+    /// Run without a visible overlay window
+    pub fn with_headless(self, headless: bool) -> Self {
+        Self { headless, ..self }
+    }
+
+    /// Record whether this process holds the single instance lock
+    pub fn with_single_instance(self, single_instance: bool) -> Self {
+        Self {
+            single_instance,
+            ..self
+        }
+    }
+
+    /// Record the command line overrides for this launch
+    pub fn with_startup_args(self, startup_args: StartupArgs) -> Self {
+        Self {
+            startup_args,
+            ..self
+        }
+    }
+
+    /// Record a command call, returning false if it exceeds `max_per_second`
+    pub fn check_rate_limit(&self, command: &str, max_per_second: u32) -> bool {
+        let now = Instant::now();
+        let mut limits = self.rate_limit.write();
+        let (window_start, count) = limits.entry(command.to_string()).or_insert((now, 0));
+
+        if now.duration_since(*window_start) >= Duration::from_secs(1) {
+            *window_start = now;
+            *count = 0;
+        }
+
+        if *count >= max_per_second {
+            log::debug!("Rate limit exceeded for {}", command);
+            return false;
+        }
+
+        *count += 1;
+        true
+    }
+
+    /// Get call counts in the current window for each rate-limited command
+    pub fn get_rate_limit_stats(&self) -> HashMap<String, u32> {
+        let now = Instant::now();
+        self.rate_limit
+            .read()
+            .iter()
+            .map(|(command, (window_start, count))| {
+                let active = now.duration_since(*window_start) < Duration::from_secs(1);
+                (command.clone(), if active { *count } else { 0 })
+            })
+            .collect()
+    }
+
+    /// Take the startup parse failure, if any
+    pub fn take_preferences_corrupt(&self) -> Option<PreferencesCorrupt> {
+        self.preferences_corrupt.lock().take()
+    }
+
+    /// Take the unclean shutdown report, if any
+    pub fn take_crash_recovery(&self) -> Option<CrashRecovery> {
+        self.crash_recovery.lock().take()
+    }
+
+    /// Get write access to the preferences, marking them as unsaved
+    ///
+    /// Preference listeners run when the returned guard is dropped.
+    pub fn preferences_mut(&self) -> PreferencesGuard<'_> {
+        PreferencesGuard {
+            guard: Some(self.preferences.write()),
+            listeners: &self.on_preference_changed,
+            dirty: &self.dirty,
+            snapshot_dirty: &self.snapshot_dirty,
+        }
+    }
+
+    /// Run a callback after every preferences change
+    pub fn register_preference_listener(
+        &self,
+        listener: impl Fn(&Preferences) + Send + Sync + 'static,
+    ) {
+        self.on_preference_changed.write().push(Box::new(listener));
+    }
+
+    /// Mark preferences as changed since the last save
+    pub fn mark_dirty(&self) {
+        self.dirty.store(true, Ordering::SeqCst);
+        self.snapshot_dirty.store(true, Ordering::SeqCst);
+    }
+
+    /// Clear the unsaved flag, returning whether it was set
+    pub fn take_dirty(&self) -> bool {
+        self.dirty.swap(false, Ordering::SeqCst)
+    }
+
+    /// Clear the flag for changes since the last crash recovery snapshot,
+    /// returning whether it was set
+    ///
+    /// Unlike [`take_dirty`](Self::take_dirty), this is set even when
+    /// auto-save is off, so the snapshot keeps up with every change.
+    pub fn take_snapshot_dirty(&self) -> bool {
+        self.snapshot_dirty.swap(false, Ordering::SeqCst)
+    }
+
+    /// Check if auto-save is enabled
+    pub fn get_auto_save(&self) -> bool {
+        self.preferences.read().auto_save
+    }
+
+    /// Set auto-save state
+    pub fn set_auto_save(&self, enabled: bool) {
+        self.preferences_mut().auto_save = enabled;
+    }
+
+    /// Get the path of the preferences store
+    ///
+    /// In portable mode this is next to the executable, otherwise it is in
+    /// the app data directory.
+    fn store_path(&self, app: &AppHandle) -> Result<PathBuf, String> {
+        let dir = if self.portable_mode {
+            portable_dir()?
+        } else {
+            app.path()
+                .app_data_dir()
+                .map_err(|e| format!("Failed to get app data directory: {}", e))?
+        };
+        Ok(dir.join(STORE_FILENAME))
+    }
+
+    /// Get the current crosshair
+    pub fn get_crosshair(&self) -> String {
+        self.preferences.read().crosshair.clone()
+    }
+
+    /// Set the current crosshair, returning true if the recents list changed
+    pub fn set_crosshair(&self, crosshair: String) -> bool {
+        let changed = self.add_recent_crosshair(&crosshair);
+        self.push_crosshair_history(&crosshair);
+        self.preferences_mut().crosshair = crosshair;
+        changed
+    }
+
+    /// Record a crosshair selection, seeding the history with the current one
+    fn push_crosshair_history(&self, crosshair: &str) {
+        let current = self.get_crosshair();
+        let mut history = self.crosshair_history.write();
+        if history.current().is_none() {
+            history.push(&current);
+        }
+        history.push(crosshair);
+    }
+
+    /// Mark a crosshair as a favorite, returning false if it already was
+    pub fn add_crosshair_favorite(&self, filename: &str) -> bool {
+        self.preferences_mut()
+            .favorites
+            .insert(filename.to_string())
+    }
+
+    /// Unmark a favorite crosshair, returning true if it was one
+    pub fn remove_crosshair_favorite(&self, filename: &str) -> bool {
+        self.preferences_mut().favorites.remove(filename)
+    }
+
+    /// Flip whether a crosshair is a favorite, returning the new state
+    pub fn toggle_crosshair_favorite(&self, filename: &str) -> bool {
+        if self.remove_crosshair_favorite(filename) {
+            false
+        } else {
+            self.add_crosshair_favorite(filename)
+        }
+    }
+
+    /// Check if a crosshair is a favorite
+    pub fn is_crosshair_favorite(&self, filename: &str) -> bool {
+        self.preferences.read().favorites.contains(filename)
+    }
+
+    /// Get the favorite crosshairs sorted by filename
+    pub fn get_crosshair_favorites(&self) -> Vec<String> {
+        let mut favorites: Vec<String> =
+            self.preferences.read().favorites.iter().cloned().collect();
+        favorites.sort();
+        favorites
+    }
+
+    /// Get the crosshair history, oldest first
+    pub fn get_crosshair_history(&self) -> Vec<String> {
+        self.crosshair_history.read().entries()
+    }
+
+    /// Step back in the crosshair history
+    pub fn prev_crosshair_from_history(&self) -> Option<String> {
+        self.crosshair_history.write().back()
+    }
+
+    /// Step forward in the crosshair history
+    pub fn next_crosshair_from_history(&self) -> Option<String> {
+        self.crosshair_history.write().forward()
+    }
+
+    /// Move a crosshair to the front of the recents list
+    fn add_recent_crosshair(&self, crosshair: &str) -> bool {
+        let mut recents = self.recent_crosshairs.write();
+        if recents.front().map(String::as_str) == Some(crosshair) {
+            return false;
+        }
+
+        recents.retain(|c| c != crosshair);
+        recents.push_front(crosshair.to_string());
+        recents.truncate(MAX_RECENT_CROSSHAIRS);
+        true
+    }
+
+    /// Get recently selected crosshairs, most recent first
+    pub fn get_recent_crosshairs(&self) -> Vec<String> {
+        self.recent_crosshairs.read().iter().cloned().collect()
+    }
+
+    /// Get the current size
+    pub fn get_size(&self) -> u32 {
+        self.preferences.read().size
+    }
+
+    /// Set the current size
+    pub fn set_size(&self, size: u32) {
+        self.preferences_mut().size = size;
+    }
+
+    /// Get the current opacity
+    pub fn get_opacity(&self) -> f64 {
+        self.preferences.read().opacity
+    }
+
+    /// Set the current opacity
+    pub fn set_opacity(&self, opacity: f64) {
+        self.preferences_mut().opacity = opacity.clamp(0.0, 1.0);
+    }
+
+    /// Get the current color
+    pub fn get_color(&self) -> String {
+        self.preferences.read().color.clone()
+    }
+
+    /// Set the current color
+    pub fn set_color(&self, color: String) {
+        self.preferences_mut().color = color;
+    }
+
+    /// Get the color format shown by the settings color picker
+    pub fn get_color_format(&self) -> String {
+        self.preferences.read().color_format.clone()
+    }
+
+    /// Set the color format shown by the settings color picker
+    pub fn set_color_format(&self, format: String) {
+        self.preferences_mut().color_format = format;
+    }
+
+    /// Check if window is locked (click-through)
+    pub fn is_locked(&self) -> bool {
+        self.preferences.read().locked
+    }
+
+    /// Check if the window position is locked
+    pub fn is_position_locked(&self) -> bool {
+        self.preferences.read().lock_mode.is_position_locked()
+    }
+
+    /// Set the locked state
+    pub fn set_locked(&self, locked: bool) {
+        self.set_lock_mode(if locked {
+            LockMode::Locked
+        } else {
+            LockMode::Unlocked
+        });
+    }
+
+    /// Toggle between unlocked and fully locked
+    pub fn toggle_locked(&self) -> bool {
+        let mut prefs = self.preferences_mut();
+        prefs.lock_mode = if prefs.lock_mode.is_click_through() {
+            LockMode::Unlocked
+        } else {
+            LockMode::Locked
+        };
+        prefs.locked = prefs.lock_mode.is_click_through();
+        prefs.locked
+    }
+
+    /// Get the lock mode
+    pub fn get_lock_mode(&self) -> LockMode {
+        self.preferences.read().lock_mode
+    }
+
+    /// Set the lock mode
+    pub fn set_lock_mode(&self, mode: LockMode) {
+        let mut prefs = self.preferences_mut();
+        prefs.lock_mode = mode;
+        prefs.locked = mode.is_click_through();
+    }
+
+    /// Check if window is visible
+    pub fn is_visible(&self) -> bool {
+        self.preferences.read().visible
+    }
+
+    /// Set the visibility state
+    pub fn set_visible(&self, visible: bool) {
+        self.preferences_mut().visible = visible;
+    }
+
+    /// Toggle the visibility state
+    pub fn toggle_visible(&self) -> bool {
+        let mut prefs = self.preferences_mut();
+        prefs.visible = !prefs.visible;
+        prefs.visible
+    }
+
+    /// Check if follow mouse is enabled
+    pub fn get_follow_mouse(&self) -> bool {
+        self.preferences.read().follow_mouse
+    }
+
+    /// Set follow mouse state
+    pub fn set_follow_mouse(&self, follow: bool) {
+        self.preferences_mut().follow_mouse = follow;
+    }
+
+    /// Get the mouse following configuration
+    pub fn get_follow_mouse_config(&self) -> FollowMouseConfig {
+        self.preferences.read().follow_mouse_config
+    }
+
+    /// Set the mouse following configuration
+    pub fn set_follow_mouse_config(&self, mut config: FollowMouseConfig) {
+        config.smoothing = config.smoothing.clamp(0.0, 1.0);
+        self.preferences_mut().follow_mouse_config = config;
+    }
+
+    /// Check if hide on ADS is enabled
+    pub fn get_hide_on_ads(&self) -> bool {
+        self.preferences.read().hide_on_ads
+    }
+
+    /// Set hide on ADS state
+    pub fn set_hide_on_ads(&self, hide: bool) {
+        self.preferences_mut().hide_on_ads = hide;
+    }
+
+    /// Get the mouse button index used for ADS detection
+    pub fn get_ads_button(&self) -> u8 {
+        self.preferences.read().ads_button
+    }
+
+    /// Get the delay before hiding on ADS
+    pub fn get_hide_on_ads_delay_ms(&self) -> u64 {
+        self.preferences.read().hide_on_ads_delay_ms
+    }
+
+    /// Set the delay before hiding on ADS, capped at the maximum
+    pub fn set_hide_on_ads_delay_ms(&self, delay_ms: u64) {
+        self.preferences_mut().hide_on_ads_delay_ms = delay_ms.min(MAX_HIDE_ON_ADS_DELAY_MS);
+    }
+
+    /// Check if scroll wheel opacity adjustment is enabled
+    pub fn get_scroll_wheel_opacity(&self) -> bool {
+        self.preferences.read().scroll_wheel_opacity
+    }
+
+    /// Set scroll wheel opacity adjustment
+    pub fn set_scroll_wheel_opacity(&self, enabled: bool) {
+        self.preferences_mut().scroll_wheel_opacity = enabled;
+    }
+
+    /// Check if the window stays square when resized
+    pub fn get_lock_aspect_ratio(&self) -> bool {
+        self.preferences.read().lock_aspect_ratio
+    }
+
+    /// Get the keyboard move step in pixels
+    pub fn get_move_step_px(&self) -> u32 {
+        self.preferences.read().move_step_px
+    }
+
+    /// Set the keyboard move step in pixels
+    pub fn set_move_step_px(&self, step: u32) {
+        self.preferences_mut().move_step_px = step.clamp(MIN_MOVE_STEP_PX, MAX_MOVE_STEP_PX);
+    }
+
+    /// Get the fast keyboard move step in pixels
+    pub fn get_fast_move_step_px(&self) -> u32 {
+        self.preferences.read().fast_move_step_px
+    }
+
+    /// Set the fast keyboard move step in pixels
+    pub fn set_fast_move_step_px(&self, step: u32) {
+        self.preferences_mut().fast_move_step_px = step.clamp(MIN_MOVE_STEP_PX, MAX_MOVE_STEP_PX);
+    }
+
+    /// Check if shadow windows follow the main window back to the first monitor
+    pub fn get_sync_shadows_to_main_monitor(&self) -> bool {
+        self.preferences.read().sync_shadows_to_main_monitor
+    }
+
+    /// Set whether shadow windows follow the main window back to the first monitor
+    pub fn set_sync_shadows_to_main_monitor(&self, enabled: bool) {
+        self.preferences_mut().sync_shadows_to_main_monitor = enabled;
+    }
+
+    /// Check if moves keep the window on screen
+    pub fn get_clamp_to_monitor(&self) -> bool {
+        self.preferences.read().clamp_to_monitor
+    }
+
+    /// Set whether moves keep the window on screen
+    pub fn set_clamp_to_monitor(&self, enabled: bool) {
+        self.preferences_mut().clamp_to_monitor = enabled;
+    }
+
+    /// Check if the window follows the crosshair size
+    pub fn get_auto_resize_window(&self) -> bool {
+        self.preferences.read().auto_resize_window
+    }
+
+    /// Check if sound feedback is turned off
+    pub fn get_disable_sounds(&self) -> bool {
+        self.preferences.read().disable_sounds
+    }
+
+    /// Turn sound feedback off or on
+    pub fn set_disable_sounds(&self, disabled: bool) {
+        self.preferences_mut().disable_sounds = disabled;
+    }
+
+    /// Get the sound feedback volume
+    pub fn get_sound_volume(&self) -> f64 {
+        self.preferences.read().sound_volume
+    }
+
+    /// Set the sound feedback volume, clamped to 0.0-1.0
+    pub fn set_sound_volume(&self, volume: f64) {
+        self.preferences_mut().sound_volume = volume.clamp(0.0, 1.0);
+    }
+
+    /// Get custom sound file paths keyed by sound name
+    pub fn get_custom_sounds(&self) -> HashMap<String, String> {
+        self.preferences.read().custom_sounds.clone()
+    }
+
+    /// Play a custom file for a sound
+    pub fn set_custom_sound(&self, event: String, path: String) {
+        self.preferences_mut().custom_sounds.insert(event, path);
+    }
+
+    /// Go back to the bundled sound, returning true if a custom one was set
+    pub fn remove_custom_sound(&self, event: &str) -> bool {
+        self.preferences_mut().custom_sounds.remove(event).is_some()
+    }
+
+    /// Get the mouse following frame rate
+    pub fn get_mouse_follow_fps(&self) -> u32 {
+        self.preferences.read().mouse_follow_fps
+    }
+
+    /// Set the mouse following frame rate, clamped to the supported range
+    pub fn set_mouse_follow_fps(&self, fps: u32) {
+        self.preferences_mut().mouse_follow_fps =
+            fps.clamp(MIN_MOUSE_FOLLOW_FPS, MAX_MOUSE_FOLLOW_FPS);
+    }
+
+    /// Get reticle type
+    pub fn get_reticle(&self) -> String {
+        self.preferences.read().reticle.clone()
+    }
+
+    /// Set reticle type
+    pub fn set_reticle(&self, reticle: String) {
+        self.preferences_mut().reticle = reticle;
+    }
+
+    /// Get the crosshair assigned to a monitor
+    pub fn get_monitor_crosshair(&self, monitor_name: &str) -> Option<String> {
+        self.preferences
+            .read()
+            .per_monitor_crosshair
+            .get(monitor_name)
+            .cloned()
+    }
+
+    /// Assign a crosshair to a monitor
+    pub fn set_monitor_crosshair(&self, monitor_name: String, crosshair: String) {
+        self.preferences_mut()
+            .per_monitor_crosshair
+            .insert(monitor_name, crosshair);
+    }
+
+    /// Remove a monitor's crosshair, returning whether one was assigned
+    pub fn clear_monitor_crosshair(&self, monitor_name: &str) -> bool {
+        self.preferences_mut()
+            .per_monitor_crosshair
+            .remove(monitor_name)
+            .is_some()
+    }
+
+    /// Check if imported crosshairs are converted to PNG
+    pub fn get_normalize_on_import(&self) -> bool {
+        self.preferences.read().normalize_on_import
+    }
+
+    /// Check if the app should launch at login
+    pub fn get_start_on_boot(&self) -> bool {
+        self.preferences.read().start_on_boot
+    }
+
+    /// Set whether the app should launch at login
+    pub fn set_start_on_boot(&self, enabled: bool) {
+        self.preferences_mut().start_on_boot = enabled;
+    }
+
+    /// Whether the loaded preferences had no `start_on_boot` value yet,
+    /// clearing the flag
+    ///
+    /// Installs that toggled autostart through the plugin directly never
+    /// stored the preference, so it should be seeded from the OS instead.
+    pub fn take_start_on_boot_unset(&self) -> bool {
+        self.start_on_boot_unset.swap(false, Ordering::SeqCst)
+    }
+
+    /// Check if toggling the lock saves preferences immediately
+    pub fn get_save_on_lock(&self) -> bool {
+        self.preferences.read().save_on_lock
+    }
+
+    /// Check if auto-centering on display changes is enabled
+    pub fn get_auto_center_on_display_change(&self) -> bool {
+        self.preferences.read().auto_center_on_display_change
+    }
+
+    /// Set auto-centering on display changes
+    pub fn set_auto_center_on_display_change(&self, enabled: bool) {
+        self.preferences_mut().auto_center_on_display_change = enabled;
+    }
+
+    /// Replace the known display layout, returning true if it changed
+    ///
+    /// The first call only records the baseline and reports no change.
+    pub fn update_known_displays(&self, displays: Vec<String>) -> bool {
+        let mut known = self.known_displays.write();
+        let changed = !known.is_empty() && *known != displays;
+        *known = displays;
+        changed
+    }
+
+    /// Get the crosshair rotation in degrees
+    pub fn get_rotation(&self) -> f64 {
+        self.preferences.read().rotation_degrees
+    }
+
+    /// Set the crosshair rotation, clamped to 0 - 360 degrees
+    pub fn set_rotation(&self, degrees: f64) {
+        self.preferences_mut().rotation_degrees = degrees.clamp(0.0, 360.0);
+    }
+
+    /// Rotate the crosshair by a relative amount, wrapping around 360 degrees
+    pub fn rotate_by(&self, delta: f64) -> f64 {
+        let mut prefs = self.preferences_mut();
+        prefs.rotation_degrees = (prefs.rotation_degrees + delta).rem_euclid(360.0);
+        prefs.rotation_degrees
+    }
+
+    /// Get the flip state
+    pub fn get_flip(&self) -> FlipState {
+        self.preferences.read().flip()
+    }
+
+    /// Set horizontal flip
+    pub fn set_flip_horizontal(&self, flip: bool) {
+        self.preferences_mut().flip_horizontal = flip;
+    }
+
+    /// Set vertical flip
+    pub fn set_flip_vertical(&self, flip: bool) {
+        self.preferences_mut().flip_vertical = flip;
+    }
+
+    /// Toggle horizontal flip
+    pub fn toggle_flip_horizontal(&self) -> bool {
+        let mut prefs = self.preferences_mut();
+        prefs.flip_horizontal = !prefs.flip_horizontal;
+        prefs.flip_horizontal
+    }
+
+    /// Get the drop shadow and outline settings
+    pub fn get_crosshair_effect(&self) -> CrosshairEffect {
+        self.preferences.read().crosshair_effect()
+    }
+
+    /// Enable or disable the crosshair drop shadow
+    pub fn set_shadow_enabled(&self, enabled: bool) {
+        self.preferences_mut().shadow_enabled = enabled;
+    }
+
+    /// Set the drop shadow blur radius
+    pub fn set_shadow_blur(&self, blur: u32) -> Result<(), String> {
+        if blur > MAX_SHADOW_BLUR {
+            return Err(format!(
+                "Shadow blur {} exceeds the maximum of {}",
+                blur, MAX_SHADOW_BLUR
+            ));
+        }
+        self.preferences_mut().shadow_blur = blur;
+        Ok(())
+    }
+
+    /// Set the drop shadow color
+    pub fn set_shadow_color(&self, color: &str) -> Result<(), String> {
+        let color = crate::color::validate_color(color)?;
+        self.preferences_mut().shadow_color = color;
+        Ok(())
+    }
+
+    /// Enable or disable the crosshair outline
+    pub fn set_outline_enabled(&self, enabled: bool) {
+        self.preferences_mut().outline_enabled = enabled;
+    }
+
+    /// Set the outline width
+    pub fn set_outline_width(&self, width: u32) -> Result<(), String> {
+        if !(MIN_OUTLINE_WIDTH..=MAX_OUTLINE_WIDTH).contains(&width) {
+            return Err(format!(
+                "Outline width must be between {} and {}",
+                MIN_OUTLINE_WIDTH, MAX_OUTLINE_WIDTH
+            ));
+        }
+        self.preferences_mut().outline_width = width;
+        Ok(())
+    }
+
+    /// Set the outline color
+    pub fn set_outline_color(&self, color: &str) -> Result<(), String> {
+        let color = crate::color::validate_color(color)?;
+        self.preferences_mut().outline_color = color;
+        Ok(())
+    }
+
+    /// Get the crosshair offset from the window center
+    pub fn get_crosshair_offset(&self) -> CrosshairOffset {
+        self.preferences.read().crosshair_offset()
+    }
+
+    /// Set the crosshair offset from the window center
+    pub fn set_crosshair_offset(&self, offset: CrosshairOffset) {
+        let mut prefs = self.preferences_mut();
+        prefs.crosshair_offset_x = offset.x;
+        prefs.crosshair_offset_y = offset.y;
+        prefs.clamp_crosshair_offset();
+    }
+
+    /// Get the fade duration in milliseconds
+    pub fn get_fade_duration(&self) -> u64 {
+        self.preferences.read().fade_duration_ms
+    }
+
+    /// Set the fade duration in milliseconds
+    pub fn set_fade_duration(&self, duration_ms: u64) {
+        self.preferences_mut().fade_duration_ms = duration_ms;
+    }
+
+    /// Check if blink mode is enabled
+    pub fn get_blink_enabled(&self) -> bool {
+        self.preferences.read().blink_enabled
+    }
+
+    /// Set blink mode
+    pub fn set_blink_enabled(&self, enabled: bool) {
+        self.preferences_mut().blink_enabled = enabled;
+    }
+
+    /// Get the blink interval in milliseconds
+    pub fn get_blink_interval(&self) -> u64 {
+        self.preferences.read().blink_interval_ms
+    }
+
+    /// Set the blink interval in milliseconds
+    pub fn set_blink_interval(&self, interval_ms: u64) {
+        self.preferences_mut().blink_interval_ms = interval_ms.max(MIN_BLINK_INTERVAL_MS);
+    }
+
+    /// Get the built-in reticle settings
+    pub fn get_reticle_config(&self) -> ReticleConfig {
+        self.preferences.read().reticle_config()
+    }
+
+    /// Set the reticle size, which must be at least the thickness
+    pub fn set_reticle_size(&self, size: u32) -> Result<(), String> {
+        // Validate first so a rejected value neither marks the preferences
+        // unsaved nor notifies listeners
+        validate_reticle_dimensions(size, self.preferences.read().reticle_thickness)?;
+        self.preferences_mut().reticle_size = size;
+        Ok(())
+    }
+
+    /// Set the reticle thickness, which must not exceed the size
+    pub fn set_reticle_thickness(&self, thickness: u32) -> Result<(), String> {
+        validate_reticle_dimensions(self.preferences.read().reticle_size, thickness)?;
+        self.preferences_mut().reticle_thickness = thickness;
+        Ok(())
+    }
+
+    /// Set the reticle center gap
+    pub fn set_reticle_gap(&self, gap: u32) {
+        self.preferences_mut().reticle_gap = gap;
+    }
+
+    /// Get the UI theme
+    pub fn get_theme(&self) -> Theme {
+        self.preferences.read().theme
+    }
+
+    /// Set the UI theme
+    pub fn set_theme(&self, theme: Theme) {
+        self.preferences_mut().theme = theme;
+    }
+
+    /// Get the overlay window level
+    pub fn get_window_level(&self) -> u8 {
+        self.preferences.read().window_level
+    }
+
+    /// Set the overlay window level
+    pub fn set_window_level(&self, level: u8) -> Result<(), String> {
+        if level > WINDOW_LEVEL_SCREEN_SAVER {
+            return Err(format!(
+                "Window level must be between 0 and {}",
+                WINDOW_LEVEL_SCREEN_SAVER
+            ));
+        }
+        self.preferences_mut().window_level = level;
+        Ok(())
+    }
+
+    /// Check if DirectX overlay mode is enabled
+    pub fn get_use_dx_overlay(&self) -> bool {
+        self.preferences.read().use_dx_overlay
+    }
+
+    /// Set whether DirectX overlay mode is enabled
+    pub fn set_use_dx_overlay(&self, enabled: bool) {
+        self.preferences_mut().use_dx_overlay = enabled;
+    }
+
+    /// Save the current appearance as a named profile
+    pub fn save_profile(&self, name: &str) {
+        let mut prefs = self.preferences_mut();
+        let profile = prefs.profile_snapshot();
+        prefs.profiles.insert(name.to_string(), profile);
+        prefs.active_profile = Some(name.to_string());
+    }
+
+    /// Load a named profile
+    pub fn load_profile(&self, name: &str) -> Result<(), String> {
+        let mut prefs = self.preferences_mut();
+        let profile = prefs
+            .profiles
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("Profile not found: {}", name))?;
+        prefs.apply_profile(&profile);
+        prefs.active_profile = Some(name.to_string());
+        Ok(())
+    }
+
+    /// Apply an unnamed profile snapshot and set the active profile name
+    pub fn restore_profile(&self, profile: &CrosshairProfile, active: Option<String>) {
+        let mut prefs = self.preferences_mut();
+        prefs.apply_profile(profile);
+        prefs.active_profile = active;
+    }
+
+    /// Get the name of the active profile
+    pub fn get_active_profile(&self) -> Option<String> {
+        self.preferences.read().active_profile.clone()
+    }
+
+    /// Get all profile names, sorted
+    pub fn list_profiles(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.preferences.read().profiles.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Get the current appearance as a profile snapshot
+    pub fn profile_snapshot(&self) -> CrosshairProfile {
+        self.preferences.read().profile_snapshot()
+    }
+
+    /// Add a process rule, replacing any rule for the same process
+    pub fn add_process_rule(&self, rule: ProcessRule) {
+        let mut prefs = self.preferences_mut();
+        prefs
+            .process_rules
+            .retain(|r| !r.process_name.eq_ignore_ascii_case(&rule.process_name));
+        prefs.process_rules.push(rule);
+    }
+
+    /// Remove the rule for a process, returning true if one existed
+    pub fn remove_process_rule(&self, process_name: &str) -> bool {
+        let mut prefs = self.preferences_mut();
+        let before = prefs.process_rules.len();
+        prefs
+            .process_rules
+            .retain(|r| !r.process_name.eq_ignore_ascii_case(process_name));
+        prefs.process_rules.len() != before
+    }
+
+    /// Get all process rules
+    pub fn get_process_rules(&self) -> Vec<ProcessRule> {
+        self.preferences.read().process_rules.clone()
+    }
+
+    /// Add a process that hides the crosshair, returning false if already listed
+    pub fn add_hide_process(&self, name: &str) -> bool {
+        let mut prefs = self.preferences_mut();
+        if prefs
+            .hide_for_processes
+            .iter()
+            .any(|p| p.eq_ignore_ascii_case(name))
+        {
+            return false;
+        }
+        prefs.hide_for_processes.push(name.to_string());
+        true
+    }
+
+    /// Remove a process from the hide list, returning true if it was listed
+    pub fn remove_hide_process(&self, name: &str) -> bool {
+        let mut prefs = self.preferences_mut();
+        let before = prefs.hide_for_processes.len();
+        prefs
+            .hide_for_processes
+            .retain(|p| !p.eq_ignore_ascii_case(name));
+        prefs.hide_for_processes.len() != before
+    }
+
+    /// Get the processes that hide the crosshair while focused
+    pub fn get_hide_processes(&self) -> Vec<String> {
+        self.preferences.read().hide_for_processes.clone()
+    }
+
+    /// Get saved position
+    pub fn get_position(&self) -> (Option<i32>, Option<i32>) {
+        let prefs = self.preferences.read();
+        (prefs.position_x, prefs.position_y)
+    }
+
+    /// Set saved position
+    pub fn set_position(&self, x: i32, y: i32) {
+        let mut prefs = self.preferences_mut();
+        prefs.position_x = Some(x);
+        prefs.position_y = Some(y);
+    }
+
+    /// Record a window move and push back the debounced position save
+    ///
+    /// The position before a series of moves is kept for undo.
+    pub fn record_move(&self, x: i32, y: i32) {
+        let starting = !self.is_move_pending();
+        if starting && !self.restoring_position.swap(false, Ordering::SeqCst) {
+            if let (Some(old_x), Some(old_y)) = self.get_position() {
+                if (old_x, old_y) != (x, y) {
+                    self.push_position_history((old_x, old_y));
+                    self.position_redo.write().clear();
+                }
+            }
+        }
+
+        self.set_position(x, y);
+        *self.move_save_deadline.lock() =
+            Some(Instant::now() + Duration::from_millis(SAVE_DEBOUNCE_MS));
+    }
+
+    /// Remember a position to return to on undo
+    fn push_position_history(&self, position: (i32, i32)) {
+        let mut history = self.position_history.write();
+        history.push_back(position);
+        while history.len() > MAX_POSITION_HISTORY {
+            history.pop_front();
+        }
+    }
+
+    /// Take the position to undo to, remembering `current` for redo
+    pub fn undo_position(&self, current: (i32, i32)) -> Option<(i32, i32)> {
+        let position = self.position_history.write().pop_back()?;
+        self.position_redo.write().push(current);
+        self.restoring_position.store(true, Ordering::SeqCst);
+        Some(position)
+    }
+
+    /// Take the position to redo to, remembering `current` for undo
+    pub fn redo_position(&self, current: (i32, i32)) -> Option<(i32, i32)> {
+        let position = self.position_redo.write().pop()?;
+        self.push_position_history(current);
+        self.restoring_position.store(true, Ordering::SeqCst);
+        Some(position)
+    }
+
+    /// Check if the window moved within the debounce interval
+    pub fn is_move_pending(&self) -> bool {
+        matches!(*self.move_save_deadline.lock(), Some(deadline) if Instant::now() < deadline)
+    }
+
+    /// Clear the debounced position save, returning true if it was due
+    pub fn take_settled_move(&self) -> bool {
+        let mut deadline = self.move_save_deadline.lock();
+        match *deadline {
+            Some(at) if Instant::now() >= at => {
+                *deadline = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Generate a new shadow window ID
+    ///
+    /// Marks the preferences unsaved so the counter is persisted with them.
+    pub fn next_shadow_id(&self) -> String {
+        let id = {
+            let mut counter = self.shadow_counter.write();
+            *counter += 1;
+            *counter
+        };
+        self.mark_dirty();
+        format!("shadow-{}", id)
+    }
+
+    /// Get the last shadow window ID number handed out
+    pub fn shadow_counter(&self) -> u64 {
+        *self.shadow_counter.read()
+    }
+
+    /// Continue shadow window IDs from a stored counter
+    ///
+    /// The counter never goes backwards, so IDs already handed out this
+    /// session are not reused.
+    pub fn restore_shadow_counter(&self, value: u64) {
+        let mut counter = self.shadow_counter.write();
+        *counter = (*counter).max(value);
+    }
+
+    /// Add a shadow window
+    pub fn add_shadow_window(&self, label: String) {
+        self.shadow_windows.write().insert(label);
+    }
+
+    /// Remove a shadow window
+    pub fn remove_shadow_window(&self, label: &str) {
+        self.shadow_windows.write().remove(label);
+        self.shadow_options.write().remove(label);
+        self.setup_complete_windows.write().remove(label);
+    }
+
+    /// Check if overlay setup has already been applied to a window
+    pub fn is_window_setup_complete(&self, label: &str) -> bool {
+        self.setup_complete_windows.read().contains(label)
+    }
+
+    /// Record that overlay setup has been applied to a window
+    pub fn mark_window_setup_complete(&self, label: &str) {
+        self.setup_complete_windows
+            .write()
+            .insert(label.to_string());
+    }
+
+    /// Store the setting overrides a shadow window was created with
+    pub fn set_shadow_options(&self, label: &str, options: ShadowWindowOptions) {
+        self.shadow_options
+            .write()
+            .insert(label.to_string(), options);
+    }
+
+    /// Get the setting overrides for a shadow window
+    pub fn get_shadow_options(&self, label: &str) -> ShadowWindowOptions {
+        self.shadow_options
+            .read()
+            .get(label)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Get all shadow window labels
+    pub fn get_shadow_windows(&self) -> Vec<String> {
+        self.shadow_windows.read().iter().cloned().collect()
+    }
+
+    /// Get the maximum number of shadow windows
+    pub fn get_max_shadow_windows(&self) -> usize {
+        self.preferences.read().max_shadow_windows
+    }
+
+    /// Set the maximum number of shadow windows
+    pub fn set_max_shadow_windows(&self, max: usize) -> Result<(), String> {
+        if max > MAX_SHADOW_WINDOWS {
+            return Err(format!(
+                "Maximum shadow windows cannot exceed {}",
+                MAX_SHADOW_WINDOWS
+            ));
+        }
+        self.preferences_mut().max_shadow_windows = max;
+        Ok(())
+    }
+
+    /// Get shadow window count
+    pub fn shadow_window_count(&self) -> usize {
+        self.shadow_windows.read().len()
+    }
+
+    /// Clear all shadow windows
+    pub fn clear_shadow_windows(&self) {
+        let mut setup_complete = self.setup_complete_windows.write();
+        for label in self.shadow_windows.write().drain() {
+            setup_complete.remove(&label);
+        }
+        self.shadow_options.write().clear();
+    }
+
+    /// Record a successfully registered shortcut for an action
+    pub fn set_registered_hotkey(&self, action: &str, shortcut: &str) {
+        self.registered_hotkeys
+            .write()
+            .insert(action.to_string(), shortcut.to_string());
+    }
+
+    /// Forget all registered shortcuts
+    pub fn clear_registered_hotkeys(&self) {
+        self.registered_hotkeys.write().clear();
+    }
+
+    /// Get why the mouse listener failed, if it did
+    pub fn get_mouse_follow_error(&self) -> Option<String> {
+        self.mouse_follow_error.read().clone()
+    }
+
+    /// Record or clear a mouse listener failure
+    pub fn set_mouse_follow_error(&self, error: Option<String>) {
+        *self.mouse_follow_error.write() = error;
+    }
+
+    /// Lock the control server slot, to check and store it in one step
+    pub fn ipc_server_slot(&self) -> MutexGuard<'_, Option<IpcServer>> {
+        self.ipc_server.lock()
+    }
+
+    /// Remove the running control server so it can be stopped
+    pub fn take_ipc_server(&self) -> Option<IpcServer> {
+        self.ipc_server.lock().take()
+    }
+
+    /// Get the registered shortcut for each action
+    pub fn get_registered_hotkeys(&self) -> HashMap<String, String> {
+        self.registered_hotkeys.read().clone()
+    }
+
+    /// Get a cached colored SVG data URL
+    pub fn get_cached_svg(&self, filename: &str, color: &str) -> Option<String> {
+        self.svg_cache
+            .read()
+            .get(&(filename.to_string(), color.to_string()))
+            .cloned()
+    }
+
+    /// Cache a colored SVG data URL
+    pub fn cache_svg(&self, filename: String, color: String, data_url: String) {
+        self.svg_cache.write().insert((filename, color), data_url);
+    }
+
+    /// Get cached crosshair image bytes
+    pub fn get_crosshair_bytes(&self, filename: &str) -> Option<Vec<u8>> {
+        self.crosshair_cache.lock().get(filename)
+    }
+
+    /// Cache crosshair image bytes
+    pub fn cache_crosshair(&self, filename: &str, bytes: Vec<u8>) {
+        self.crosshair_cache.lock().insert(filename, bytes);
+    }
+
+    /// Get a cached crosshair preview
+    pub fn get_cached_preview(&self, filename: &str, color: &str) -> Option<String> {
+        self.preview_cache.lock().get(filename, color)
+    }
+
+    /// Cache a crosshair preview
+    pub fn cache_preview(&self, filename: &str, color: &str, data_url: String) {
+        self.preview_cache.lock().insert(filename, color, data_url);
+    }
+
+    /// Get cached crosshair metadata
+    pub fn get_cached_metadata(&self, filename: &str) -> Option<CrosshairMetadata> {
+        self.metadata_cache.read().get(filename).cloned()
+    }
+
+    /// Cache crosshair metadata
+    pub fn cache_metadata(&self, filename: &str, metadata: CrosshairMetadata) {
+        self.metadata_cache
+            .write()
+            .insert(filename.to_string(), metadata);
+    }
+
+    /// Get a cached crosshair thumbnail
+    pub fn get_cached_thumbnail(&self, filename: &str) -> Option<String> {
+        self.thumbnail_cache.lock().get(filename)
+    }
+
+    /// Cache a crosshair thumbnail
+    pub fn cache_thumbnail(&self, filename: &str, data_url: String) {
+        self.thumbnail_cache.lock().insert(filename, data_url);
+    }
+
+    /// Drop all cached data for a crosshair
+    pub fn evict_crosshair(&self, filename: &str) {
+        self.crosshair_cache.lock().remove(filename);
+        self.preview_cache.lock().remove(filename);
+        self.metadata_cache.write().remove(filename);
+        self.thumbnail_cache.lock().remove(filename);
+        self.svg_cache
+            .write()
+            .retain(|(cached, _), _| cached != filename);
+    }
+
+    /// Check whether the backups should be rotated on this save
+    ///
+    /// The first save of a session always rotates, then at most once every
+    /// `BACKUP_INTERVAL_SECS`.
+    fn take_backup_due(&self) -> bool {
+        let mut last = self.last_backup.lock();
+        let now = Instant::now();
+        let due = match *last {
+            Some(at) => now.duration_since(at) >= Duration::from_secs(BACKUP_INTERVAL_SECS),
+            None => true,
+        };
+        if due {
+            *last = Some(now);
+        }
+        due
+    }
+
+    /// Remember the preferences from before the command line overrides
+    ///
+    /// Call right after applying the overrides, with the preferences as they
+    /// were before.
+    pub fn set_session_overrides(&self, before: &Preferences) -> Result<(), String> {
+        let serialize = |prefs: &Preferences| {
+            serde_json::to_value(prefs)
+                .map_err(|e| format!("Failed to serialize preferences: {}", e))
+        };
+        let before = serialize(before)?;
+        let applied = serialize(&self.preferences.read())?;
+        *self.session_overrides.lock() = Some((before, applied));
+        Ok(())
+    }
+
+    /// Preferences as they should be written to disk
+    ///
+    /// Values still as the command line overrides set them are swapped back to
+    /// the saved ones, so the overrides only last for the session. Anything
+    /// changed since launch is written as is.
+    pub fn persisted_preferences(&self) -> Result<serde_json::Value, String> {
+        let mut current = serde_json::to_value(&*self.preferences.read())
+            .map_err(|e| format!("Failed to serialize preferences: {}", e))?;
+        if let (Some((before, applied)), Some(fields)) =
+            (&*self.session_overrides.lock(), current.as_object_mut())
+        {
+            for (key, value) in fields.iter_mut() {
+                if applied.get(key) == Some(value) {
+                    if let Some(saved) = before.get(key) {
+                        *value = saved.clone();
+                    }
+                }
+            }
+        }
+        Ok(current)
+    }
+
+    /// Save preferences to disk
+    pub fn save_preferences(&self, app: &AppHandle) -> Result<(), String> {
+        if self.safe_mode {
+            log::debug!("Safe mode: not saving preferences");
+            return Ok(());
+        }
+
+        let store = app
+            .store(self.store_path(app)?)
+            .map_err(|e| format!("Failed to get store: {}", e))?;
+
+        let prefs = self.persisted_preferences()?;
+
+        // Keep the previous copies around in case the new file gets corrupted
+        if self.take_backup_due() {
+            if let Err(e) = rotate_backups(&self.store_path(app)?, BACKUP_COUNT) {
+                log::warn!("Failed to rotate preference backups: {}", e);
+            }
+        }
+
+        store.set(STORE_KEY_PREFERENCES, prefs);
+        store.delete(LEGACY_STORE_KEY_PREFERENCES);
+        store.set(STORE_KEY_SHADOW_COUNTER, self.shadow_counter());
+
+        store
+            .save()
+            .map_err(|e| format!("Failed to save store: {}", e))?;
+
+        log::info!("Preferences saved");
+        Ok(())
+    }
+
+    /// Check whether the last session ended cleanly, then start a new one
+    ///
+    /// The clean-session marker is removed so that a crash during this
+    /// session is detected on the next launch.
+    pub fn begin_session(&self, app: &AppHandle) -> Result<(), String> {
+        if self.safe_mode {
+            return Ok(());
+        }
+
+        let store = app
+            .store(self.store_path(app)?)
+            .map_err(|e| format!("Failed to get store: {}", e))?;
+
+        // A store without preferences is a first launch, not a crash
+        let ran_before =
+            store.has(STORE_KEY_PREFERENCES) || store.has(LEGACY_STORE_KEY_PREFERENCES);
+        if ran_before && !store.has("session-clean") {
+            log::warn!("Previous session did not shut down cleanly");
+            let snapshot = store
+                .get("last_clean_snapshot")
+                .and_then(|value| Preferences::from_stored(value).ok())
+                .and_then(|prefs| prefs.validated().ok());
+            *self.crash_recovery.lock() = Some(CrashRecovery {
+                last_preferences_snapshot: snapshot,
+            });
+        }
+
+        store.delete("session-clean");
+        store
+            .save()
+            .map_err(|e| format!("Failed to save store: {}", e))
+    }
+
+    /// Record that the session is ending normally
+    pub fn mark_session_clean(&self, app: &AppHandle) -> Result<(), String> {
+        if self.safe_mode {
+            return Ok(());
+        }
+
+        let store = app
+            .store(self.store_path(app)?)
+            .map_err(|e| format!("Failed to get store: {}", e))?;
+        store.set("session-clean", true);
+        store
+            .save()
+            .map_err(|e| format!("Failed to save store: {}", e))
+    }
+
+    /// Store the current preferences as the state to offer after a crash
+    ///
+    /// Only writes to disk when the snapshot has changed.
+    pub fn save_clean_snapshot(&self, app: &AppHandle) -> Result<(), String> {
+        let store = app
+            .store(self.store_path(app)?)
+            .map_err(|e| format!("Failed to get store: {}", e))?;
+
+        let snapshot = self.persisted_preferences()?;
+        if store.get("last_clean_snapshot").as_ref() == Some(&snapshot) {
+            return Ok(());
+        }
+
+        store.set("last_clean_snapshot", snapshot);
+        store
+            .save()
+            .map_err(|e| format!("Failed to save store: {}", e))
+    }
+
+    /// Load preferences from disk
+    pub fn load_preferences(&self, app: &AppHandle) -> Result<(), String> {
+        let store = app
+            .store(self.store_path(app)?)
+            .map_err(|e| format!("Failed to get store: {}", e))?;
+
+        if let Some(counter) = store
+            .get(STORE_KEY_SHADOW_COUNTER)
+            .and_then(|value| value.as_u64())
+        {
+            self.restore_shadow_counter(counter);
+        }
+
+        let (value, legacy) = match store.get(STORE_KEY_PREFERENCES) {
+            Some(value) => (Some(value), false),
+            None => (store.get(LEGACY_STORE_KEY_PREFERENCES), true),
+        };
+
+        let has_start_on_boot = value
+            .as_ref()
+            .is_some_and(|value| value.get("start_on_boot").is_some());
+        self.start_on_boot_unset
+            .store(!has_start_on_boot, Ordering::SeqCst);
+
+        if let Some(value) = value {
+            match Preferences::from_stored(value.clone())
+                .map_err(|e| e.to_string())
+                .and_then(Preferences::validated)
+            {
+                Ok(prefs) => {
+                    *self.preferences.write() = prefs;
+                    log::info!("Preferences loaded");
+
+                    if legacy && !self.safe_mode {
+                        store.set(STORE_KEY_PREFERENCES, value);
+                        store.delete(LEGACY_STORE_KEY_PREFERENCES);
+                        store
+                            .save()
+                            .map_err(|e| format!("Failed to save store: {}", e))?;
+                        log::info!("Migrated preferences to {}", STORE_KEY_PREFERENCES);
+                    }
+                }
+                Err(e) => {
+                    log::warn!("Failed to parse preferences, using defaults: {}", e);
+
+                    // Move the corrupt file aside so the next save doesn't
+                    // rotate it into the backups
+                    let path = self.store_path(app)?;
+                    let mut corrupt_path = path.as_os_str().to_os_string();
+                    corrupt_path.push(".corrupt");
+                    if let Err(e) = std::fs::rename(&path, &corrupt_path) {
+                        log::warn!("Failed to move corrupt preferences aside: {}", e);
+                    }
+
+                    let latest = backup_path(&path, 1);
+                    *self.preferences_corrupt.lock() = Some(PreferencesCorrupt {
+                        error: e.to_string(),
+                        backup_path: latest
+                            .exists()
+                            .then(|| latest.to_string_lossy().into_owned()),
+                    });
+                }
+            }
+        } else {
+            log::info!("No saved preferences found, using defaults");
+        }
+
+        Ok(())
+    }
+
+    /// Restore preferences from the nth backup (0 is the most recent)
+    pub fn restore_preferences_backup(&self, app: &AppHandle, index: usize) -> Result<(), String> {
+        if index >= BACKUP_COUNT {
+            return Err(format!(
+                "Backup index must be between 0 and {}",
+                BACKUP_COUNT - 1
+            ));
+        }
+
+        let path = backup_path(&self.store_path(app)?, index + 1);
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read backup {:?}: {}", path, e))?;
+        let value: serde_json::Value = serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse backup {:?}: {}", path, e))?;
+        let prefs =
+            Preferences::from_stored(stored_preferences(&value).cloned().unwrap_or_default())
+                .map_err(|e| format!("Failed to parse preferences in backup: {}", e))?
+                .validated()?;
+
+        *self.preferences_mut() = prefs;
+        log::info!("Preferences restored from {:?}", path);
+        Ok(())
+    }
+
+    /// Write the current preferences as pretty-printed JSON to a file
+    pub fn export_preferences(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(&*self.preferences.read())
+            .map_err(|e| format!("Failed to serialize preferences: {}", e))?;
+        std::fs::write(path, json).map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+        log::info!("Preferences exported to {:?}", path);
+        Ok(())
+    }
+
+    /// Read, validate and apply preferences from a file
+    ///
+    /// Accepts both exported files and store files with a preferences key.
+    pub fn import_preferences(&self, path: &Path) -> Result<(), String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+        let mut value: serde_json::Value = serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse {:?}: {}", path, e))?;
+        if let Some(inner) = stored_preferences(&value) {
+            value = inner.clone();
+        }
+
+        let prefs = Preferences::from_stored(value)
+            .map_err(|e| format!("Invalid preferences file: {}", e))?
+            .validated()?;
+
+        *self.preferences_mut() = prefs;
+        log::info!("Preferences imported from {:?}", path);
+        Ok(())
+    }
+
+    /// Reset preferences to defaults
+    ///
+    /// Custom keybinds are kept unless `reset_keybinds` is set.
+    pub fn reset_preferences(&self, reset_keybinds: bool) {
+        // Keep the crosshair in history so the reset can be stepped back over
+        self.push_crosshair_history(&Preferences::default().crosshair);
+        let mut prefs = self.preferences_mut();
+        let keybinds = std::mem::take(&mut prefs.keybinds);
+        *prefs = Preferences::default();
+        if !reset_keybinds {
+            prefs.keybinds = keybinds;
+        }
+        log::info!("Preferences reset to defaults");
+    }
+
+    /// Get a clone of current preferences
+    pub fn get_preferences(&self) -> Preferences {
+        self.preferences.read().clone()
+    }
+
+    /// Read the commonly used fields in one go so they are consistent
+    pub fn get_snapshot(&self) -> StateSnapshot {
+        let prefs = self.preferences.read();
+        StateSnapshot {
+            locked: prefs.locked,
+            visible: prefs.visible,
+            follow_mouse: prefs.follow_mouse,
+            size: prefs.size,
+            opacity: prefs.opacity,
+            color: prefs.color.clone(),
+            crosshair: prefs.crosshair.clone(),
+            reticle: prefs.reticle.clone(),
         }
     }
 }
 
-/// Keybind preferences
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct KeybindPreferences {
-    pub toggle_lock: String,
-    pub center: String,
-    pub hide: String,
-    pub reset: String,
-    pub move_up: String,
-    pub move_down: String,
-    pub move_left: String,
-    pub move_right: String,
-    pub change_display: String,
-    pub duplicate: String,
-    pub quit: String,
+/// Find the preferences in the contents of a store file
+///
+/// Files written before the key was namespaced use the legacy key.
+pub fn stored_preferences(store: &serde_json::Value) -> Option<&serde_json::Value> {
+    store
+        .get(STORE_KEY_PREFERENCES)
+        .or_else(|| store.get(LEGACY_STORE_KEY_PREFERENCES))
 }
 
-impl Default for KeybindPreferences {
-    fn default() -> Self {
-        Self {
-            toggle_lock: "Control+Shift+Alt+X".to_string(),
-            center: "Control+Shift+Alt+C".to_string(),
-            hide: "Control+Shift+Alt+H".to_string(),
-            reset: "Control+Shift+Alt+R".to_string(),
-            move_up: "Control+Shift+Alt+Up".to_string(),
-            move_down: "Control+Shift+Alt+Down".to_string(),
-            move_left: "Control+Shift+Alt+Left".to_string(),
-            move_right: "Control+Shift+Alt+Right".to_string(),
-            change_display: "Control+Shift+Alt+M".to_string(),
-            duplicate: "Control+Shift+Alt+D".to_string(),
-            quit: "Control+Shift+Alt+Q".to_string(),
+/// Get the path of the nth backup of a file, e.g. `settings.json.bak.1`
+pub fn backup_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(format!(".bak.{}", index));
+    PathBuf::from(name)
+}
+
+/// Shift existing backups up by one and copy the current file to `.bak.1`
+///
+/// The oldest backup beyond `count` is discarded.
+pub fn rotate_backups(path: &Path, count: usize) -> std::io::Result<()> {
+    if count == 0 || !path.exists() {
+        return Ok(());
+    }
+
+    for index in (1..count).rev() {
+        let from = backup_path(path, index);
+        if from.exists() {
+            std::fs::rename(&from, backup_path(path, index + 1))?;
         }
     }
+
+    std::fs::copy(path, backup_path(path, 1))?;
+    Ok(())
 }
 
-/// Global application state
-pub struct AppState {
-    /// Current preferences
-    pub preferences: RwLock<Preferences>,
+/// Start a background task that saves preferences after changes
+///
+/// Checks for unsaved changes every `SAVE_DEBOUNCE_MS` and writes them to
+/// disk when auto-save is enabled. Saving is held back while the window is
+/// being dragged, and the position is saved once it stops moving. The crash
+/// recovery snapshot is only refreshed after a change.
+pub fn start_auto_save(app: &AppHandle) {
+    if app.state::<Arc<AppState>>().safe_mode {
+        log::info!("Safe mode: auto-save disabled");
+        return;
+    }
 
-    /// Set of shadow window labels
-    pub shadow_windows: RwLock<HashSet<String>>,
+    let app = app.clone();
 
-    /// Counter for shadow window IDs
-    shadow_counter: RwLock<u32>,
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_millis(SAVE_DEBOUNCE_MS));
 
-    /// Whether mouse following is currently active
-    pub mouse_following_active: RwLock<bool>,
-}
+        loop {
+            interval.tick().await;
 
-impl Default for AppState {
-    fn default() -> Self {
-        Self {
-            preferences: RwLock::new(Preferences::default()),
-            shadow_windows: RwLock::new(HashSet::new()),
-            shadow_counter: RwLock::new(0),
-            mouse_following_active: RwLock::new(false),
+            let state = app.state::<Arc<AppState>>();
+            if state.is_move_pending() {
+                continue;
+            }
+
+            let snapshot = state.take_snapshot_dirty();
+            let settled = state.take_settled_move();
+            if settled || (state.get_auto_save() && state.take_dirty()) {
+                if let Err(e) = state.save_preferences(&app) {
+                    log::warn!("Auto-save failed: {}", e);
+                    state.mark_dirty();
+                }
+            }
+
+            if snapshot {
+                if let Err(e) = state.save_clean_snapshot(&app) {
+                    log::debug!("Failed to save crash recovery snapshot: {}", e);
+                }
+            }
         }
-    }
+    });
+
+    log::info!("Auto-save started");
 }
 
-impl AppState {
-    /// Create a new AppState with default values
-    pub fn new() -> Self {
-        Self::default()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patch_omitted_fields_unchanged() {
+        let mut prefs = Preferences::default();
+        let patch: PatchPreferences =
+            serde_json::from_str(r##"{ "size": 42, "color": "#f00" }"##).unwrap();
+
+        prefs.apply_patch(patch).unwrap();
+
+        let defaults = Preferences::default();
+        assert_eq!(prefs.size, 42);
+        assert_eq!(prefs.color, "#FF0000");
+        assert_eq!(prefs.crosshair, defaults.crosshair);
+        assert_eq!(prefs.opacity, defaults.opacity);
+        assert_eq!(prefs.reticle, defaults.reticle);
+        assert_eq!(prefs.position_x, None);
     }
 
-    /// Get the current crosshair
-    pub fn get_crosshair(&self) -> String {
-        self.preferences.read().crosshair.clone()
+    #[test]
+    fn test_patch_empty_payload() {
+        let patch: PatchPreferences = serde_json::from_str("{}").unwrap();
+        assert!(patch.crosshair.is_none());
+        assert!(patch.keybinds.is_none());
+
+        let mut prefs = Preferences::default();
+        prefs.apply_patch(patch).unwrap();
+        assert_eq!(prefs.size, DEFAULT_SIZE);
     }
 
-    /// Set the current crosshair
-    pub fn set_crosshair(&self, crosshair: String) {
-        self.preferences.write().crosshair = crosshair;
+    #[test]
+    fn test_patch_invalid_color_leaves_prefs_untouched() {
+        let mut prefs = Preferences::default();
+        let patch: PatchPreferences =
+            serde_json::from_str(r#"{ "size": 42, "color": "nope" }"#).unwrap();
+
+        assert!(prefs.apply_patch(patch).is_err());
+        assert_eq!(prefs.size, DEFAULT_SIZE);
     }
 
-    /// Get the current size
-    pub fn get_size(&self) -> u32 {
-        self.preferences.read().size
+    #[test]
+    fn test_setters_mark_dirty() {
+        let state = AppState::new();
+        assert!(!state.take_dirty());
+
+        state.set_size(42);
+        assert!(state.take_dirty());
+        assert!(!state.take_dirty());
+
+        let _ = state.get_size();
+        assert!(!state.take_dirty());
     }
 
-    /// Set the current size
-    pub fn set_size(&self, size: u32) {
-        self.preferences.write().size = size;
+    #[test]
+    fn test_session_overrides_not_persisted() {
+        let state = AppState::new();
+        state.set_opacity(0.8);
+        let before = state.get_preferences();
+        state.set_opacity(0.3);
+        state.set_size(200);
+        state.set_session_overrides(&before).unwrap();
+
+        // Untouched overrides are written with their saved values
+        let persisted = state.persisted_preferences().unwrap();
+        assert_eq!(persisted["opacity"], serde_json::json!(0.8));
+        assert_eq!(persisted["size"], serde_json::json!(before.size));
+
+        // Values changed after launch are written as they are
+        state.set_size(150);
+        state.set_color("#FF0000".to_string());
+        let persisted = state.persisted_preferences().unwrap();
+        assert_eq!(persisted["opacity"], serde_json::json!(0.8));
+        assert_eq!(persisted["size"], serde_json::json!(150));
+        assert_eq!(persisted["color"], serde_json::json!("#FF0000"));
+        assert_eq!(state.get_opacity(), 0.3);
     }
 
-    /// Get the current opacity
-    pub fn get_opacity(&self) -> f64 {
-        self.preferences.read().opacity
+    #[test]
+    fn test_dirty_marked_after_write() {
+        let state = AppState::new();
+        let mut prefs = state.preferences_mut();
+        prefs.size = 42;
+
+        // An auto-save tick during the write must not see the flag yet
+        assert!(!state.take_dirty());
+        drop(prefs);
+        assert!(state.take_dirty());
     }
 
-    /// Set the current opacity
-    pub fn set_opacity(&self, opacity: f64) {
-        self.preferences.write().opacity = opacity.clamp(0.0, 1.0);
+    #[test]
+    fn test_snapshot_dirty_independent_of_save() {
+        let state = AppState::new();
+        assert!(state.take_snapshot_dirty());
+        assert!(!state.take_snapshot_dirty());
+
+        state.set_size(42);
+        assert!(state.take_dirty());
+        assert!(state.take_snapshot_dirty());
+        assert!(!state.take_snapshot_dirty());
     }
 
-    /// Get the current color
-    pub fn get_color(&self) -> String {
-        self.preferences.read().color.clone()
+    #[test]
+    fn test_reticle_dimensions() {
+        let state = AppState::new();
+        assert!(state.set_reticle_thickness(5).is_err());
+        assert!(state.set_reticle_size(10).is_ok());
+        assert!(state.set_reticle_thickness(5).is_ok());
+        assert!(state.set_reticle_size(4).is_err());
+
+        let config = state.get_reticle_config();
+        assert_eq!((config.size, config.thickness), (10, 5));
+
+        // Rejected values leave the preferences untouched
+        state.take_dirty();
+        assert!(state.set_reticle_size(4).is_err());
+        assert!(state.set_reticle_thickness(11).is_err());
+        assert!(!state.take_dirty());
     }
 
-    /// Set the current color
-    pub fn set_color(&self, color: String) {
-        self.preferences.write().color = color;
+    #[test]
+    fn test_position_history() {
+        let state = AppState::new();
+        state.record_move(10, 10);
+        assert!(state.undo_position((10, 10)).is_none());
+
+        // Moves within the debounce interval are a single undo step
+        state.set_position(0, 0);
+        *state.move_save_deadline.lock() = None;
+        state.record_move(5, 5);
+        state.record_move(8, 8);
+
+        assert_eq!(state.undo_position((8, 8)), Some((0, 0)));
+        assert!(state.undo_position((0, 0)).is_none());
+        assert_eq!(state.redo_position((0, 0)), Some((8, 8)));
+        assert!(state.redo_position((8, 8)).is_none());
+        assert_eq!(state.undo_position((8, 8)), Some((0, 0)));
     }
 
-    /// Check if window is locked
-    pub fn is_locked(&self) -> bool {
-        self.preferences.read().locked
+    #[test]
+    fn test_crosshair_history_navigation() {
+        let mut history = CrosshairHistory::default();
+        assert!(history.back().is_none());
+
+        for crosshair in ["a.png", "b.png", "b.png", "c.png"] {
+            history.push(crosshair);
+        }
+        assert_eq!(history.entries(), vec!["a.png", "b.png", "c.png"]);
+
+        assert_eq!(history.back().as_deref(), Some("b.png"));
+        assert_eq!(history.back().as_deref(), Some("a.png"));
+        assert!(history.back().is_none());
+        assert_eq!(history.forward().as_deref(), Some("b.png"));
+
+        // A new selection drops the forward entries
+        history.push("d.png");
+        assert_eq!(history.entries(), vec!["a.png", "b.png", "d.png"]);
+        assert!(history.forward().is_none());
     }
 
-    /// Set the locked state
-    pub fn set_locked(&self, locked: bool) {
-        self.preferences.write().locked = locked;
+    #[test]
+    fn test_crosshair_history_capacity() {
+        let mut history = CrosshairHistory::default();
+        for i in 0..MAX_CROSSHAIR_HISTORY + 3 {
+            history.push(&format!("{}.png", i));
+        }
+
+        let entries = history.entries();
+        assert_eq!(entries.len(), MAX_CROSSHAIR_HISTORY);
+        assert_eq!(entries[0], "3.png");
+        assert_eq!(history.current(), Some("12.png"));
     }
 
-    /// Toggle the locked state
-    pub fn toggle_locked(&self) -> bool {
-        let mut prefs = self.preferences.write();
-        prefs.locked = !prefs.locked;
-        prefs.locked
+    #[test]
+    fn test_crosshair_favorites() {
+        let state = AppState::new();
+        assert!(state.add_crosshair_favorite("dot.png"));
+        assert!(!state.add_crosshair_favorite("dot.png"));
+        assert!(state.toggle_crosshair_favorite("cross.png"));
+        assert_eq!(
+            state.get_crosshair_favorites(),
+            vec!["cross.png", "dot.png"]
+        );
+
+        assert!(!state.toggle_crosshair_favorite("dot.png"));
+        assert!(!state.is_crosshair_favorite("dot.png"));
+        assert!(state.remove_crosshair_favorite("cross.png"));
+        assert!(state.get_crosshair_favorites().is_empty());
     }
 
-    /// Check if window is visible
-    pub fn is_visible(&self) -> bool {
-        self.preferences.read().visible
+    #[test]
+    fn test_reset_preferences_keybinds() {
+        let state = AppState::new();
+        state.set_size(64);
+        state.preferences_mut().keybinds.center = "Alt+C".to_string();
+
+        state.reset_preferences(false);
+        assert_eq!(state.get_size(), DEFAULT_SIZE);
+        assert_eq!(state.get_preferences().keybinds.center, "Alt+C");
+
+        state.reset_preferences(true);
+        assert_eq!(
+            state.get_preferences().keybinds,
+            KeybindPreferences::default()
+        );
     }
 
-    /// Set the visibility state
-    pub fn set_visible(&self, visible: bool) {
-        self.preferences.write().visible = visible;
+    #[test]
+    fn test_move_step_clamped() {
+        let state = AppState::new();
+        assert_eq!(state.get_move_step_px(), MOVE_INCREMENT);
+        assert_eq!(state.get_fast_move_step_px(), FAST_MOVE_INCREMENT);
+
+        state.set_move_step_px(0);
+        assert_eq!(state.get_move_step_px(), MIN_MOVE_STEP_PX);
+        state.set_fast_move_step_px(500);
+        assert_eq!(state.get_fast_move_step_px(), MAX_MOVE_STEP_PX);
     }
 
-    /// Toggle the visibility state
-    pub fn toggle_visible(&self) -> bool {
-        let mut prefs = self.preferences.write();
-        prefs.visible = !prefs.visible;
-        prefs.visible
+    #[test]
+    fn test_reset_keeps_crosshair_history() {
+        let state = AppState::new();
+        state.set_crosshair("dot.png".to_string());
+        state.reset_preferences(true);
+
+        assert_eq!(
+            state.prev_crosshair_from_history().as_deref(),
+            Some("dot.png")
+        );
     }
 
-    /// Check if follow mouse is enabled
-    pub fn get_follow_mouse(&self) -> bool {
-        self.preferences.read().follow_mouse
+    #[test]
+    fn test_monitor_crosshair() {
+        let state = AppState::new();
+        assert!(state.get_monitor_crosshair("DELL").is_none());
+
+        state.set_monitor_crosshair("DELL".to_string(), "dot.png".to_string());
+        assert_eq!(
+            state.get_monitor_crosshair("DELL").as_deref(),
+            Some("dot.png")
+        );
+
+        assert!(state.clear_monitor_crosshair("DELL"));
+        assert!(!state.clear_monitor_crosshair("DELL"));
     }
 
-    /// Set follow mouse state
-    pub fn set_follow_mouse(&self, follow: bool) {
-        self.preferences.write().follow_mouse = follow;
+    #[test]
+    fn test_lock_mode() {
+        let state = AppState::new();
+        state.set_lock_mode(LockMode::ClickThrough);
+        assert!(state.is_locked());
+        assert!(!state.is_position_locked());
+
+        assert!(!state.toggle_locked());
+        assert_eq!(state.get_lock_mode(), LockMode::Unlocked);
+        assert!(state.toggle_locked());
+        assert_eq!(state.get_lock_mode(), LockMode::Locked);
+
+        assert_eq!("click_through".parse(), Ok(LockMode::ClickThrough));
+        assert!("frozen".parse::<LockMode>().is_err());
     }
 
-    /// Check if hide on ADS is enabled
-    pub fn get_hide_on_ads(&self) -> bool {
-        self.preferences.read().hide_on_ads
+    #[test]
+    fn test_lock_mode_migration() {
+        let prefs = Preferences::from_stored(serde_json::json!({ "locked": true })).unwrap();
+        assert_eq!(prefs.lock_mode, LockMode::Locked);
+
+        let prefs = Preferences::from_stored(
+            serde_json::json!({ "locked": true, "lock_mode": "click_through" }),
+        )
+        .unwrap();
+        assert_eq!(prefs.lock_mode, LockMode::ClickThrough);
+
+        let prefs = Preferences::from_stored(serde_json::json!({})).unwrap();
+        assert_eq!(prefs.lock_mode, LockMode::Unlocked);
+        assert!(!prefs.locked);
     }
 
-    /// Set hide on ADS state
-    pub fn set_hide_on_ads(&self, hide: bool) {
-        self.preferences.write().hide_on_ads = hide;
+    #[test]
+    fn test_validated_clamps_blink_and_rotation() {
+        let prefs = Preferences::from_stored(serde_json::json!({
+            "blink_interval_ms": 0,
+            "rotation_degrees": 720.0,
+        }))
+        .unwrap()
+        .validated()
+        .unwrap();
+        assert_eq!(prefs.blink_interval_ms, MIN_BLINK_INTERVAL_MS);
+        assert_eq!(prefs.rotation_degrees, 360.0);
     }
 
-    /// Get reticle type
-    pub fn get_reticle(&self) -> String {
-        self.preferences.read().reticle.clone()
+    #[test]
+    fn test_crosshair_offset_clamped_on_every_path() {
+        let mut prefs = Preferences::default();
+        prefs
+            .apply_patch(PatchPreferences {
+                crosshair_offset_x: Some(500),
+                crosshair_offset_y: Some(-500),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(prefs.crosshair_offset(), CrosshairOffset { x: 99, y: -99 });
+        assert!(validate_crosshair_offset(prefs.crosshair_offset(), 200, 200).is_ok());
+
+        let imported = Preferences::from_stored(serde_json::json!({
+            "auto_resize_window": true,
+            "size": 60,
+            "crosshair_offset_x": 40,
+        }))
+        .unwrap()
+        .validated()
+        .unwrap();
+        assert_eq!(imported.crosshair_offset_x, 29);
+
+        let state = AppState::new();
+        state.set_crosshair_offset(CrosshairOffset { x: 0, y: 1000 });
+        assert_eq!(state.get_crosshair_offset().y, 99);
     }
 
-    /// Set reticle type
-    pub fn set_reticle(&self, reticle: String) {
-        self.preferences.write().reticle = reticle;
+    #[test]
+    fn test_crosshair_effect() {
+        let state = AppState::new();
+        let effect = state.get_crosshair_effect();
+        assert!(!effect.shadow.enabled && !effect.outline.enabled);
+        assert_eq!((effect.shadow.blur, effect.outline.width), (4, 1));
+
+        assert!(state.set_shadow_blur(MAX_SHADOW_BLUR + 1).is_err());
+        assert!(state.set_outline_width(0).is_err());
+        assert!(state.set_shadow_color("red").is_err());
+        assert!(state.set_outline_color("#fff").is_ok());
+        state.set_shadow_enabled(true);
+
+        let effect = state.get_crosshair_effect();
+        assert!(effect.shadow.enabled);
+        assert_eq!(effect.outline.color, "#FFFFFF");
+        assert_eq!(effect.shadow.color, "#000000");
     }
 
-    /// Get saved position
-    pub fn get_position(&self) -> (Option<i32>, Option<i32>) {
-        let prefs = self.preferences.read();
-        (prefs.position_x, prefs.position_y)
+    #[test]
+    fn test_validate_crosshair_offset() {
+        let offset = |x, y| CrosshairOffset { x, y };
+        assert!(validate_crosshair_offset(offset(0, 0), 200, 200).is_ok());
+        assert!(validate_crosshair_offset(offset(-99, 99), 200, 200).is_ok());
+        assert!(validate_crosshair_offset(offset(100, 0), 200, 200).is_err());
+        assert!(validate_crosshair_offset(offset(0, -150), 200, 300).is_ok());
+        assert!(validate_crosshair_offset(offset(0, -150), 300, 200).is_err());
     }
 
-    /// Set saved position
-    pub fn set_position(&self, x: i32, y: i32) {
-        let mut prefs = self.preferences.write();
-        prefs.position_x = Some(x);
-        prefs.position_y = Some(y);
+    #[test]
+    fn test_export_import_preferences() {
+        let path =
+            std::env::temp_dir().join(format!("crossover-export-test-{}.json", std::process::id()));
+
+        let state = AppState::new();
+        state.set_size(123);
+        state.set_color("#ABCDEF".to_string());
+        state.export_preferences(&path).unwrap();
+
+        let imported = AppState::new();
+        imported.import_preferences(&path).unwrap();
+        assert_eq!(imported.get_size(), 123);
+        assert_eq!(imported.get_color(), "#ABCDEF");
+
+        std::fs::write(&path, r##"{"preferences": {"color": "#f00"}}"##).unwrap();
+        imported.import_preferences(&path).unwrap();
+        assert_eq!(imported.get_color(), "#FF0000");
+
+        std::fs::write(&path, r#"{"color": "red"}"#).unwrap();
+        assert!(imported.import_preferences(&path).is_err());
+
+        let _ = std::fs::remove_file(&path);
     }
 
-    /// Generate a new shadow window ID
-    pub fn next_shadow_id(&self) -> String {
-        let mut counter = self.shadow_counter.write();
-        *counter += 1;
-        format!("shadow-{}", *counter)
+    #[test]
+    fn test_stored_preferences_legacy_key() {
+        let legacy: serde_json::Value =
+            serde_json::from_str(r##"{"preferences": {"color": "#f00", "size": 42}}"##).unwrap();
+        let prefs = Preferences::from_stored(stored_preferences(&legacy).unwrap().clone()).unwrap();
+        assert_eq!(prefs.size, 42);
+
+        let both = serde_json::json!({
+            STORE_KEY_PREFERENCES: { "size": 64 },
+            LEGACY_STORE_KEY_PREFERENCES: { "size": 42 },
+        });
+        assert_eq!(stored_preferences(&both).unwrap()["size"], 64);
+        assert!(stored_preferences(&serde_json::json!({})).is_none());
     }
 
-    /// Add a shadow window
-    pub fn add_shadow_window(&self, label: String) {
-        self.shadow_windows.write().insert(label);
+    #[test]
+    fn test_preference_listener() {
+        let state = AppState::new();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let listener_seen = seen.clone();
+        state.register_preference_listener(move |prefs| {
+            listener_seen.lock().push((prefs.size, prefs.opacity));
+        });
+
+        state.set_size(48);
+        {
+            let mut prefs = state.preferences_mut();
+            prefs.size = 64;
+            prefs.opacity = 0.5;
+        }
+        assert_eq!(*seen.lock(), vec![(48, DEFAULT_OPACITY), (64, 0.5)]);
     }
 
-    /// Remove a shadow window
-    pub fn remove_shadow_window(&self, label: &str) {
-        self.shadow_windows.write().remove(label);
+    #[test]
+    fn test_get_snapshot() {
+        let state = AppState::new();
+        state.set_crosshair("dot.png".to_string());
+        state.set_size(48);
+        state.set_opacity(0.5);
+        state.set_color("#00FF00".to_string());
+        state.set_locked(true);
+        state.set_visible(false);
+        state.set_follow_mouse(true);
+        state.set_reticle("circle".to_string());
+
+        let snapshot = state.get_snapshot();
+        assert_eq!(
+            snapshot,
+            StateSnapshot {
+                locked: true,
+                visible: false,
+                follow_mouse: true,
+                size: 48,
+                opacity: 0.5,
+                color: "#00FF00".to_string(),
+                crosshair: "dot.png".to_string(),
+                reticle: "circle".to_string(),
+            }
+        );
+        assert_eq!(snapshot.locked, state.is_locked());
+        assert_eq!(snapshot.crosshair, state.get_crosshair());
     }
 
-    /// Get all shadow window labels
-    pub fn get_shadow_windows(&self) -> Vec<String> {
-        self.shadow_windows.read().iter().cloned().collect()
+    #[test]
+    fn test_recent_crosshairs() {
+        let state = AppState::new();
+        assert!(state.set_crosshair("a.png".to_string()));
+        assert!(!state.set_crosshair("a.png".to_string()));
+        for name in ["b.png", "c.png", "d.png", "e.png", "f.png"] {
+            state.set_crosshair(name.to_string());
+        }
+        assert!(state.set_crosshair("c.png".to_string()));
+
+        assert_eq!(
+            state.get_recent_crosshairs(),
+            vec!["c.png", "f.png", "e.png", "d.png", "b.png"]
+        );
     }
 
-    /// Get shadow window count
-    pub fn shadow_window_count(&self) -> usize {
-        self.shadow_windows.read().len()
+    #[test]
+    fn test_shadow_options_apply_to() {
+        let mut prefs = Preferences::default();
+        let options = ShadowWindowOptions {
+            crosshair: Some("dot.png".to_string()),
+            opacity: Some(2.0),
+            ..Default::default()
+        };
+        options.apply_to(&mut prefs);
+
+        assert_eq!(prefs.crosshair, "dot.png");
+        assert_eq!(prefs.opacity, 1.0);
+        assert_eq!(prefs.size, Preferences::default().size);
     }
 
-    /// Clear all shadow windows
-    pub fn clear_shadow_windows(&self) {
-        self.shadow_windows.write().clear();
+    #[test]
+    fn test_shadow_options_copy_settings() {
+        let mut prefs = Preferences::default();
+        prefs.size = 42;
+
+        let mut options = ShadowWindowOptions {
+            position_x: Some(10),
+            ..Default::default()
+        };
+        options.copy_settings_from(&prefs);
+        assert_eq!(options.position_x, Some(10));
+        assert_eq!(options.size, Some(42));
+
+        let patch = options.settings_patch();
+        assert_eq!(patch.crosshair, Some(prefs.crosshair));
+        assert!(patch.color.is_none() && patch.position_x.is_none());
     }
 
-    /// Save preferences to disk
-    pub fn save_preferences(&self, app: &AppHandle) -> Result<(), String> {
-        let store = app
-            .store(STORE_FILENAME)
-            .map_err(|e| format!("Failed to get store: {}", e))?;
+    #[test]
+    fn test_max_shadow_windows() {
+        let state = AppState::new();
+        assert_eq!(state.get_max_shadow_windows(), MAX_SHADOW_WINDOWS);
+        assert!(state
+            .set_max_shadow_windows(MAX_SHADOW_WINDOWS + 1)
+            .is_err());
+        state.set_max_shadow_windows(3).unwrap();
+        assert_eq!(state.get_max_shadow_windows(), 3);
+    }
 
-        let prefs = self.preferences.read().clone();
+    #[test]
+    fn test_shadow_counter_persists() {
+        let state = AppState::new();
+        assert_eq!(state.next_shadow_id(), "shadow-1");
+        assert!(state.take_dirty());
+        assert_eq!(state.next_shadow_id(), "shadow-2");
+
+        // Round trip through the stored JSON value
+        let stored = serde_json::to_value(state.shadow_counter()).unwrap();
+        let next_session = AppState::new();
+        next_session.restore_shadow_counter(stored.as_u64().unwrap());
+        assert_eq!(next_session.next_shadow_id(), "shadow-3");
+
+        // A stale stored value never moves the counter backwards
+        next_session.restore_shadow_counter(1);
+        assert_eq!(next_session.next_shadow_id(), "shadow-4");
+    }
 
-        store.set("preferences", serde_json::to_value(&prefs).unwrap());
+    #[test]
+    fn test_window_setup_complete() {
+        let state = AppState::new();
+        state.add_shadow_window("shadow-1".to_string());
+        state.mark_window_setup_complete("main");
+        state.mark_window_setup_complete("shadow-1");
+        assert!(state.is_window_setup_complete("main"));
+        assert!(state.is_window_setup_complete("shadow-1"));
+
+        state.clear_shadow_windows();
+        assert!(state.is_window_setup_complete("main"));
+        assert!(!state.is_window_setup_complete("shadow-1"));
+    }
 
-        store
-            .save()
-            .map_err(|e| format!("Failed to save store: {}", e))?;
+    #[test]
+    fn test_rate_limit() {
+        let state = AppState::new();
+        assert!(state.check_rate_limit("set_size", 2));
+        assert!(state.check_rate_limit("set_size", 2));
+        assert!(!state.check_rate_limit("set_size", 2));
+        assert!(state.check_rate_limit("set_color", 2));
+
+        let stats = state.get_rate_limit_stats();
+        assert_eq!(stats.get("set_size"), Some(&2));
+        assert_eq!(stats.get("set_color"), Some(&1));
+    }
 
-        log::info!("Preferences saved");
-        Ok(())
+    #[test]
+    fn test_profiles() {
+        let state = AppState::new();
+        state.set_size(42);
+        state.save_profile("big");
+        state.set_size(10);
+
+        assert!(state.load_profile("missing").is_err());
+        state.load_profile("big").unwrap();
+        assert_eq!(state.get_size(), 42);
+        assert_eq!(state.get_active_profile(), Some("big".to_string()));
+        assert_eq!(state.list_profiles(), vec!["big".to_string()]);
     }
 
-    /// Load preferences from disk
-    pub fn load_preferences(&self, app: &AppHandle) -> Result<(), String> {
-        let store = app
-            .store(STORE_FILENAME)
-            .map_err(|e| format!("Failed to get store: {}", e))?;
+    #[test]
+    fn test_process_rules() {
+        let state = AppState::new();
+        let rule = |process: &str, profile: &str| ProcessRule {
+            process_name: process.to_string(),
+            profile_name: profile.to_string(),
+        };
+
+        state.add_process_rule(rule("game.exe", "a"));
+        state.add_process_rule(rule("GAME.exe", "b"));
+        assert_eq!(state.get_process_rules(), vec![rule("GAME.exe", "b")]);
+
+        assert!(state.remove_process_rule("game.exe"));
+        assert!(!state.remove_process_rule("game.exe"));
+
+        assert!(state.add_hide_process("explorer.exe"));
+        assert!(!state.add_hide_process("Explorer.exe"));
+        assert_eq!(state.get_hide_processes(), vec!["explorer.exe"]);
+        assert!(state.remove_hide_process("EXPLORER.EXE"));
+        assert!(state.get_hide_processes().is_empty());
+    }
 
-        if let Some(value) = store.get("preferences") {
-            match serde_json::from_value::<Preferences>(value.clone()) {
-                Ok(prefs) => {
-                    *self.preferences.write() = prefs;
-                    log::info!("Preferences loaded");
-                }
-                Err(e) => {
-                    log::warn!("Failed to parse preferences, using defaults: {}", e);
-                }
-            }
-        } else {
-            log::info!("No saved preferences found, using defaults");
+    #[test]
+    fn test_rotation() {
+        let state = AppState::new();
+        state.set_rotation(400.0);
+        assert_eq!(state.get_rotation(), 360.0);
+
+        state.set_rotation(0.0);
+        assert_eq!(state.rotate_by(-90.0), 270.0);
+        assert_eq!(state.rotate_by(180.0), 90.0);
+    }
+
+    #[test]
+    fn test_keybind_get_mut() {
+        let mut keybinds = KeybindPreferences::default();
+        *keybinds.get_mut("center").unwrap() = "Control+K".to_string();
+
+        assert_eq!(keybinds.center, "Control+K");
+        assert!(keybinds.get_mut("unknown").is_none());
+        assert!(keybinds.get_mut("lock_and_save").unwrap().is_empty());
+        assert!(keybinds.get_mut("mute_sounds").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_backup_path() {
+        let path = Path::new("/tmp/crossover-settings.json");
+        assert_eq!(
+            backup_path(path, 2),
+            PathBuf::from("/tmp/crossover-settings.json.bak.2")
+        );
+    }
+
+    #[test]
+    fn test_rotate_backups() {
+        let dir = std::env::temp_dir().join(format!("crossover-backup-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("settings.json");
+
+        for version in 1..=5 {
+            std::fs::write(&path, version.to_string()).unwrap();
+            rotate_backups(&path, 3).unwrap();
         }
 
-        Ok(())
+        let read = |index| std::fs::read_to_string(backup_path(&path, index)).unwrap();
+        assert_eq!(read(1), "5");
+        assert_eq!(read(2), "4");
+        assert_eq!(read(3), "3");
+        assert!(!backup_path(&path, 4).exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 
-    /// Reset preferences to defaults
-    pub fn reset_preferences(&self) {
-        *self.preferences.write() = Preferences::default();
-        log::info!("Preferences reset to defaults");
+    #[test]
+    fn test_backup_rotated_once_per_interval() {
+        let state = AppState::new();
+        assert!(state.take_backup_due());
+        assert!(!state.take_backup_due());
+
+        *state.last_backup.lock() =
+            Instant::now().checked_sub(Duration::from_secs(BACKUP_INTERVAL_SECS));
+        assert!(state.take_backup_due());
+        assert!(!state.take_backup_due());
     }
 
-    /// Get a clone of current preferences
-    pub fn get_preferences(&self) -> Preferences {
-        self.preferences.read().clone()
+    #[test]
+    fn test_move_debounce() {
+        let state = AppState::new();
+        assert!(!state.is_move_pending());
+        assert!(!state.take_settled_move());
+
+        state.record_move(10, 20);
+        assert_eq!(state.get_position(), (Some(10), Some(20)));
+        assert!(state.is_move_pending());
+        assert!(!state.take_settled_move());
+
+        std::thread::sleep(Duration::from_millis(SAVE_DEBOUNCE_MS + 50));
+        assert!(!state.is_move_pending());
+        assert!(state.take_settled_move());
+        assert!(!state.take_settled_move());
+    }
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let prefs: Preferences = serde_json::from_str(r#"{ "size": 42 }"#).unwrap();
+        assert_eq!(prefs.size, 42);
+        assert!(prefs.auto_center_on_display_change);
+    }
+
+    #[test]
+    fn test_update_known_displays() {
+        let state = AppState::new();
+        let layout = vec!["A@0,0 1920x1080".to_string()];
+
+        assert!(!state.update_known_displays(layout.clone()));
+        assert!(!state.update_known_displays(layout.clone()));
+
+        let mut extended = layout;
+        extended.push("B@1920,0 1920x1080".to_string());
+        assert!(state.update_known_displays(extended));
     }
 }
@@ -4,29 +4,35 @@
 //! The tray provides quick access to common actions without needing
 //! to interact with the crosshair window directly.
 
+use crate::config::ReticleType;
 use crate::state::AppState;
 use crate::window;
-use log::{debug, error, info};
-use std::sync::Arc;
+use log::{debug, error, info, warn};
+use std::sync::{Arc, Once};
 use tauri::{
     image::Image,
-    menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem},
+    menu::{CheckMenuItem, IsMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem, Submenu},
     tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent},
     AppHandle, Emitter, Manager, Runtime,
 };
 
+/// Locked tray icon, generated from `icons/icon.png` by `build.rs`
+const LOCKED_ICON: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/icon-locked.png"));
+
 /// Set up the system tray icon and menu
 pub fn setup_tray(app: &AppHandle) -> Result<(), String> {
     info!("Setting up system tray...");
 
     // Create the tray menu
-    let menu = create_tray_menu(app)?;
+    let state = app.state::<Arc<AppState>>();
+    let menu = create_tray_menu(app, &state)?;
+    *state.tray_menu.lock() = Some(menu.clone());
 
     // Load tray icon
     let icon = load_tray_icon(app, false)?;
 
     // Build the tray icon
-    let _tray = TrayIconBuilder::new()
+    let tray = TrayIconBuilder::with_id(state.tray_id.clone())
         .icon(icon)
         .menu(&menu)
         .tooltip("CrossOver - Crosshair Overlay")
@@ -35,23 +41,145 @@ pub fn setup_tray(app: &AppHandle) -> Result<(), String> {
         .on_tray_icon_event(handle_tray_event)
         .build(app)
         .map_err(|e| format!("Failed to build tray icon: {}", e))?;
+    *state.tray_icon_id.write() = Some(tray.id().as_ref().to_string());
 
     info!("System tray setup complete");
     Ok(())
 }
 
+/// Refresh the tray tooltip with the crosshair, lock state and shadow count
+pub fn update_tray_tooltip(app: &AppHandle) -> Result<(), String> {
+    let state = app.try_state::<Arc<AppState>>().ok_or("State not found")?;
+    let tray = app
+        .tray_by_id(state.tray_id.as_str())
+        .ok_or("Tray icon not found")?;
+
+    let snapshot = state.get_snapshot();
+    let tooltip = tooltip_text(
+        &snapshot.crosshair,
+        snapshot.locked,
+        state.shadow_window_count(),
+    );
+    tray.set_tooltip(Some(tooltip))
+        .map_err(|e| format!("Failed to set tray tooltip: {}", e))
+}
+
+/// Notify all windows of a lock change and update the tray to match
+///
+/// Every lock change goes through here so the event payload, tray icon,
+/// tooltip and menu checkmark can't drift apart.
+pub fn emit_lock_changed(app: &AppHandle, locked: bool) -> Result<(), String> {
+    app.emit("lock-changed", locked)
+        .map_err(|e| e.to_string())?;
+    update_tray_icon(app, locked)?;
+    update_tray_tooltip(app)?;
+    update_tray_menu(app)
+}
+
+/// Switch the tray icon to the locked or unlocked variant
+pub fn update_tray_icon(app: &AppHandle, locked: bool) -> Result<(), String> {
+    let state = app.try_state::<Arc<AppState>>().ok_or("State not found")?;
+    let id = state
+        .tray_icon_id
+        .read()
+        .clone()
+        .ok_or("Tray icon not found")?;
+    let tray = app.tray_by_id(id.as_str()).ok_or("Tray icon not found")?;
+
+    let icon = load_tray_icon(app, locked)?;
+    tray.set_icon(Some(icon))
+        .map_err(|e| format!("Failed to set tray icon: {}", e))
+}
+
+/// Build the tray tooltip text
+fn tooltip_text(crosshair: &str, locked: bool, shadow_count: usize) -> String {
+    let name = std::path::Path::new(crosshair)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or(crosshair);
+    let lock_icon = if locked { "🔒" } else { "🔓" };
+
+    format!(
+        "CrossOver | {} | {} | {} shadows",
+        name, lock_icon, shadow_count
+    )
+}
+
+/// Prefix of the IDs of items in the recent crosshairs submenu
+const RECENT_CROSSHAIR_PREFIX: &str = "recent_crosshair_";
+
+/// Rebuild the tray menu, e.g. after the recent crosshairs change
+pub fn rebuild_tray_menu(app: &AppHandle) -> Result<(), String> {
+    let state = app.try_state::<Arc<AppState>>().ok_or("State not found")?;
+    let tray = app
+        .tray_by_id(state.tray_id.as_str())
+        .ok_or("Tray icon not found")?;
+
+    let menu = create_tray_menu(app, &state)?;
+    tray.set_menu(Some(menu.clone()))
+        .map_err(|e| format!("Failed to set tray menu: {}", e))?;
+    *state.tray_menu.lock() = Some(menu);
+
+    Ok(())
+}
+
+/// Sync the tray check items with the current lock and visibility state
+pub fn update_tray_menu(app: &AppHandle) -> Result<(), String> {
+    let state = app.try_state::<Arc<AppState>>().ok_or("State not found")?;
+    let menu = state
+        .tray_menu
+        .lock()
+        .clone()
+        .ok_or("Tray menu not found")?;
+
+    let snapshot = state.get_snapshot();
+    let items = [
+        (&state.tray_lock_item_id, snapshot.locked),
+        (&state.tray_hide_item_id, !snapshot.visible),
+    ];
+    for (id, checked) in items {
+        let item = menu
+            .get(id.as_str())
+            .and_then(|item| item.as_check_menuitem().cloned())
+            .ok_or_else(|| format!("Tray menu item not found: {}", id))?;
+        item.set_checked(checked)
+            .map_err(|e| format!("Failed to update menu item {}: {}", id, e))?;
+    }
+
+    Ok(())
+}
+
 /// Create the tray context menu
-fn create_tray_menu<R: Runtime>(app: &AppHandle<R>) -> Result<Menu<R>, String> {
+fn create_tray_menu<R: Runtime>(app: &AppHandle<R>, state: &AppState) -> Result<Menu<R>, String> {
+    let snapshot = state.get_snapshot();
+
     // Create menu items
-    let toggle_lock = MenuItem::with_id(app, "toggle_lock", "Lock/Unlock", true, None::<&str>)
-        .map_err(|e| format!("Failed to create menu item: {}", e))?;
+    let toggle_lock = CheckMenuItem::with_id(
+        app,
+        state.tray_lock_item_id.clone(),
+        "Locked",
+        true,
+        snapshot.locked,
+        None::<&str>,
+    )
+    .map_err(|e| format!("Failed to create menu item: {}", e))?;
 
     let center = MenuItem::with_id(app, "center", "Center", true, None::<&str>)
         .map_err(|e| format!("Failed to create menu item: {}", e))?;
 
-    let hide = MenuItem::with_id(app, "hide", "Hide/Show", true, None::<&str>)
+    let center_all = MenuItem::with_id(app, "center_all", "Center All Windows", true, None::<&str>)
         .map_err(|e| format!("Failed to create menu item: {}", e))?;
 
+    let hide = CheckMenuItem::with_id(
+        app,
+        state.tray_hide_item_id.clone(),
+        "Hidden",
+        true,
+        !snapshot.visible,
+        None::<&str>,
+    )
+    .map_err(|e| format!("Failed to create menu item: {}", e))?;
+
     let settings = MenuItem::with_id(app, "settings", "Settings...", true, None::<&str>)
         .map_err(|e| format!("Failed to create menu item: {}", e))?;
 
@@ -64,6 +192,27 @@ fn create_tray_menu<R: Runtime>(app: &AppHandle<R>) -> Result<Menu<R>, String> {
     )
     .map_err(|e| format!("Failed to create menu item: {}", e))?;
 
+    let recent_items = state
+        .get_recent_crosshairs()
+        .into_iter()
+        .map(|crosshair| {
+            let id = format!("{}{}", RECENT_CROSSHAIR_PREFIX, crosshair);
+            MenuItem::with_id(app, id, &crosshair, true, None::<&str>)
+                .map_err(|e| format!("Failed to create menu item: {}", e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let recent_refs: Vec<&dyn IsMenuItem<R>> = recent_items
+        .iter()
+        .map(|item| item as &dyn IsMenuItem<R>)
+        .collect();
+    let recent_crosshairs = Submenu::with_items(
+        app,
+        "Recent Crosshairs",
+        !recent_refs.is_empty(),
+        &recent_refs,
+    )
+    .map_err(|e| format!("Failed to create submenu: {}", e))?;
+
     let next_display = MenuItem::with_id(
         app,
         "next_display",
@@ -97,10 +246,12 @@ fn create_tray_menu<R: Runtime>(app: &AppHandle<R>) -> Result<Menu<R>, String> {
         &[
             &toggle_lock,
             &center,
+            &center_all,
             &hide,
             &separator1,
             &settings,
             &choose_crosshair,
+            &recent_crosshairs,
             &separator2,
             &next_display,
             &reset,
@@ -114,13 +265,16 @@ fn create_tray_menu<R: Runtime>(app: &AppHandle<R>) -> Result<Menu<R>, String> {
 
 /// Load the tray icon image
 /// If `locked` is true, loads the locked variant of the icon
+///
+/// The normal icon is read from `icons/icon.png` in the bundled resource
+/// directory, falling back to a generated reticle icon when it is missing.
+/// The locked icon is generated from `icon.png` by `build.rs` and embedded in
+/// the binary.
 fn load_tray_icon<R: Runtime>(app: &AppHandle<R>, locked: bool) -> Result<Image<'static>, String> {
-    // Determine icon filename based on lock state
-    let icon_name = if locked {
-        "icon-locked.png"
-    } else {
-        "icon.png"
-    };
+    if locked {
+        return Image::from_bytes(LOCKED_ICON)
+            .map_err(|e| format!("Failed to load locked icon: {}", e));
+    }
 
     // Try to load from resources
     let resource_path = app
@@ -128,7 +282,7 @@ fn load_tray_icon<R: Runtime>(app: &AppHandle<R>, locked: bool) -> Result<Image<
         .resource_dir()
         .map_err(|e| format!("Failed to get resource dir: {}", e))?
         .join("icons")
-        .join(icon_name);
+        .join("icon.png");
 
     if resource_path.exists() {
         return Ok(
@@ -136,31 +290,33 @@ fn load_tray_icon<R: Runtime>(app: &AppHandle<R>, locked: bool) -> Result<Image<
         );
     }
 
-    // Fallback: try the default icon if locked variant doesn't exist
-    if locked {
-        let fallback_path = app
-            .path()
-            .resource_dir()
-            .map_err(|e| format!("Failed to get resource dir: {}", e))?
-            .join("icons")
-            .join("icon.png");
-
-        if fallback_path.exists() {
-            return Ok(Image::from_path(&fallback_path)
-                .map_err(|e| format!("Failed to load fallback icon: {}", e))?);
-        }
-    }
-
     // Final fallback: create a simple colored icon
-    debug!(
-        "Tray icon not found at {:?}, generating default",
-        resource_path
-    );
-    Ok(generate_default_icon(locked))
+    static MISSING_ICON_WARNING: Once = Once::new();
+    let mut warned = false;
+    MISSING_ICON_WARNING.call_once(|| {
+        warn!(
+            "Tray icon not found at {:?}, generating default",
+            resource_path
+        );
+        warned = true;
+    });
+    if !warned {
+        debug!(
+            "Tray icon not found at {:?}, generating default",
+            resource_path
+        );
+    }
+    let reticle = app
+        .try_state::<Arc<AppState>>()
+        .map(|state| ReticleType::from_str(&state.get_reticle()))
+        .unwrap_or_default();
+    Ok(generate_default_icon(locked, reticle))
 }
 
 /// Generate a default icon programmatically
-fn generate_default_icon(locked: bool) -> Image<'static> {
+///
+/// The shape follows the reticle type and the color shows the lock state.
+fn generate_default_icon(locked: bool, reticle: ReticleType) -> Image<'static> {
     let size = 32usize;
     let mut rgba = vec![0u8; size * size * 4];
 
@@ -171,32 +327,23 @@ fn generate_default_icon(locked: bool) -> Image<'static> {
         (100u8, 255u8, 100u8) // Green when unlocked
     };
 
-    // Draw a simple cross pattern
+    let center = size as i32 / 2;
     for y in 0..size {
         for x in 0..size {
-            let idx = (y * size + x) * 4;
-            let is_cross = (x == size / 2 || y == size / 2)
-                && x > size / 4
-                && x < size * 3 / 4
-                && y > size / 4
-                && y < size * 3 / 4;
-
-            // Add a circle around the cross when locked
-            let dx = (x as i32 - size as i32 / 2).abs();
-            let dy = (y as i32 - size as i32 / 2).abs();
+            let dx = (x as i32 - center).abs();
+            let dy = (y as i32 - center).abs();
             let dist = ((dx * dx + dy * dy) as f64).sqrt();
-            let is_circle = locked && dist >= 10.0 && dist <= 12.0;
-
-            if is_cross || is_circle {
-                rgba[idx] = r;
-                rgba[idx + 1] = g;
-                rgba[idx + 2] = b;
-                rgba[idx + 3] = 255;
-            } else {
-                rgba[idx] = 0;
-                rgba[idx + 1] = 0;
-                rgba[idx + 2] = 0;
-                rgba[idx + 3] = 0; // Transparent
+
+            let filled = match reticle {
+                ReticleType::None => false,
+                ReticleType::Dot => dist <= 3.0,
+                ReticleType::Circle => (8.0..=10.0).contains(&dist),
+                ReticleType::Cross => (dx == 0 || dy == 0) && dx < 8 && dy < 8,
+            };
+
+            if filled {
+                let idx = (y * size + x) * 4;
+                rgba[idx..idx + 4].copy_from_slice(&[r, g, b, 255]);
             }
         }
     }
@@ -211,7 +358,8 @@ fn handle_menu_event(app: &AppHandle, event: MenuEvent) {
 
     let result = match id {
         "toggle_lock" => handle_toggle_lock(app),
-        "center" => handle_center(app),
+        "center" => handle_center(app, false),
+        "center_all" => handle_center(app, true),
         "hide" => handle_hide(app),
         "settings" => handle_settings(app),
         "choose_crosshair" => handle_choose_crosshair(app),
@@ -219,6 +367,9 @@ fn handle_menu_event(app: &AppHandle, event: MenuEvent) {
         "reset" => handle_reset(app),
         "about" => handle_about(app),
         "quit" => handle_quit(app),
+        _ if id.starts_with(RECENT_CROSSHAIR_PREFIX) => {
+            handle_recent_crosshair(app, &id[RECENT_CROSSHAIR_PREFIX.len()..])
+        }
         _ => {
             debug!("Unknown menu item: {}", id);
             Ok(())
@@ -251,13 +402,8 @@ fn handle_tray_event(tray: &TrayIcon, event: TrayIconEvent) {
         } => {
             debug!("Tray icon double-clicked");
             // Toggle lock on double click
-            let app = tray.app_handle();
-            if let Some(state) = app.try_state::<Arc<AppState>>() {
-                let locked = state.toggle_locked();
-                if let Some(win) = app.get_webview_window("main") {
-                    let _ = window::set_click_through(&win, locked);
-                }
-                let _ = app.emit("lock-changed", locked);
+            if let Err(e) = handle_toggle_lock(tray.app_handle()) {
+                error!("Failed to toggle lock: {}", e);
             }
         }
         _ => {}
@@ -269,32 +415,32 @@ fn handle_tray_event(tray: &TrayIcon, event: TrayIconEvent) {
 fn handle_toggle_lock(app: &AppHandle) -> Result<(), String> {
     info!("Tray: Toggle lock");
 
+    // Same path as the hotkey, so the sound and save on lock apply too
     let state = app.try_state::<Arc<AppState>>().ok_or("State not found")?;
-    let locked = state.toggle_locked();
+    crate::hotkeys::toggle_lock(app, state.get_save_on_lock())
+}
 
-    if let Some(win) = app.get_webview_window("main") {
-        window::set_click_through(&win, locked)?;
-    }
+fn handle_recent_crosshair(app: &AppHandle, crosshair: &str) -> Result<(), String> {
+    info!("Tray: Recent crosshair {}", crosshair);
 
-    // Update shadow windows
-    for label in state.get_shadow_windows() {
-        if let Some(win) = app.get_webview_window(&label) {
-            window::set_click_through(&win, locked)?;
-        }
+    let state = app.try_state::<Arc<AppState>>().ok_or("State not found")?;
+    if state.set_crosshair(crosshair.to_string()) {
+        rebuild_tray_menu(app)?;
     }
-
-    app.emit("lock-changed", locked)
-        .map_err(|e| e.to_string())?;
+    update_tray_tooltip(app)?;
 
     Ok(())
 }
 
-fn handle_center(app: &AppHandle) -> Result<(), String> {
+fn handle_center(app: &AppHandle, include_shadows: bool) -> Result<(), String> {
     info!("Tray: Center");
 
     if let Some(win) = app.get_webview_window("main") {
         window::center_on_current_monitor(&win)?;
     }
+    if include_shadows {
+        window::center_shadow_windows(app)?;
+    }
 
     Ok(())
 }
@@ -305,27 +451,12 @@ fn handle_hide(app: &AppHandle) -> Result<(), String> {
     let state = app.try_state::<Arc<AppState>>().ok_or("State not found")?;
     let visible = state.toggle_visible();
 
-    if let Some(win) = app.get_webview_window("main") {
-        if visible {
-            win.show().map_err(|e| e.to_string())?;
-        } else {
-            win.hide().map_err(|e| e.to_string())?;
-        }
-    }
-
-    // Update shadow windows
-    for label in state.get_shadow_windows() {
-        if let Some(win) = app.get_webview_window(&label) {
-            if visible {
-                win.show().map_err(|e| e.to_string())?;
-            } else {
-                win.hide().map_err(|e| e.to_string())?;
-            }
-        }
-    }
+    // Update main and shadow windows, fading if configured
+    window::apply_visibility(app, visible)?;
 
     app.emit("visibility-changed", visible)
         .map_err(|e| e.to_string())?;
+    update_tray_menu(app)?;
 
     Ok(())
 }
@@ -352,7 +483,7 @@ fn handle_choose_crosshair(app: &AppHandle) -> Result<(), String> {
         if let Some(win) = app.get_webview_window("main") {
             window::set_click_through(&win, false)?;
         }
-        app.emit("lock-changed", false).map_err(|e| e.to_string())?;
+        emit_lock_changed(app, false)?;
     }
 
     if let Some(win) = app.get_webview_window("main") {
@@ -366,32 +497,21 @@ fn handle_choose_crosshair(app: &AppHandle) -> Result<(), String> {
 fn handle_next_display(app: &AppHandle) -> Result<(), String> {
     info!("Tray: Next display");
 
-    if let Some(win) = app.get_webview_window("main") {
-        window::move_to_next_display(&win)?;
-    }
-
-    Ok(())
+    window::move_main_to_next_display(app, true)
 }
 
 fn handle_reset(app: &AppHandle) -> Result<(), String> {
     info!("Tray: Reset");
 
+    // Re-registers shortcuts if the reset changed any keybinds
     let state = app.try_state::<Arc<AppState>>().ok_or("State not found")?;
-    state.reset_preferences();
-
-    // Emit events to update UI
-    let prefs = state.get_preferences();
-    app.emit("crosshair-changed", &prefs.crosshair)
-        .map_err(|e| e.to_string())?;
-    app.emit("opacity-changed", prefs.opacity)
-        .map_err(|e| e.to_string())?;
-    app.emit("size-changed", prefs.size)
-        .map_err(|e| e.to_string())?;
-    app.emit("color-changed", &prefs.color)
-        .map_err(|e| e.to_string())?;
+    crate::commands::replace_preferences(app, state.inner(), |state| {
+        state.reset_preferences(true);
+        Ok(())
+    })?;
 
     // Center the window
-    handle_center(app)?;
+    handle_center(app, false)?;
 
     Ok(())
 }
@@ -419,6 +539,10 @@ fn handle_quit(app: &AppHandle) -> Result<(), String> {
         if let Err(e) = state.save_preferences(app) {
             error!("Failed to save preferences on quit: {}", e);
         }
+        if let Err(e) = state.mark_session_clean(app) {
+            error!("Failed to mark session clean: {}", e);
+        }
+        crate::blink::stop_blink(&state);
     }
 
     app.exit(0);
@@ -429,17 +553,63 @@ fn handle_quit(app: &AppHandle) -> Result<(), String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_tooltip_text() {
+        assert_eq!(
+            tooltip_text("crosshair-default.png", false, 0),
+            "CrossOver | crosshair-default | 🔓 | 0 shadows"
+        );
+        assert_eq!(
+            tooltip_text("custom/dot.svg", true, 3),
+            "CrossOver | dot | 🔒 | 3 shadows"
+        );
+    }
+
+    /// Alpha of the icon pixel at (x, y)
+    fn alpha(icon: &Image<'_>, x: usize, y: usize) -> u8 {
+        icon.rgba()[(y * 32 + x) * 4 + 3]
+    }
+
     #[test]
     fn test_generate_default_icon_unlocked() {
-        let icon = generate_default_icon(false);
-        // Just verify it doesn't panic and returns valid dimensions
-        assert!(icon.rgba().len() > 0);
+        let icon = generate_default_icon(false, ReticleType::Cross);
+        assert_eq!(icon.rgba().len(), 32 * 32 * 4);
+        assert_eq!(&icon.rgba()[(16 * 32 + 16) * 4..][..3], &[100, 255, 100]);
     }
 
     #[test]
     fn test_generate_default_icon_locked() {
-        let icon = generate_default_icon(true);
-        // Just verify it doesn't panic and returns valid dimensions
-        assert!(icon.rgba().len() > 0);
+        let icon = generate_default_icon(true, ReticleType::Cross);
+        assert_eq!(icon.rgba().len(), 32 * 32 * 4);
+        assert_eq!(&icon.rgba()[(16 * 32 + 16) * 4..][..3], &[255, 100, 100]);
+    }
+
+    #[test]
+    fn test_generate_default_icon_dot() {
+        let icon = generate_default_icon(false, ReticleType::Dot);
+        assert_eq!(alpha(&icon, 16, 16), 255);
+        assert_eq!(alpha(&icon, 16, 8), 0);
+    }
+
+    #[test]
+    fn test_generate_default_icon_circle() {
+        let icon = generate_default_icon(false, ReticleType::Circle);
+        assert_eq!(alpha(&icon, 16, 16), 0);
+        assert_eq!(alpha(&icon, 25, 16), 255);
+        assert_eq!(alpha(&icon, 16, 7), 255);
+    }
+
+    #[test]
+    fn test_generate_default_icon_cross() {
+        let icon = generate_default_icon(false, ReticleType::Cross);
+        assert_eq!(alpha(&icon, 16, 10), 255);
+        assert_eq!(alpha(&icon, 22, 16), 255);
+        assert_eq!(alpha(&icon, 10, 10), 0);
+    }
+
+    #[test]
+    fn test_generate_default_icon_none() {
+        let icon = generate_default_icon(false, ReticleType::None);
+        assert!(icon.rgba().chunks(4).all(|pixel| pixel[3] == 0));
     }
 }
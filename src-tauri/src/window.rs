@@ -4,8 +4,16 @@
 //! a transparent, click-through overlay window that stays on top of all
 //! other windows, including fullscreen applications.
 
+use crate::config::{
+    CLAMP_MARGIN_PX, DISPLAY_POLL_INTERVAL_MS, MAX_WINDOW_HEIGHT, MAX_WINDOW_WIDTH,
+    MIN_WINDOW_HEIGHT, MIN_WINDOW_WIDTH, WINDOW_ASPECT_RATIO, WINDOW_LEVEL_NORMAL,
+    WINDOW_LEVEL_SCREEN_SAVER,
+};
+use crate::state::{AppState, LockMode};
 use log::{debug, info};
-use tauri::{Monitor, WebviewWindow};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Monitor, WebviewWindow};
 
 #[cfg(target_os = "linux")]
 use log::warn;
@@ -17,13 +25,18 @@ use log::warn;
 /// - Stay on top of all windows (including fullscreen)
 /// - Be visible on all workspaces/virtual desktops
 /// - Initially accept mouse events (unlocked state)
+///
+/// Safe to call more than once: windows that were already set up are skipped.
 pub fn setup_overlay_window(window: &WebviewWindow) -> Result<(), String> {
-    info!("Setting up overlay window: {}", window.label());
+    let state = window.try_state::<Arc<AppState>>();
+    if state
+        .as_ref()
+        .is_some_and(|state| state.is_window_setup_complete(window.label()))
+    {
+        return Ok(());
+    }
 
-    // Ensure window is always on top with highest level
-    window
-        .set_always_on_top(true)
-        .map_err(|e| format!("Failed to set always on top: {}", e))?;
+    info!("Setting up overlay window: {}", window.label());
 
     // Make visible on all workspaces
     window
@@ -40,10 +53,132 @@ pub fn setup_overlay_window(window: &WebviewWindow) -> Result<(), String> {
     #[cfg(target_os = "linux")]
     setup_linux_overlay(window)?;
 
+    // Stay on top using the configured level
+    let level = state
+        .as_ref()
+        .map(|state| state.get_window_level())
+        .unwrap_or(WINDOW_LEVEL_SCREEN_SAVER);
+    apply_window_level(window, level)?;
+
+    if let Some(state) = state {
+        state.mark_window_setup_complete(window.label());
+    }
+
     info!("Overlay window setup complete");
     Ok(())
 }
 
+/// Apply an overlay window level (see `config::WINDOW_LEVEL_*`)
+pub fn apply_window_level(window: &WebviewWindow, level: u8) -> Result<(), String> {
+    window
+        .set_always_on_top(level != WINDOW_LEVEL_NORMAL)
+        .map_err(|e| format!("Failed to set always on top: {}", e))?;
+
+    #[cfg(target_os = "macos")]
+    set_macos_window_level(window, level)?;
+
+    #[cfg(target_os = "windows")]
+    set_windows_window_level(window, level)?;
+
+    debug!("Window {} level set to {}", window.label(), level);
+    Ok(())
+}
+
+/// Raise a window above other overlays, or drop it below them
+pub fn set_z_order(window: &WebviewWindow, front: bool) -> Result<(), String> {
+    window
+        .set_always_on_top(front)
+        .map_err(|e| format!("Failed to set always on top: {}", e))?;
+
+    #[cfg(target_os = "windows")]
+    {
+        use windows::Win32::Foundation::HWND;
+        use windows::Win32::UI::WindowsAndMessaging::{
+            SetWindowPos, HWND_NOTOPMOST, HWND_TOP, SWP_NOMOVE, SWP_NOSIZE,
+        };
+
+        let hwnd = window.hwnd().map_err(|e| e.to_string())?;
+        let insert_after = if front { HWND_TOP } else { HWND_NOTOPMOST };
+        unsafe {
+            SetWindowPos(
+                HWND(hwnd.0),
+                insert_after,
+                0,
+                0,
+                0,
+                0,
+                SWP_NOMOVE | SWP_NOSIZE,
+            )
+            .map_err(|e| format!("Failed to set window position: {}", e))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Payload of the `fade-in` / `fade-out` events
+#[derive(Debug, Clone, Copy, serde::Serialize)]
+struct FadeEvent {
+    duration_ms: u64,
+}
+
+/// Show or hide the main window and all shadow windows
+///
+/// When a fade duration is configured, windows are shown before the
+/// `fade-in` event is emitted, and hidden only after the `fade-out`
+/// animation has had time to finish.
+pub fn apply_visibility(app: &AppHandle, visible: bool) -> Result<(), String> {
+    let state = app.state::<Arc<AppState>>();
+    let duration_ms = state.get_fade_duration();
+
+    if visible {
+        set_windows_shown(app, &state, true)?;
+        if duration_ms > 0 {
+            app.emit("fade-in", FadeEvent { duration_ms })
+                .map_err(|e| e.to_string())?;
+        }
+        return Ok(());
+    }
+
+    if duration_ms == 0 {
+        return set_windows_shown(app, &state, false);
+    }
+
+    app.emit("fade-out", FadeEvent { duration_ms })
+        .map_err(|e| e.to_string())?;
+
+    let app = app.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(duration_ms));
+        let state = app.state::<Arc<AppState>>();
+        // Skip if the crosshair was shown again while fading out
+        if !state.is_visible() {
+            if let Err(e) = set_windows_shown(&app, &state, false) {
+                debug!("Failed to hide windows after fade: {}", e);
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Immediately show or hide the main window and all shadow windows
+pub fn set_windows_shown(app: &AppHandle, state: &AppState, shown: bool) -> Result<(), String> {
+    let labels = std::iter::once("main".to_string()).chain(state.get_shadow_windows());
+
+    for label in labels {
+        if let Some(window) = app.get_webview_window(&label) {
+            if shown {
+                window.show().map_err(|e| e.to_string())?;
+            } else {
+                window.hide().map_err(|e| e.to_string())?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// Set the click-through (ignore mouse events) state of a window
 ///
 /// When `enabled` is true, all mouse events pass through the window
@@ -62,8 +197,202 @@ pub fn set_click_through(window: &WebviewWindow, enabled: bool) -> Result<(), St
     Ok(())
 }
 
+/// Apply a lock mode's click-through behaviour to a window
+pub fn apply_lock_mode(window: &WebviewWindow, mode: LockMode) -> Result<(), String> {
+    set_click_through(window, mode.is_click_through())
+}
+
+/// Structured metadata about a connected display
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DisplayInfo {
+    /// Monitor name reported by the OS
+    pub name: String,
+
+    /// Index in the list of available monitors
+    pub index: usize,
+
+    /// Width in physical pixels
+    pub width: u32,
+
+    /// Height in physical pixels
+    pub height: u32,
+
+    /// X position of the top-left corner
+    pub x: i32,
+
+    /// Y position of the top-left corner
+    pub y: i32,
+
+    /// DPI scale factor
+    pub scale_factor: f64,
+
+    /// Whether this is the primary monitor
+    pub is_primary: bool,
+}
+
+/// List all available displays
+pub fn get_display_info(window: &WebviewWindow) -> Result<Vec<DisplayInfo>, String> {
+    let monitors: Vec<Monitor> = window
+        .available_monitors()
+        .map_err(|e| format!("Failed to get monitors: {}", e))?;
+
+    let primary_name = window
+        .primary_monitor()
+        .map_err(|e| format!("Failed to get primary monitor: {}", e))?
+        .and_then(|m| m.name().cloned());
+
+    Ok(monitors
+        .iter()
+        .enumerate()
+        .map(|(index, monitor)| {
+            let name = monitor.name().cloned().unwrap_or_default();
+            DisplayInfo {
+                is_primary: primary_name.as_ref() == Some(&name),
+                name,
+                index,
+                width: monitor.size().width,
+                height: monitor.size().height,
+                x: monitor.position().x,
+                y: monitor.position().y,
+                scale_factor: monitor.scale_factor(),
+            }
+        })
+        .collect())
+}
+
+/// Move the window to the display at `index`, centering it there
+pub fn move_to_display(window: &WebviewWindow, index: usize) -> Result<(), String> {
+    let monitors: Vec<Monitor> = window
+        .available_monitors()
+        .map_err(|e| format!("Failed to get monitors: {}", e))?;
+
+    let monitor = monitors
+        .get(index)
+        .ok_or_else(|| format!("No display at index {}", index))?;
+
+    center_on_monitor(window, monitor)
+}
+
+/// Build a comparable signature for each monitor in a layout
+fn display_signature(monitors: &[Monitor]) -> Vec<String> {
+    monitors
+        .iter()
+        .map(|m| {
+            format!(
+                "{}@{},{} {}x{}",
+                m.name().map(String::as_str).unwrap_or("Unknown"),
+                m.position().x,
+                m.position().y,
+                m.size().width,
+                m.size().height
+            )
+        })
+        .collect()
+}
+
+/// Start a background thread that watches for display layout changes
+///
+/// When monitors are connected, disconnected or rearranged a
+/// `displays-changed` event is emitted, and the crosshair is re-centered
+/// if `auto_center_on_display_change` is enabled.
+pub fn start_display_watcher(app: &AppHandle) {
+    let app = app.clone();
+
+    std::thread::spawn(move || loop {
+        if let Err(e) = check_displays(&app) {
+            debug!("Display check failed: {}", e);
+        }
+        std::thread::sleep(Duration::from_millis(DISPLAY_POLL_INTERVAL_MS));
+    });
+
+    info!("Display watcher started");
+}
+
+/// Compare the current display layout against the last known one
+fn check_displays(app: &AppHandle) -> Result<(), String> {
+    let window = app
+        .get_webview_window("main")
+        .ok_or("Main window not found")?;
+
+    let monitors: Vec<Monitor> = window
+        .available_monitors()
+        .map_err(|e| format!("Failed to get monitors: {}", e))?;
+
+    let state = app.state::<Arc<AppState>>();
+    if !state.update_known_displays(display_signature(&monitors)) {
+        return Ok(());
+    }
+
+    info!("Display layout changed ({} monitors)", monitors.len());
+
+    app.emit("displays-changed", get_display_info(&window)?)
+        .map_err(|e| e.to_string())?;
+
+    if state.get_auto_center_on_display_change() {
+        center_on_current_monitor(&window)?;
+    }
+
+    Ok(())
+}
+
+/// Top-left position that centers a window of `window_size` on a monitor
+pub fn centered_position(
+    monitor_pos: (i32, i32),
+    monitor_size: (u32, u32),
+    window_size: (u32, u32),
+) -> (i32, i32) {
+    (
+        monitor_pos.0 + (monitor_size.0 as i32 - window_size.0 as i32) / 2,
+        monitor_pos.1 + (monitor_size.1 as i32 - window_size.1 as i32) / 2,
+    )
+}
+
+/// Center the window on the given monitor
+fn center_on_monitor(window: &WebviewWindow, monitor: &Monitor) -> Result<(), String> {
+    // The overlay is borderless, but some window managers (e.g. GNOME) still
+    // add invisible chrome to the outer size. Center the visible content.
+    let window_size = window
+        .inner_size()
+        .map_err(|e| format!("Failed to get window size: {}", e))?;
+
+    let monitor_pos = monitor.position();
+    let monitor_size = monitor.size();
+    let (new_x, new_y) = centered_position(
+        (monitor_pos.x, monitor_pos.y),
+        (monitor_size.width, monitor_size.height),
+        (window_size.width, window_size.height),
+    );
+
+    window
+        .set_position(tauri::Position::Physical(tauri::PhysicalPosition {
+            x: new_x,
+            y: new_y,
+        }))
+        .map_err(|e| format!("Failed to move window: {}", e))?;
+
+    info!(
+        "Moved window to monitor {} at ({}, {})",
+        monitor.name().unwrap_or(&"Unknown".to_string()),
+        new_x,
+        new_y
+    );
+
+    Ok(())
+}
+
+/// Index of the monitor after `current`, and whether it wrapped back to the first
+///
+/// With a single monitor there is nowhere to wrap to, so it never reports a wrap.
+pub fn next_display_index(current: usize, count: usize) -> (usize, bool) {
+    let next = (current + 1) % count;
+    (next, count > 1 && next <= current)
+}
+
 /// Move the window to the next display/monitor
-pub fn move_to_next_display(window: &WebviewWindow) -> Result<(), String> {
+///
+/// Returns the monitor's name and whether the move wrapped back to the first
+/// monitor.
+pub fn move_to_next_display(window: &WebviewWindow) -> Result<(Option<String>, bool), String> {
     // Get all available monitors
     let monitors: Vec<Monitor> = window
         .available_monitors()
@@ -86,82 +415,514 @@ pub fn move_to_next_display(window: &WebviewWindow) -> Result<(), String> {
         .unwrap_or(0);
 
     // Get next monitor (wrap around)
-    let next_index = (current_index + 1) % monitors.len();
+    let (next_index, wrapped) = next_display_index(current_index, monitors.len());
     let next_monitor = &monitors[next_index];
 
-    // Get window size
-    let window_size = window
-        .outer_size()
-        .map_err(|e| format!("Failed to get window size: {}", e))?;
+    // Center the window on the next monitor
+    center_on_monitor(window, next_monitor)?;
+    Ok((next_monitor.name().cloned(), wrapped))
+}
+
+/// Move the main window to the next display, optionally taking shadows along
+///
+/// Shadow windows keep their offset from the main window. When the main window
+/// wraps back to the first monitor and `sync_shadows_to_main_monitor` is on,
+/// every shadow window is centered on that monitor instead. If the destination
+/// monitor has its own crosshair, it is switched to.
+pub fn move_main_to_next_display(app: &AppHandle, include_shadows: bool) -> Result<(), String> {
+    let Some(main) = app.get_webview_window("main") else {
+        return Ok(());
+    };
+    let state = app.state::<Arc<AppState>>();
+
+    let before = main
+        .outer_position()
+        .map_err(|e| format!("Failed to get window position: {}", e))?;
+
+    let mut shadows = Vec::new();
+    if include_shadows {
+        for label in state.get_shadow_windows() {
+            if let Some(shadow) = app.get_webview_window(&label) {
+                let position = shadow
+                    .outer_position()
+                    .map_err(|e| format!("Failed to get window position: {}", e))?;
+                shadows.push((shadow, position.x - before.x, position.y - before.y));
+            }
+        }
+    }
+
+    let (monitor_name, wrapped) = move_to_next_display(&main)?;
+
+    if wrapped && state.get_sync_shadows_to_main_monitor() {
+        let monitors = main
+            .available_monitors()
+            .map_err(|e| format!("Failed to get monitors: {}", e))?;
+        if let Some(first) = monitors.first() {
+            for label in state.get_shadow_windows() {
+                if let Some(shadow) = app.get_webview_window(&label) {
+                    center_on_monitor(&shadow, first)?;
+                }
+            }
+        }
+        shadows.clear();
+    }
 
-    // Calculate center position on next monitor
-    let monitor_pos = next_monitor.position();
-    let monitor_size = next_monitor.size();
+    let after = main
+        .outer_position()
+        .map_err(|e| format!("Failed to get window position: {}", e))?;
+    for (shadow, dx, dy) in shadows {
+        shadow
+            .set_position(tauri::Position::Physical(tauri::PhysicalPosition {
+                x: after.x + dx,
+                y: after.y + dy,
+            }))
+            .map_err(|e| format!("Failed to move window: {}", e))?;
+    }
 
-    let new_x = monitor_pos.x + (monitor_size.width as i32 - window_size.width as i32) / 2;
-    let new_y = monitor_pos.y + (monitor_size.height as i32 - window_size.height as i32) / 2;
+    if let Some(crosshair) = monitor_name.and_then(|name| state.get_monitor_crosshair(&name)) {
+        crate::commands::apply_crosshair(app, &state, crosshair)?;
+    }
+
+    Ok(())
+}
+
+/// Undo or redo the last move of the main window
+pub fn step_position_history(app: &AppHandle, undo: bool) -> Result<(), String> {
+    let window = app
+        .get_webview_window("main")
+        .ok_or("Main window not found")?;
+    let current = window
+        .outer_position()
+        .map_err(|e| format!("Failed to get window position: {}", e))?;
+
+    let state = app.state::<Arc<AppState>>();
+    let (x, y) = if undo {
+        state
+            .undo_position((current.x, current.y))
+            .ok_or("Nothing to undo")?
+    } else {
+        state
+            .redo_position((current.x, current.y))
+            .ok_or("Nothing to redo")?
+    };
+
+    window
+        .set_position(tauri::Position::Physical(tauri::PhysicalPosition { x, y }))
+        .map_err(|e| format!("Failed to move window: {}", e))
+}
+
+/// Move the window by a relative offset
+///
+/// Logical offsets are scaled by the monitor's DPI so a move covers the same
+/// visible distance on every display.
+pub fn move_window_by(
+    window: &WebviewWindow,
+    dx: i32,
+    dy: i32,
+    logical: bool,
+) -> Result<(), String> {
+    let position = window
+        .outer_position()
+        .map_err(|e| format!("Failed to get window position: {}", e))?;
+    let scale_factor = if logical {
+        window
+            .scale_factor()
+            .map_err(|e| format!("Failed to get scale factor: {}", e))?
+    } else {
+        1.0
+    };
+    let (dx, dy) = physical_delta(dx, dy, scale_factor);
 
-    // Move window
     window
         .set_position(tauri::Position::Physical(tauri::PhysicalPosition {
-            x: new_x,
-            y: new_y,
+            x: position.x + dx,
+            y: position.y + dy,
         }))
         .map_err(|e| format!("Failed to move window: {}", e))?;
 
-    info!(
-        "Moved window to monitor {} at ({}, {})",
-        next_monitor.name().unwrap_or(&"Unknown".to_string()),
-        new_x,
-        new_y
+    let clamp = window
+        .try_state::<Arc<AppState>>()
+        .is_some_and(|state| state.get_clamp_to_monitor());
+    if clamp {
+        clamp_window_to_monitor(window)?;
+    }
+
+    Ok(())
+}
+
+/// Pull the window back so that part of it stays on the nearest monitor
+pub fn clamp_window_to_monitor(window: &WebviewWindow) -> Result<(), String> {
+    let position = window
+        .outer_position()
+        .map_err(|e| format!("Failed to get window position: {}", e))?;
+    let size = window
+        .outer_size()
+        .map_err(|e| format!("Failed to get window size: {}", e))?;
+    let displays: Vec<(i32, i32, u32, u32)> = window
+        .available_monitors()
+        .map_err(|e| format!("Failed to get monitors: {}", e))?
+        .iter()
+        .map(|m| {
+            (
+                m.position().x,
+                m.position().y,
+                m.size().width,
+                m.size().height,
+            )
+        })
+        .collect();
+
+    let bounds = WindowBounds {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    };
+    let Some((x, y)) = clamp_to_displays(bounds, &displays, CLAMP_MARGIN_PX) else {
+        return Ok(());
+    };
+    if (x, y) == (position.x, position.y) {
+        return Ok(());
+    }
+
+    debug!(
+        "Clamping window from ({}, {}) to ({}, {})",
+        position.x, position.y, x, y
     );
+    window
+        .set_position(tauri::Position::Physical(tauri::PhysicalPosition { x, y }))
+        .map_err(|e| format!("Failed to move window: {}", e))
+}
+
+/// Position that keeps at least `margin` pixels of the window on the display
+/// nearest to its center
+///
+/// Each display is `(x, y, width, height)` in physical pixels. Returns `None`
+/// if there are no displays.
+pub fn clamp_to_displays(
+    bounds: WindowBounds,
+    displays: &[(i32, i32, u32, u32)],
+    margin: i32,
+) -> Option<(i32, i32)> {
+    let center_x = bounds.x + bounds.width as i32 / 2;
+    let center_y = bounds.y + bounds.height as i32 / 2;
+
+    let &(dx, dy, width, height) = displays.iter().min_by_key(|&&(dx, dy, width, height)| {
+        let outside_x = (dx - center_x).max(center_x - (dx + width as i32)).max(0) as i64;
+        let outside_y = (dy - center_y).max(center_y - (dy + height as i32)).max(0) as i64;
+        outside_x * outside_x + outside_y * outside_y
+    })?;
+
+    // Never require more of the window on screen than it has
+    let margin_x = margin.min(bounds.width as i32);
+    let margin_y = margin.min(bounds.height as i32);
+
+    let x = bounds.x.clamp(
+        dx + margin_x - bounds.width as i32,
+        dx + width as i32 - margin_x,
+    );
+    let y = bounds.y.clamp(
+        dy + margin_y - bounds.height as i32,
+        dy + height as i32 - margin_y,
+    );
+    Some((x, y))
+}
+
+/// Convert a logical offset to physical pixels
+pub fn physical_delta(dx: i32, dy: i32, scale_factor: f64) -> (i32, i32) {
+    (
+        (dx as f64 * scale_factor).round() as i32,
+        (dy as f64 * scale_factor).round() as i32,
+    )
+}
 
+/// Window position and size in physical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct WindowBounds {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl WindowBounds {
+    /// Check that the size is within the configured window limits
+    pub fn validate(&self) -> Result<(), String> {
+        validate_window_size(self.width, self.height)
+    }
+}
+
+/// Check that a window size is within the configured limits
+pub fn validate_window_size(width: u32, height: u32) -> Result<(), String> {
+    if !(MIN_WINDOW_WIDTH..=MAX_WINDOW_WIDTH).contains(&width) {
+        return Err(format!(
+            "Width {} must be between {} and {}",
+            width, MIN_WINDOW_WIDTH, MAX_WINDOW_WIDTH
+        ));
+    }
+    if !(MIN_WINDOW_HEIGHT..=MAX_WINDOW_HEIGHT).contains(&height) {
+        return Err(format!(
+            "Height {} must be between {} and {}",
+            height, MIN_WINDOW_HEIGHT, MAX_WINDOW_HEIGHT
+        ));
+    }
     Ok(())
 }
 
-/// Move the window by a relative offset
-pub fn move_window_by(window: &WebviewWindow, dx: i32, dy: i32) -> Result<(), String> {
+/// Size to apply for a requested window size
+///
+/// With the aspect ratio locked, the height follows the width.
+pub fn window_size_for(width: u32, height: u32, lock_aspect_ratio: bool) -> (u32, u32) {
+    if lock_aspect_ratio {
+        (width, (width as f64 / WINDOW_ASPECT_RATIO).round() as u32)
+    } else {
+        (width, height)
+    }
+}
+
+/// Resize a window, returning the size that was applied
+pub fn set_window_size(
+    window: &WebviewWindow,
+    width: u32,
+    height: u32,
+    lock_aspect_ratio: bool,
+) -> Result<(u32, u32), String> {
+    let (width, height) = window_size_for(width, height, lock_aspect_ratio);
+    validate_window_size(width, height)?;
+
+    window
+        .set_size(tauri::Size::Physical(tauri::PhysicalSize { width, height }))
+        .map_err(|e| format!("Failed to resize window: {}", e))?;
+    Ok((width, height))
+}
+
+/// Resize the main window to fit the crosshair size
+///
+/// The window grows or shrinks around its center so the crosshair stays on
+/// the same spot.
+pub fn resize_to_crosshair(app: &AppHandle, size: u32) -> Result<(), String> {
+    let Some(window) = app.get_webview_window("main") else {
+        return Ok(());
+    };
+
+    let bounds = get_window_bounds(&window)?;
+    let width = size.clamp(MIN_WINDOW_WIDTH, MAX_WINDOW_WIDTH);
+    let height = size.clamp(MIN_WINDOW_HEIGHT, MAX_WINDOW_HEIGHT);
+    let (x, y) = centered_position(
+        (bounds.x, bounds.y),
+        (bounds.width, bounds.height),
+        (width, height),
+    );
+    set_window_bounds(
+        &window,
+        WindowBounds {
+            x,
+            y,
+            width,
+            height,
+        },
+    )
+}
+
+/// Get the outer position and size of a window
+pub fn get_window_bounds(window: &WebviewWindow) -> Result<WindowBounds, String> {
     let position = window
         .outer_position()
         .map_err(|e| format!("Failed to get window position: {}", e))?;
+    let size = window
+        .outer_size()
+        .map_err(|e| format!("Failed to get window size: {}", e))?;
+
+    Ok(WindowBounds {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    })
+}
+
+/// Set the position and size of a window
+pub fn set_window_bounds(window: &WebviewWindow, bounds: WindowBounds) -> Result<(), String> {
+    bounds.validate()?;
 
     window
         .set_position(tauri::Position::Physical(tauri::PhysicalPosition {
-            x: position.x + dx,
-            y: position.y + dy,
+            x: bounds.x,
+            y: bounds.y,
         }))
         .map_err(|e| format!("Failed to move window: {}", e))?;
 
+    window
+        .set_size(tauri::Size::Physical(tauri::PhysicalSize {
+            width: bounds.width,
+            height: bounds.height,
+        }))
+        .map_err(|e| format!("Failed to resize window: {}", e))?;
+
     Ok(())
 }
 
-/// Center the window on its current monitor
-pub fn center_on_current_monitor(window: &WebviewWindow) -> Result<(), String> {
-    let monitor = window
+/// Check whether a point lies within any of the given display rectangles
+///
+/// Each rectangle is `(x, y, width, height)` in physical pixels.
+pub fn is_point_on_displays(x: i32, y: i32, displays: &[(i32, i32, u32, u32)]) -> bool {
+    displays.iter().any(|&(dx, dy, width, height)| {
+        x >= dx && y >= dy && x < dx + width as i32 && y < dy + height as i32
+    })
+}
+
+/// Top-left position of a window centered in cell `index` of a grid
+///
+/// The area `(x, y, width, height)` is split into `columns` columns and as
+/// many rows as needed for `count` cells, with `padding` pixels around each
+/// cell. Returns `None` if the window doesn't fit in a cell.
+pub fn grid_cell_position(
+    index: usize,
+    count: usize,
+    columns: u32,
+    padding: u32,
+    area: (i32, i32, u32, u32),
+    window_size: (u32, u32),
+) -> Option<(i32, i32)> {
+    let columns = columns.max(1) as usize;
+    let rows = count.div_ceil(columns).max(1);
+    let (area_x, area_y, area_width, area_height) = area;
+    let (width, height) = window_size;
+
+    let padding = padding as usize;
+    let cell_width = (area_width as usize).checked_sub(padding * (columns + 1))? / columns;
+    let cell_height = (area_height as usize).checked_sub(padding * (rows + 1))? / rows;
+    if width as usize > cell_width || height as usize > cell_height {
+        return None;
+    }
+
+    let column = index % columns;
+    let row = index / columns;
+    let cell_x = padding + column * (cell_width + padding);
+    let cell_y = padding + row * (cell_height + padding);
+
+    Some((
+        area_x + (cell_x + (cell_width - width as usize) / 2) as i32,
+        area_y + (cell_y + (cell_height - height as usize) / 2) as i32,
+    ))
+}
+
+/// Payload of the `window-arranged` event
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct WindowArranged {
+    pub label: String,
+    pub x: i32,
+    pub y: i32,
+}
+
+/// Lay out windows in a grid on the first window's monitor
+///
+/// Windows that don't fit in a cell are centered on their monitor instead.
+pub fn arrange_in_grid(
+    windows: &[WebviewWindow],
+    columns: u32,
+    padding: u32,
+) -> Result<Vec<WindowArranged>, String> {
+    let Some(first) = windows.first() else {
+        return Ok(Vec::new());
+    };
+    let monitor = first
         .current_monitor()
         .map_err(|e| format!("Failed to get current monitor: {}", e))?
         .ok_or("No current monitor")?;
+    let area = (
+        monitor.position().x,
+        monitor.position().y,
+        monitor.size().width,
+        monitor.size().height,
+    );
 
-    let window_size = window
-        .outer_size()
-        .map_err(|e| format!("Failed to get window size: {}", e))?;
+    let mut arranged = Vec::with_capacity(windows.len());
+    for (index, window) in windows.iter().enumerate() {
+        let size = window
+            .outer_size()
+            .map_err(|e| format!("Failed to get window size: {}", e))?;
 
-    let monitor_pos = monitor.position();
-    let monitor_size = monitor.size();
+        match grid_cell_position(
+            index,
+            windows.len(),
+            columns,
+            padding,
+            area,
+            (size.width, size.height),
+        ) {
+            Some((x, y)) => window
+                .set_position(tauri::Position::Physical(tauri::PhysicalPosition { x, y }))
+                .map_err(|e| format!("Failed to move window: {}", e))?,
+            None => center_on_current_monitor(window)?,
+        }
+
+        let position = window
+            .outer_position()
+            .map_err(|e| format!("Failed to get window position: {}", e))?;
+        arranged.push(WindowArranged {
+            label: window.label().to_string(),
+            x: position.x,
+            y: position.y,
+        });
+    }
 
-    let new_x = monitor_pos.x + (monitor_size.width as i32 - window_size.width as i32) / 2;
-    let new_y = monitor_pos.y + (monitor_size.height as i32 - window_size.height as i32) / 2;
+    Ok(arranged)
+}
+
+/// Restore the window to a saved position
+///
+/// Falls back to centering on the current monitor if the position is not
+/// on any connected display.
+pub fn restore_position(window: &WebviewWindow, x: i32, y: i32) -> Result<(), String> {
+    let displays: Vec<(i32, i32, u32, u32)> = window
+        .available_monitors()
+        .map_err(|e| format!("Failed to get monitors: {}", e))?
+        .iter()
+        .map(|m| {
+            (
+                m.position().x,
+                m.position().y,
+                m.size().width,
+                m.size().height,
+            )
+        })
+        .collect();
+
+    if !is_point_on_displays(x, y, &displays) {
+        info!("Saved position ({}, {}) is off-screen, centering", x, y);
+        return center_on_current_monitor(window);
+    }
 
     window
-        .set_position(tauri::Position::Physical(tauri::PhysicalPosition {
-            x: new_x,
-            y: new_y,
-        }))
-        .map_err(|e| format!("Failed to center window: {}", e))?;
+        .set_position(tauri::Position::Physical(tauri::PhysicalPosition { x, y }))
+        .map_err(|e| format!("Failed to restore window position: {}", e))?;
 
+    info!("Restored window position to ({}, {})", x, y);
     Ok(())
 }
 
+/// Center every shadow window on the monitor it is currently on
+pub fn center_shadow_windows(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<Arc<AppState>>();
+    for label in state.get_shadow_windows() {
+        if let Some(shadow) = app.get_webview_window(&label) {
+            center_on_current_monitor(&shadow)?;
+        }
+    }
+    Ok(())
+}
+
+/// Center the window on its current monitor
+pub fn center_on_current_monitor(window: &WebviewWindow) -> Result<(), String> {
+    let monitor = window
+        .current_monitor()
+        .map_err(|e| format!("Failed to get current monitor: {}", e))?
+        .ok_or("No current monitor")?;
+
+    center_on_monitor(window, &monitor)
+}
+
 // ============================================================================
 // Platform-specific implementations
 // ============================================================================
@@ -169,7 +930,7 @@ pub fn center_on_current_monitor(window: &WebviewWindow) -> Result<(), String> {
 /// macOS-specific overlay window setup using objc2
 #[cfg(target_os = "macos")]
 fn setup_macos_overlay(window: &WebviewWindow) -> Result<(), String> {
-    use objc2_app_kit::{NSWindow, NSWindowCollectionBehavior};
+    use objc2_app_kit::NSWindow;
 
     info!("Applying macOS-specific overlay settings");
 
@@ -180,20 +941,8 @@ fn setup_macos_overlay(window: &WebviewWindow) -> Result<(), String> {
         // Cast the raw pointer to NSWindow
         let ns_window: &NSWindow = &*(ns_window_ptr as *const NSWindow);
 
-        // Set window level to be above screen savers and fullscreen apps
-        // CGWindowLevelForKey(kCGScreenSaverWindowLevelKey) is typically 1000
-        // We set it to 1001 to be above screen savers
-        let screen_saver_level: isize = 1001;
-        ns_window.setLevel(screen_saver_level);
-
         // Set collection behavior to work with fullscreen apps and spaces
-        // NSWindowCollectionBehaviorCanJoinAllSpaces: Window appears on all spaces
-        // NSWindowCollectionBehaviorStationary: Window doesn't move when switching spaces
-        // NSWindowCollectionBehaviorFullScreenAuxiliary: Works with fullscreen apps
-        let behavior = NSWindowCollectionBehavior::CanJoinAllSpaces
-            | NSWindowCollectionBehavior::Stationary
-            | NSWindowCollectionBehavior::FullScreenAuxiliary;
-        ns_window.setCollectionBehavior(behavior);
+        ns_window.setCollectionBehavior(overlay_collection_behavior());
 
         // Prevent window from hiding when app is deactivated
         ns_window.setHidesOnDeactivate(false);
@@ -209,13 +958,95 @@ fn setup_macos_overlay(window: &WebviewWindow) -> Result<(), String> {
     Ok(())
 }
 
+/// Map a window level preference to an NSWindowLevel
+#[cfg(target_os = "macos")]
+fn set_macos_window_level(window: &WebviewWindow, level: u8) -> Result<(), String> {
+    use crate::config::WINDOW_LEVEL_FLOATING;
+    use objc2_app_kit::NSWindow;
+
+    // NSNormalWindowLevel is 0, NSFloatingWindowLevel is 3 and
+    // NSScreenSaverWindowLevel is 1000, so 1001 is above screen savers
+    let ns_level: isize = match level {
+        WINDOW_LEVEL_NORMAL => 0,
+        WINDOW_LEVEL_FLOATING => 3,
+        _ => 1001,
+    };
+
+    let ns_window_ptr = window.ns_window().map_err(|e| e.to_string())?;
+    unsafe {
+        let ns_window: &NSWindow = &*(ns_window_ptr as *const NSWindow);
+        ns_window.setLevel(ns_level);
+    }
+
+    Ok(())
+}
+
+/// Collection behavior for overlay windows on macOS
+///
+/// - CanJoinAllSpaces: Window appears on all spaces
+/// - Stationary: Window isn't moved or hidden by Mission Control
+/// - FullScreenAuxiliary: Works with fullscreen apps
+/// - IgnoresCycle: Window is skipped when cycling with Cmd+`
+///
+/// MoveToActiveSpace can't be used as AppKit rejects it combined with
+/// CanJoinAllSpaces; `start_space_observer` re-raises windows instead.
+#[cfg(target_os = "macos")]
+fn overlay_collection_behavior() -> objc2_app_kit::NSWindowCollectionBehavior {
+    use objc2_app_kit::NSWindowCollectionBehavior;
+
+    NSWindowCollectionBehavior::CanJoinAllSpaces
+        | NSWindowCollectionBehavior::Stationary
+        | NSWindowCollectionBehavior::FullScreenAuxiliary
+        | NSWindowCollectionBehavior::IgnoresCycle
+}
+
+/// Re-raise overlay windows and emit `space-changed` when the active space changes
+#[cfg(target_os = "macos")]
+pub fn start_space_observer(app: &AppHandle) {
+    use block2::RcBlock;
+    use objc2_app_kit::{NSWorkspace, NSWorkspaceActiveSpaceDidChangeNotification};
+    use objc2_foundation::NSNotification;
+    use std::ptr::NonNull;
+
+    let app = app.clone();
+    let block = RcBlock::new(move |_notification: NonNull<NSNotification>| {
+        debug!("Active space changed");
+
+        let level = app.state::<Arc<AppState>>().get_window_level();
+        for window in app.webview_windows().values() {
+            if window.is_visible().unwrap_or(false) {
+                if let Err(e) = apply_window_level(window, level) {
+                    debug!("Failed to re-raise {}: {}", window.label(), e);
+                }
+            }
+        }
+
+        if let Err(e) = app.emit("space-changed", ()) {
+            debug!("Failed to emit space-changed: {}", e);
+        }
+    });
+
+    unsafe {
+        let center = NSWorkspace::sharedWorkspace().notificationCenter();
+        let observer = center.addObserverForName_object_queue_usingBlock(
+            Some(NSWorkspaceActiveSpaceDidChangeNotification),
+            None,
+            None,
+            &block,
+        );
+        // The observer lives for the rest of the app's lifetime
+        std::mem::forget(observer);
+    }
+
+    info!("Space change observer started");
+}
+
 /// Windows-specific overlay window setup
 #[cfg(target_os = "windows")]
 fn setup_windows_overlay(window: &WebviewWindow) -> Result<(), String> {
     use windows::Win32::Foundation::HWND;
     use windows::Win32::UI::WindowsAndMessaging::{
-        GetWindowLongPtrW, SetWindowLongPtrW, SetWindowPos, GWL_EXSTYLE, HWND_TOPMOST, SWP_NOMOVE,
-        SWP_NOSIZE, WS_EX_LAYERED, WS_EX_TOOLWINDOW, WS_EX_TOPMOST,
+        GetWindowLongPtrW, SetWindowLongPtrW, GWL_EXSTYLE, WS_EX_LAYERED, WS_EX_TOOLWINDOW,
     };
 
     info!("Applying Windows-specific overlay settings");
@@ -230,50 +1061,476 @@ fn setup_windows_overlay(window: &WebviewWindow) -> Result<(), String> {
         // Add layered and toolwindow styles
         // WS_EX_TOOLWINDOW: Doesn't appear in taskbar
         // WS_EX_LAYERED: Required for transparency
-        // WS_EX_TOPMOST: Always on top
         // Note: WS_EX_TRANSPARENT is controlled by set_ignore_cursor_events
-        let new_style = ex_style
-            | WS_EX_LAYERED.0 as isize
-            | WS_EX_TOOLWINDOW.0 as isize
-            | WS_EX_TOPMOST.0 as isize;
+        // Note: WS_EX_TOPMOST is controlled by set_windows_window_level
+        let new_style = ex_style | WS_EX_LAYERED.0 as isize | WS_EX_TOOLWINDOW.0 as isize;
 
         SetWindowLongPtrW(hwnd, GWL_EXSTYLE, new_style);
+    }
+
+    debug!("Windows overlay settings applied successfully");
+    Ok(())
+}
+
+/// Map a window level preference to the Win32 z-order
+///
+/// 0 is not topmost, 1 is `HWND_TOPMOST` and 2 additionally sets the
+/// `WS_EX_TOPMOST` extended style.
+#[cfg(target_os = "windows")]
+fn set_windows_window_level(window: &WebviewWindow, level: u8) -> Result<(), String> {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetWindowLongPtrW, SetWindowLongPtrW, SetWindowPos, GWL_EXSTYLE, HWND_NOTOPMOST,
+        HWND_TOPMOST, SWP_NOMOVE, SWP_NOSIZE, WS_EX_TOPMOST,
+    };
+
+    let hwnd = window.hwnd().map_err(|e| e.to_string())?;
+    let hwnd = HWND(hwnd.0);
+
+    unsafe {
+        let ex_style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+        let new_style = if level == WINDOW_LEVEL_SCREEN_SAVER {
+            ex_style | WS_EX_TOPMOST.0 as isize
+        } else {
+            ex_style & !(WS_EX_TOPMOST.0 as isize)
+        };
+        SetWindowLongPtrW(hwnd, GWL_EXSTYLE, new_style);
+
+        let insert_after = if level == WINDOW_LEVEL_NORMAL {
+            HWND_NOTOPMOST
+        } else {
+            HWND_TOPMOST
+        };
+        SetWindowPos(hwnd, insert_after, 0, 0, 0, 0, SWP_NOMOVE | SWP_NOSIZE)
+            .map_err(|e| format!("Failed to set window position: {}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Check if the OS supports the DirectX overlay workarounds
+///
+/// `WDA_EXCLUDEFROMCAPTURE` requires Windows 10 version 2004 (build 19041).
+pub fn dx_overlay_supported() -> bool {
+    if !cfg!(target_os = "windows") {
+        return false;
+    }
+
+    match tauri_plugin_os::version() {
+        tauri_plugin_os::Version::Semantic(major, _, build) => major > 10 || build >= 19041,
+        _ => false,
+    }
+}
+
+/// Apply or remove the DirectX fullscreen overlay workarounds
+///
+/// Excluding the window from capture moves it onto a separate composition
+/// path, and briefly cloaking it makes DWM re-evaluate its z-order. Note that
+/// the crosshair will no longer appear in screenshots or recordings.
+#[cfg(target_os = "windows")]
+pub fn apply_dx_overlay(window: &WebviewWindow, enabled: bool) -> Result<(), String> {
+    use windows::Win32::Foundation::{BOOL, HWND};
+    use windows::Win32::Graphics::Dwm::{DwmSetWindowAttribute, DWMWA_CLOAK};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        SetWindowDisplayAffinity, SetWindowPos, HWND_TOPMOST, SWP_NOMOVE, SWP_NOSIZE,
+        WDA_EXCLUDEFROMCAPTURE, WDA_NONE,
+    };
+
+    if enabled && !dx_overlay_supported() {
+        return Err("DirectX overlay mode requires Windows 10 version 2004 or later".to_string());
+    }
+
+    let hwnd = window.hwnd().map_err(|e| e.to_string())?;
+    let hwnd = HWND(hwnd.0);
+
+    unsafe {
+        let affinity = if enabled {
+            WDA_EXCLUDEFROMCAPTURE
+        } else {
+            WDA_NONE
+        };
+        SetWindowDisplayAffinity(hwnd, affinity)
+            .map_err(|e| format!("Failed to set display affinity: {}", e))?;
+
+        if enabled {
+            for cloak in [BOOL(1), BOOL(0)] {
+                DwmSetWindowAttribute(
+                    hwnd,
+                    DWMWA_CLOAK,
+                    &cloak as *const BOOL as *const _,
+                    std::mem::size_of::<BOOL>() as u32,
+                )
+                .map_err(|e| format!("Failed to toggle window cloak: {}", e))?;
+            }
+        }
 
-        // Ensure topmost positioning
         SetWindowPos(hwnd, HWND_TOPMOST, 0, 0, 0, 0, SWP_NOMOVE | SWP_NOSIZE)
             .map_err(|e| format!("Failed to set window position: {}", e))?;
     }
 
-    debug!("Windows overlay settings applied successfully");
+    info!(
+        "DirectX overlay mode {}",
+        if enabled { "enabled" } else { "disabled" }
+    );
+    Ok(())
+}
+
+/// DirectX overlay mode is only available on Windows
+#[cfg(not(target_os = "windows"))]
+pub fn apply_dx_overlay(_window: &WebviewWindow, enabled: bool) -> Result<(), String> {
+    if enabled {
+        return Err("DirectX overlay mode is only supported on Windows".to_string());
+    }
     Ok(())
 }
 
 /// Linux-specific overlay window setup
 #[cfg(target_os = "linux")]
-fn setup_linux_overlay(_window: &WebviewWindow) -> Result<(), String> {
+fn setup_linux_overlay(window: &WebviewWindow) -> Result<(), String> {
     info!("Applying Linux-specific overlay settings");
 
-    // Most Linux functionality is handled by Tauri's built-in APIs
-    // Additional X11/Wayland specific handling could be added here if needed
+    // Most Wayland compositors don't allow true overlay windows for security,
+    // so always-on-top and sticky behavior is left to the compositor
+    if std::env::var("WAYLAND_DISPLAY").is_ok() {
+        warn!("Linux overlay: running on Wayland, overlay behavior depends on your compositor");
+        return Ok(());
+    }
+
+    let xid = x11_window_id(window)?;
+
+    unsafe {
+        let display = x11::xlib::XOpenDisplay(std::ptr::null());
+        if display.is_null() {
+            return Err("Failed to open X11 display".to_string());
+        }
+
+        let result = set_x11_overlay_atoms(display, xid);
+        x11::xlib::XCloseDisplay(display);
+        result?;
+    }
+
+    debug!("Linux overlay settings applied successfully");
+    Ok(())
+}
+
+/// Get the X11 window ID backing a Tauri window
+#[cfg(target_os = "linux")]
+fn x11_window_id(window: &WebviewWindow) -> Result<x11::xlib::Window, String> {
+    use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+
+    let handle = window
+        .window_handle()
+        .map_err(|e| format!("Failed to get window handle: {}", e))?;
+
+    match handle.as_raw() {
+        RawWindowHandle::Xlib(handle) => Ok(handle.window),
+        RawWindowHandle::Xcb(handle) => Ok(handle.window.get() as x11::xlib::Window),
+        _ => Err("Window is not an X11 window".to_string()),
+    }
+}
+
+/// Mark an X11 window as an always-on-top, sticky utility window
+///
+/// The properties are written directly for windows that are not mapped yet,
+/// and a `_NET_WM_STATE` client message is sent to the root window so the
+/// window manager applies them to windows that already are.
+#[cfg(target_os = "linux")]
+unsafe fn set_x11_overlay_atoms(
+    display: *mut x11::xlib::Display,
+    xid: x11::xlib::Window,
+) -> Result<(), String> {
+    use std::ffi::CString;
+    use x11::xlib;
+
+    let intern = |name: &str| {
+        let name = CString::new(name).expect("atom names contain no NUL bytes");
+        xlib::XInternAtom(display, name.as_ptr(), xlib::False)
+    };
 
-    // For X11, we might want to set _NET_WM_STATE atoms for:
-    // - _NET_WM_STATE_ABOVE (always on top)
-    // - _NET_WM_STATE_STICKY (visible on all workspaces)
-    // - _NET_WM_WINDOW_TYPE_DOCK or _NET_WM_WINDOW_TYPE_UTILITY
+    let wm_state = intern("_NET_WM_STATE");
+    let states = [
+        intern("_NET_WM_STATE_ABOVE"),
+        intern("_NET_WM_STATE_STICKY"),
+    ];
+    let wm_window_type = intern("_NET_WM_WINDOW_TYPE");
+    let utility = intern("_NET_WM_WINDOW_TYPE_UTILITY");
 
-    // For Wayland, overlay behavior depends heavily on compositor support
-    // Most Wayland compositors don't allow true overlay windows for security
+    if wm_state == 0 || wm_window_type == 0 {
+        return Err("Failed to intern X11 atoms".to_string());
+    }
 
-    warn!(
-        "Linux overlay: Some features may be limited depending on your window manager/compositor"
+    xlib::XChangeProperty(
+        display,
+        xid,
+        wm_window_type,
+        xlib::XA_ATOM,
+        32,
+        xlib::PropModeReplace,
+        &utility as *const xlib::Atom as *const u8,
+        1,
+    );
+    xlib::XChangeProperty(
+        display,
+        xid,
+        wm_state,
+        xlib::XA_ATOM,
+        32,
+        xlib::PropModeReplace,
+        states.as_ptr() as *const u8,
+        states.len() as i32,
     );
-    debug!("Linux overlay settings applied");
 
+    // _NET_WM_STATE_ADD with a normal application as the source
+    let root = xlib::XDefaultRootWindow(display);
+    for state in states {
+        let mut message: xlib::XClientMessageEvent = std::mem::zeroed();
+        message.type_ = xlib::ClientMessage;
+        message.window = xid;
+        message.message_type = wm_state;
+        message.format = 32;
+        message.data.set_long(0, 1);
+        message.data.set_long(1, state as std::os::raw::c_long);
+        message.data.set_long(2, 0);
+        message.data.set_long(3, 1);
+
+        let mut event = xlib::XEvent::from(message);
+        xlib::XSendEvent(
+            display,
+            root,
+            xlib::False,
+            xlib::SubstructureRedirectMask | xlib::SubstructureNotifyMask,
+            &mut event,
+        );
+    }
+
+    xlib::XFlush(display);
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    // Unit tests would go here
     // Note: Most window tests require a running Tauri app context
+    use super::*;
+
+    #[test]
+    fn test_centered_position() {
+        assert_eq!(
+            centered_position((0, 0), (1920, 1080), (100, 100)),
+            (910, 490)
+        );
+        // Secondary monitor left of the primary
+        assert_eq!(
+            centered_position((-1280, 0), (1280, 1024), (200, 100)),
+            (-740, 462)
+        );
+        // Window larger than the monitor overhangs evenly
+        assert_eq!(
+            centered_position((0, 0), (800, 600), (1000, 700)),
+            (-100, -50)
+        );
+    }
+
+    #[test]
+    fn test_next_display_index() {
+        assert_eq!(next_display_index(0, 3), (1, false));
+        assert_eq!(next_display_index(1, 3), (2, false));
+        assert_eq!(next_display_index(2, 3), (0, true));
+        assert_eq!(next_display_index(0, 1), (0, false));
+    }
+
+    #[test]
+    fn test_grid_cell_position() {
+        let area = (0, 0, 1000, 500);
+
+        // Two columns, two rows of 485x235 cells with 10px padding
+        assert_eq!(
+            grid_cell_position(0, 3, 2, 10, area, (100, 100)),
+            Some((202, 77))
+        );
+        assert_eq!(
+            grid_cell_position(1, 3, 2, 10, area, (100, 100)),
+            Some((697, 77))
+        );
+        assert_eq!(
+            grid_cell_position(2, 3, 2, 10, area, (100, 100)),
+            Some((202, 322))
+        );
+
+        // Offset by the monitor position
+        assert_eq!(
+            grid_cell_position(0, 1, 1, 0, (1920, 0, 200, 200), (100, 100)),
+            Some((1970, 50))
+        );
+
+        // Too large for a cell
+        assert_eq!(grid_cell_position(0, 3, 2, 10, area, (500, 100)), None);
+        assert_eq!(grid_cell_position(0, 1, 1, 600, area, (10, 10)), None);
+    }
+
+    #[test]
+    fn test_window_bounds_validate() {
+        let bounds = WindowBounds {
+            x: -100,
+            y: 50,
+            width: 200,
+            height: 200,
+        };
+        assert!(bounds.validate().is_ok());
+
+        let too_small = WindowBounds {
+            width: MIN_WINDOW_WIDTH - 1,
+            ..bounds
+        };
+        assert!(too_small.validate().is_err());
+
+        let too_tall = WindowBounds {
+            height: MAX_WINDOW_HEIGHT + 1,
+            ..bounds
+        };
+        assert!(too_tall.validate().is_err());
+    }
+
+    #[test]
+    fn test_clamp_to_displays() {
+        let displays = [(0, 0, 1920, 1080), (1920, 0, 1280, 1024)];
+        let bounds = |x, y| WindowBounds {
+            x,
+            y,
+            width: 100,
+            height: 100,
+        };
+
+        assert_eq!(
+            clamp_to_displays(bounds(500, 500), &displays, 20),
+            Some((500, 500))
+        );
+        assert_eq!(
+            clamp_to_displays(bounds(-200, 500), &displays, 20),
+            Some((-80, 500))
+        );
+        assert_eq!(
+            clamp_to_displays(bounds(500, 1200), &displays, 20),
+            Some((500, 1060))
+        );
+        assert_eq!(
+            clamp_to_displays(bounds(3500, -300), &displays, 20),
+            Some((3180, -80))
+        );
+        assert_eq!(clamp_to_displays(bounds(0, 0), &[], 20), None);
+    }
+
+    #[test]
+    fn test_physical_delta() {
+        assert_eq!(physical_delta(1, -1, 2.0), (2, -2));
+        assert_eq!(physical_delta(10, 0, 2.0), (20, 0));
+        assert_eq!(physical_delta(3, 3, 1.5), (5, 5));
+        assert_eq!(physical_delta(5, -5, 1.0), (5, -5));
+    }
+
+    #[test]
+    fn test_window_size_for() {
+        assert_eq!(window_size_for(300, 150, false), (300, 150));
+        assert_eq!(window_size_for(300, 150, true), (300, 300));
+        assert!(validate_window_size(MAX_WINDOW_WIDTH + 1, 200).is_err());
+    }
+
+    #[test]
+    fn test_is_point_on_displays() {
+        let displays = [(0, 0, 1920, 1080), (1920, 0, 1280, 1024)];
+
+        assert!(is_point_on_displays(0, 0, &displays));
+        assert!(is_point_on_displays(1919, 1079, &displays));
+        assert!(is_point_on_displays(2000, 500, &displays));
+        assert!(!is_point_on_displays(2000, 1050, &displays));
+        assert!(!is_point_on_displays(-10, 10, &displays));
+        assert!(!is_point_on_displays(3200, 0, &displays));
+        assert!(!is_point_on_displays(0, 0, &[]));
+    }
+
+    #[cfg(target_os = "linux")]
+    unsafe fn read_atoms(
+        display: *mut x11::xlib::Display,
+        xid: x11::xlib::Window,
+        property: &str,
+    ) -> Vec<x11::xlib::Atom> {
+        use x11::xlib;
+
+        let name = std::ffi::CString::new(property).unwrap();
+        let property = xlib::XInternAtom(display, name.as_ptr(), xlib::False);
+
+        let mut actual_type = 0;
+        let mut actual_format = 0;
+        let mut count = 0;
+        let mut bytes_after = 0;
+        let mut data = std::ptr::null_mut();
+        xlib::XGetWindowProperty(
+            display,
+            xid,
+            property,
+            0,
+            1024,
+            xlib::False,
+            xlib::XA_ATOM,
+            &mut actual_type,
+            &mut actual_format,
+            &mut count,
+            &mut bytes_after,
+            &mut data,
+        );
+        if data.is_null() {
+            return Vec::new();
+        }
+
+        // 32-bit properties are returned as an array of longs
+        let atoms =
+            std::slice::from_raw_parts(data as *const x11::xlib::Atom, count as usize).to_vec();
+        xlib::XFree(data as *mut _);
+        atoms
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_x11_overlay_atoms() {
+        use x11::xlib;
+
+        unsafe {
+            let display = xlib::XOpenDisplay(std::ptr::null());
+            if display.is_null() {
+                // No X11 display available
+                return;
+            }
+
+            let root = xlib::XDefaultRootWindow(display);
+            let xid = xlib::XCreateSimpleWindow(display, root, 0, 0, 10, 10, 0, 0, 0);
+            set_x11_overlay_atoms(display, xid).unwrap();
+
+            let intern = |name: &str| {
+                let name = std::ffi::CString::new(name).unwrap();
+                xlib::XInternAtom(display, name.as_ptr(), xlib::False)
+            };
+
+            let states = read_atoms(display, xid, "_NET_WM_STATE");
+            assert!(states.contains(&intern("_NET_WM_STATE_ABOVE")));
+            assert!(states.contains(&intern("_NET_WM_STATE_STICKY")));
+            assert_eq!(
+                read_atoms(display, xid, "_NET_WM_WINDOW_TYPE"),
+                vec![intern("_NET_WM_WINDOW_TYPE_UTILITY")]
+            );
+
+            xlib::XDestroyWindow(display, xid);
+            xlib::XCloseDisplay(display);
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn test_overlay_collection_behavior() {
+        use objc2_app_kit::NSWindowCollectionBehavior;
+
+        let behavior = overlay_collection_behavior();
+        assert!(behavior.contains(NSWindowCollectionBehavior::CanJoinAllSpaces));
+        assert!(behavior.contains(NSWindowCollectionBehavior::Stationary));
+        assert!(behavior.contains(NSWindowCollectionBehavior::FullScreenAuxiliary));
+        assert!(!behavior.contains(NSWindowCollectionBehavior::MoveToActiveSpace));
+    }
 }
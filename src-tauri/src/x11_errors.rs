@@ -0,0 +1,50 @@
+//! Xlib error trapping
+//!
+//! Xlib's default error handler prints the error and calls `exit()`, so a
+//! request against a window that just closed or a pixel outside the screen
+//! would take the whole app down. Requests that can fail run inside
+//! [`trap_errors`], which turns protocol errors into `Err` values.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Mutex;
+use x11::xlib;
+
+/// Serializes traps, since the error handler is process-wide
+static TRAP_LOCK: Mutex<()> = Mutex::new(());
+
+/// Error code of the last trapped error, 0 if none
+static LAST_ERROR: AtomicU8 = AtomicU8::new(0);
+
+unsafe extern "C" fn record_error(
+    _display: *mut xlib::Display,
+    event: *mut xlib::XErrorEvent,
+) -> i32 {
+    LAST_ERROR.store((*event).error_code, Ordering::SeqCst);
+    0
+}
+
+/// Run Xlib calls with protocol errors recorded instead of exiting
+///
+/// Requests are flushed with `XSync` before the previous handler is
+/// restored, so every error caused by `f` is caught here.
+///
+/// # Safety
+///
+/// `display` must be an open display, and `f` must only make Xlib calls on it.
+pub unsafe fn trap_errors<T>(
+    display: *mut xlib::Display,
+    f: impl FnOnce() -> T,
+) -> Result<T, String> {
+    let _lock = TRAP_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    LAST_ERROR.store(0, Ordering::SeqCst);
+
+    let previous = xlib::XSetErrorHandler(Some(record_error));
+    let value = f();
+    xlib::XSync(display, xlib::False);
+    xlib::XSetErrorHandler(previous);
+
+    match LAST_ERROR.swap(0, Ordering::SeqCst) {
+        0 => Ok(value),
+        code => Err(format!("X11 request failed with error code {}", code)),
+    }
+}